@@ -0,0 +1,21 @@
+// Print the deterministic game fixture for a seed as JSON, with the boards
+// bound to the given player addresses and session id:
+//
+//     cargo run -p poison-game --example fixture -- 42 G...P1 G...P2 7 > fixture-42.json
+//
+// Without addresses the fixture uses two generated ones and session 1.
+
+use poison_game::testutils::generate_fixture;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{Address, Env};
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let seed = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(0);
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let owner = |i: usize| args.get(i).map(|s| Address::from_str(&env, s)).unwrap_or_else(|| Address::generate(&env));
+    let owners = [owner(2), owner(3)];
+    let session_id = args.get(4).and_then(|s| s.parse().ok()).unwrap_or(1);
+    println!("{}", generate_fixture(&env, seed, &owners, session_id).to_json());
+}
@@ -0,0 +1,124 @@
+//! # Achievements
+//!
+//! Evaluated by `finish_game` for the winner. Multi-game ("combo")
+//! conditions read the persistent `PlayerStats`, which keep the win streak,
+//! the per-day win count and the number of distinct opponents beaten for
+//! this purpose. Each achievement unlocks once per player — unlocked ids are
+//! kept as a bitmask, listed by `get_achievements` — publishes an
+//! `AchievementUnlocked` event and, when a badge contract is configured, is
+//! forwarded to it with the achievement id. A failed forward is dropped, so
+//! the badge contract cannot keep a game from finishing.
+
+use soroban_sdk::{contractclient, contractevent, contractimpl, contracttype, vec, Address, Env, IntoVal, Vec};
+
+use crate::{DataKey, Error, GameState, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::PLAYER_TTL_LEDGERS;
+
+/// Ledgers per "day" for daily conditions (~5 s ledgers).
+pub const LEDGERS_PER_DAY: u32 = 17_280;
+const DAILY_WINS_NEEDED: u32 = 3;
+const RIVALS_NEEDED:     u32 = 5;
+const STREAK_NEEDED:     u32 = 10;
+const QUICK_WIN_ATTACKS: u32 = 8; // a quick win takes fewer attacks than this
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Achievement {
+    Flawless   = 1, // won without the opponent finding any of your specials
+    TripleDay  = 2, // won 3 games within one day
+    FiveRivals = 3, // beaten 5 distinct opponents
+    FirstWin   = 4,
+    TenStreak  = 5, // won 10 games in a row
+    QuickWin   = 6, // won with fewer than 8 attacks
+}
+
+const ALL_ACHIEVEMENTS: [Achievement; 6] = [
+    Achievement::Flawless,
+    Achievement::TripleDay,
+    Achievement::FiveRivals,
+    Achievement::FirstWin,
+    Achievement::TenStreak,
+    Achievement::QuickWin,
+];
+
+#[contractevent]
+pub struct AchievementUnlocked {
+    #[topic]
+    pub player:      Address,
+    pub achievement: Achievement,
+}
+
+#[contractclient(name = "BadgeClient")]
+pub trait Badge {
+    fn award(env: Env, player: Address, achievement_id: u32);
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Forward unlocks to `badge` (or stop forwarding with None).
+    pub fn set_badge_contract(env: Env, badge: Option<Address>) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_badge_contract", vec![&env, badge.clone().into_val(&env)])?;
+        match badge {
+            Some(b) => env.storage().instance().set(&DataKey::BadgeContract, &b),
+            None    => env.storage().instance().remove(&DataKey::BadgeContract),
+        }
+        Ok(())
+    }
+
+    pub fn get_badge_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::BadgeContract)
+    }
+
+    /// Achievements `player` has unlocked, in id order.
+    pub fn get_achievements(env: Env, player: Address) -> Vec<Achievement> {
+        let mask: u32 = env.storage().persistent().get(&DataKey::Achievements(player)).unwrap_or(0);
+        let mut unlocked = vec![&env];
+        for achievement in ALL_ACHIEVEMENTS {
+            if mask & (1u32 << (achievement as u32)) != 0 { unlocked.push_back(achievement); }
+        }
+        unlocked
+    }
+}
+
+impl PoisonGameContract {
+    /// Called from `finish_game` after the result stats are recorded.
+    pub(crate) fn evaluate_achievements(env: &Env, game: &GameState) {
+        let winner = if game.winner == 1 { &game.player1 } else { &game.player2 };
+        let stats = Self::get_player_stats(env.clone(), winner.clone());
+
+        // Specials lost on the winner's board, and attacks the winner made
+        let (lost, attacks) = if game.config.lite {
+            let own  = game.lite_boards.get_unchecked(game.winner - 1);
+            let opp  = game.lite_boards.get_unchecked(2 - game.winner);
+            (own.poison + own.shield, opp.mask.count_ones())
+        } else {
+            let (own, opp) = if game.winner == 1 { (&game.p1_mask, &game.p2_mask) }
+                             else                 { (&game.p2_mask, &game.p1_mask) };
+            let (poison, shield) = own.specials();
+            (poison + shield, opp.revealed.count_ones())
+        };
+
+        if lost == 0                            { Self::unlock(env, winner, Achievement::Flawless); }
+        if stats.day_wins >= DAILY_WINS_NEEDED  { Self::unlock(env, winner, Achievement::TripleDay); }
+        if stats.rivals_beaten >= RIVALS_NEEDED { Self::unlock(env, winner, Achievement::FiveRivals); }
+        if stats.wins >= 1                      { Self::unlock(env, winner, Achievement::FirstWin); }
+        if stats.win_streak >= STREAK_NEEDED    { Self::unlock(env, winner, Achievement::TenStreak); }
+        if attacks < QUICK_WIN_ATTACKS          { Self::unlock(env, winner, Achievement::QuickWin); }
+    }
+
+    fn unlock(env: &Env, player: &Address, achievement: Achievement) {
+        let key = DataKey::Achievements(player.clone());
+        let mask: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let bit = 1u32 << (achievement as u32);
+        if mask & bit != 0 { return; }
+
+        env.storage().persistent().set(&key, &(mask | bit));
+        env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        AchievementUnlocked { player: player.clone(), achievement }.publish(env);
+        if let Some(badge) = Self::get_badge_contract(env.clone()) {
+            let _ = BadgeClient::new(env, &badge).try_award(player, &(achievement as u32));
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! # Active sessions
+//!
+//! A global set of in-progress session ids, so lobby, spectator and
+//! monitoring clients can enumerate live games from chain state. An id is
+//! added when its game is stored unfinished and removed when the game
+//! finishes (held results included) or is refunded.
+//!
+//! The set is a dense array of persistent slots plus a reverse position
+//! map; removal moves the last id into the freed slot, so `cursor` order
+//! is not stable while sessions come and go. A session whose live entry
+//! expired without finishing leaves a stale id behind, which anyone may
+//! drop with `prune_active_game`.
+
+use soroban_sdk::{contractimpl, Env, Vec};
+
+use crate::{DataKey, Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::{MAX_PAGE_SIZE, PLAYER_TTL_LEDGERS};
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Up to `limit` active session ids from slot `cursor` on.
+    pub fn list_active_games(env: Env, cursor: u32, limit: u32) -> Vec<u32> {
+        let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(Self::get_active_game_count(env.clone()));
+        let mut out = Vec::new(&env);
+        for slot in cursor..end {
+            if let Some(id) = env.storage().persistent().get(&DataKey::ActiveSlot(slot)) {
+                out.push_back(id);
+            }
+        }
+        out
+    }
+
+    pub fn get_active_game_count(env: Env) -> u32 {
+        env.storage().persistent().get(&DataKey::ActiveCount).unwrap_or(0)
+    }
+
+    /// Drop `session_id` from the active set once its live game has expired.
+    pub fn prune_active_game(env: Env, session_id: u32) -> Result<(), Error> {
+        if Self::has_game(&env, session_id) { return Err(Error::WrongPhase); }
+        Self::deactivate(&env, session_id);
+        Ok(())
+    }
+}
+
+impl PoisonGameContract {
+    pub(crate) fn activate(env: &Env, session_id: u32) {
+        let pos_key = DataKey::ActivePos(session_id);
+        if env.storage().persistent().has(&pos_key) { return; }
+
+        let count = Self::get_active_game_count(env.clone());
+        Self::set_active(env, &DataKey::ActiveSlot(count), &session_id);
+        Self::set_active(env, &pos_key, &count);
+        Self::set_active(env, &DataKey::ActiveCount, &(count + 1));
+    }
+
+    pub(crate) fn deactivate(env: &Env, session_id: u32) {
+        let pos_key = DataKey::ActivePos(session_id);
+        let slot: u32 = match env.storage().persistent().get(&pos_key) {
+            Some(s) => s,
+            None    => return,
+        };
+        let last = Self::get_active_game_count(env.clone()) - 1;
+        if slot != last {
+            let moved: u32 = env.storage().persistent().get(&DataKey::ActiveSlot(last)).unwrap_or(0);
+            Self::set_active(env, &DataKey::ActiveSlot(slot), &moved);
+            Self::set_active(env, &DataKey::ActivePos(moved), &slot);
+        }
+        env.storage().persistent().remove(&DataKey::ActiveSlot(last));
+        env.storage().persistent().remove(&pos_key);
+        Self::set_active(env, &DataKey::ActiveCount, &last);
+    }
+
+    fn set_active(env: &Env, key: &DataKey, value: &u32) {
+        env.storage().persistent().set(key, value);
+        env.storage().persistent().extend_ttl(key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+    }
+}
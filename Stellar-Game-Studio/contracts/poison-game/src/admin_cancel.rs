@@ -0,0 +1,41 @@
+//! # Emergency cancellation
+//!
+//! Incident response for a session a circuit or client bug has left stuck
+//! or corrupted: `admin_cancel` aborts it in any phase before it settles,
+//! including a finished game still held open to challenges. Both stakes
+//! (and any response bonds) are refunded — the GameHub is told
+//! `refund_game`, never a result — and the session is archived with
+//! `Outcome::Cancelled` and no winner. Side bets resolve as a refund.
+//!
+//! `reason` is an operator-defined incident code, carried on the
+//! `GameCancelled` event for indexers. Ratings and stats a finished game
+//! already recorded are left as they are.
+
+use soroban_sdk::{contractevent, contractimpl, vec, Env, IntoVal};
+
+use crate::{Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+#[contractevent]
+pub struct GameCancelled {
+    #[topic]
+    pub session_id: u32,
+    pub reason:     u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Abort an unsettled session, refund both stakes and archive it as cancelled.
+    pub fn admin_cancel(env: Env, session_id: u32, reason: u32) -> Result<(), Error> {
+        Self::authorize_admin_action(
+            &env, "admin_cancel", vec![&env, session_id.into_val(&env), reason.into_val(&env)],
+        )?;
+        let mut game = Self::get_game(env.clone(), session_id)?;
+
+        Self::settle_cancel(&env, session_id, &mut game)?;
+        Self::archive_cancelled(&env, session_id, &game);
+        Self::remove_game(&env, session_id, &game);
+        GameCancelled { session_id, reason }.publish(&env);
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+//! # Game-history archive
+//!
+//! Live games sit in temporary storage and vanish when their TTL lapses.
+//! `finish_game` and `finish_draw` therefore write a compact `GameResult` to
+//! persistent storage, indexed per player in fixed-size pages so a long history never
+//! grows a single entry without bound. Attestations hash the archived
+//! record, so a result stays attestable after the live game has expired.
+
+use soroban_sdk::{contractimpl, contracttype, vec, Address, BytesN, Env, Vec};
+
+use crate::{DataKey, GameState, Outcome, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::PLAYER_TTL_LEDGERS;
+
+/// Session ids per `PlayerResults` page.
+pub const RESULTS_PAGE_SIZE: u32 = 20;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameResult {
+    pub session_id:             u32,
+    pub player1:                Address,
+    pub player2:                Address,
+    pub player1_points:         i128,
+    pub player2_points:         i128,
+    pub winner:                 u32, // 0=none 1=player1 2=player2
+    pub outcome:                Outcome,
+    pub tiles_found:            u32, // tiles the winner revealed on the loser's board
+    pub finished_ledger:        u32,
+    pub rules_hash:             Option<BytesN<32>>,
+    // Board published with `reveal_board` and consistent with the game
+    pub player1_board_verified: bool,
+    pub player2_board_verified: bool,
+    pub move_count:             u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    pub fn get_result(env: Env, session_id: u32) -> Option<GameResult> {
+        env.storage().persistent().get(&DataKey::Result(session_id))
+    }
+
+    /// Page `page` (0-based, oldest first) of `player`'s archived results.
+    pub fn get_results_by_player(env: Env, player: Address, page: u32) -> Vec<GameResult> {
+        let ids: Vec<u32> = env.storage().persistent()
+            .get(&DataKey::PlayerResults(player, page)).unwrap_or(vec![&env]);
+        let mut out = Vec::new(&env);
+        for id in ids.iter() {
+            if let Some(r) = Self::get_result(env.clone(), id) { out.push_back(r); }
+        }
+        out
+    }
+
+    pub fn get_result_count(env: Env, player: Address) -> u32 {
+        env.storage().persistent()
+            .get(&DataKey::PlayerResultCount(player)).unwrap_or(0)
+    }
+}
+
+impl PoisonGameContract {
+    /// Called from `finish_game` once the winner is set.
+    pub(crate) fn archive_result(env: &Env, session_id: u32, game: &GameState) {
+        let result = Self::result_of(env, session_id, game, Self::outcome_of(game));
+        Self::store_result(env, session_id, game, &result);
+    }
+
+    /// Called from `finish_draw`; a draw credits no tiles found.
+    pub(crate) fn archive_draw(env: &Env, session_id: u32, game: &GameState, outcome: Outcome) {
+        let mut result = Self::result_of(env, session_id, game, outcome);
+        result.tiles_found = 0;
+        Self::store_result(env, session_id, game, &result);
+    }
+
+    /// Archive an admin-cancelled session with no winner, rewriting the
+    /// result if the game had already finished.
+    pub(crate) fn archive_cancelled(env: &Env, session_id: u32, game: &GameState) {
+        if let Some(mut result) = Self::get_result(env.clone(), session_id) {
+            result.winner  = 0;
+            result.outcome = Outcome::Cancelled;
+            env.storage().persistent().set(&DataKey::Result(session_id), &result);
+            return;
+        }
+        let mut result = Self::result_of(env, session_id, game, Outcome::Cancelled);
+        result.winner      = 0;
+        result.tiles_found = 0;
+        Self::store_result(env, session_id, game, &result);
+    }
+
+    fn result_of(env: &Env, session_id: u32, game: &GameState, outcome: Outcome) -> GameResult {
+        let found = if game.winner == 1 { &game.p2_revealed } else { &game.p1_revealed };
+        GameResult {
+            session_id,
+            player1:                game.player1.clone(),
+            player2:                game.player2.clone(),
+            player1_points:         game.player1_points,
+            player2_points:         game.player2_points,
+            winner:                 game.winner,
+            outcome,
+            tiles_found:            found.len(),
+            finished_ledger:        env.ledger().sequence(),
+            rules_hash:             game.config.rules_hash.clone(),
+            player1_board_verified: false,
+            player2_board_verified: false,
+            move_count:             game.move_count,
+        }
+    }
+
+    fn store_result(env: &Env, session_id: u32, game: &GameState, result: &GameResult) {
+        let key = DataKey::Result(session_id);
+        env.storage().persistent().set(&key, result);
+        env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+
+        Self::index_result(env, &game.player1, session_id);
+        Self::index_result(env, &game.player2, session_id);
+    }
+
+    pub(crate) fn mark_board_verified(env: &Env, session_id: u32, slot: u32) {
+        let key = DataKey::Result(session_id);
+        if let Some(mut result) = Self::get_result(env.clone(), session_id) {
+            if slot == 1 { result.player1_board_verified = true; } else { result.player2_board_verified = true; }
+            env.storage().persistent().set(&key, &result);
+        }
+    }
+
+    /// Rewrite a held result after an upheld challenge.
+    pub(crate) fn mark_overturned(env: &Env, session_id: u32, game: &GameState) {
+        let key = DataKey::Result(session_id);
+        if let Some(mut result) = Self::get_result(env.clone(), session_id) {
+            result.winner  = game.winner;
+            result.outcome = Self::outcome_of(game);
+            env.storage().persistent().set(&key, &result);
+        }
+    }
+
+    fn index_result(env: &Env, player: &Address, session_id: u32) {
+        let count = Self::get_result_count(env.clone(), player.clone());
+        let page_key = DataKey::PlayerResults(player.clone(), count / RESULTS_PAGE_SIZE);
+        let mut ids: Vec<u32> = env.storage().persistent().get(&page_key).unwrap_or(vec![env]);
+        ids.push_back(session_id);
+        env.storage().persistent().set(&page_key, &ids);
+        env.storage().persistent().extend_ttl(&page_key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+
+        let count_key = DataKey::PlayerResultCount(player.clone());
+        env.storage().persistent().set(&count_key, &(count + 1));
+        env.storage().persistent().extend_ttl(&count_key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+    }
+}
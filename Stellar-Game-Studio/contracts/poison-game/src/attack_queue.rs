@@ -0,0 +1,99 @@
+//! # Attack queue
+//!
+//! A fast client can pre-authorize its coming attacks with `queue_attacks`:
+//! an ordered list of tiles, kept per seat. Whenever an answer hands the
+//! turn back to that seat, the contract attacks for it from the front of
+//! the list, skipping tiles that have been revealed in the meantime, with
+//! no further transaction. A salvo turn takes up to `salvo_size` entries.
+//!
+//! Queueing again replaces the list and `cancel_attack_queue` drops it. A
+//! queued attack that can no longer be placed (say, the seat's clock ran
+//! out) drops the queue rather than failing the answer that triggered it.
+//! Lite and simultaneous games have no queue.
+
+use soroban_sdk::{contractimpl, vec, Address, Env, Vec};
+
+use crate::{DataKey, Error, GameState, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::GAME_TTL_LEDGERS;
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Replace `attacker`'s queued attacks with `tiles`, in order. If it is
+    /// already their turn the first attack is placed straight away.
+    pub fn queue_attacks(env: Env, session_id: u32, attacker: Address, tiles: Vec<u32>) -> Result<(), Error> {
+        attacker.require_auth();
+        let game = Self::get_game(env.clone(), session_id)?;
+        if game.phase != Phase::Playing                  { return Err(Error::WrongPhase); }
+        if game.config.lite || game.config.simultaneous { return Err(Error::WrongPhase); }
+        let slot = Self::player_slot(&game, &attacker)?;
+        if tiles.is_empty() || tiles.len() > game.board_size { return Err(Error::InvalidSalvo); }
+        for (i, tile_index) in tiles.iter().enumerate() {
+            if tile_index >= game.board_size                        { return Err(Error::InvalidTileIndex); }
+            if tiles.first_index_of(tile_index) != Some(i as u32) { return Err(Error::InvalidSalvo);     }
+        }
+
+        let key = DataKey::AttackQueue(session_id, slot);
+        env.storage().temporary().set(&key, &tiles);
+        env.storage().temporary().extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+
+        match Self::next_queued_attack(&env, session_id, &game) {
+            Some((attacker, tiles)) => Self::place_attacks(&env, session_id, attacker, tiles),
+            None                    => Ok(()),
+        }
+    }
+
+    pub fn cancel_attack_queue(env: Env, session_id: u32, attacker: Address) -> Result<(), Error> {
+        attacker.require_auth();
+        let game = Self::get_game(env.clone(), session_id)?;
+        let slot = Self::player_slot(&game, &attacker)?;
+        env.storage().temporary().remove(&DataKey::AttackQueue(session_id, slot));
+        Ok(())
+    }
+
+    /// Tiles still queued for `player_slot`, next attack first.
+    pub fn get_attack_queue(env: Env, session_id: u32, player_slot: u32) -> Vec<u32> {
+        env.storage().temporary().get(&DataKey::AttackQueue(session_id, player_slot)).unwrap_or(vec![&env])
+    }
+}
+
+impl PoisonGameContract {
+    /// Place the next queued attack once `game`, as just saved, hands the
+    /// turn to a seat with a queue.
+    pub(crate) fn run_attack_queue(env: &Env, session_id: u32, game: &GameState) {
+        if let Some((attacker, tiles)) = Self::next_queued_attack(env, session_id, game) {
+            if Self::place_attacks(env, session_id, attacker, tiles).is_err() {
+                env.storage().temporary().remove(&DataKey::AttackQueue(session_id, game.current_turn));
+            }
+        }
+    }
+
+    /// Pop the attacker's next turn off their queue, skipping revealed tiles.
+    /// `None` if nobody is waiting to attack or their queue is used up.
+    fn next_queued_attack(env: &Env, session_id: u32, game: &GameState) -> Option<(Address, Vec<u32>)> {
+        if game.phase != Phase::Playing || game.winner != 0 { return None; }
+        if !game.pending_tiles.is_empty() || game.pending_scan.is_some() { return None; }
+
+        let slot = game.current_turn;
+        let key = DataKey::AttackQueue(session_id, slot);
+        let mut queue: Vec<u32> = env.storage().temporary().get(&key)?;
+        let defender_mask = if slot == 1 { &game.p2_mask } else { &game.p1_mask };
+        let mut tiles = vec![env];
+        while tiles.len() < game.config.salvo_size.max(1) {
+            match queue.pop_front() {
+                Some(tile_index) if !defender_mask.is_revealed(tile_index) => tiles.push_back(tile_index),
+                Some(_) => {}
+                None    => break,
+            }
+        }
+        if queue.is_empty() {
+            env.storage().temporary().remove(&key);
+        } else {
+            env.storage().temporary().set(&key, &queue);
+        }
+
+        if tiles.is_empty() { return None; }
+        let attacker = if slot == 1 { game.player1.clone() } else { game.player2.clone() };
+        Some((attacker, tiles))
+    }
+}
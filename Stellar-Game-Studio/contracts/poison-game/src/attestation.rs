@@ -0,0 +1,127 @@
+//! # Result attestation
+//!
+//! An admin-appointed attestor co-signs finalized results so they can be
+//! mirrored to another chain or an L2 leaderboard. Each attestation is bound
+//! to the session id and to a keccak hash over the canonical XDR encoding of
+//! the archived `GameResult`, and is announced with a `ResultAttested` event.
+//!
+//! Consumers that only need to check a result — bridges, chat bots,
+//! off-chain leaderboards — can compare against `get_result_digest`, a
+//! keccak hash of the players, their stakes, the winner, the move count
+//! and the finish ledger, without an attestor or the full game state.
+
+use soroban_sdk::{contractevent, contractimpl, contracttype, xdr::ToXdr, Address, BytesN, Env};
+
+use crate::{DataKey, Error, GameResult, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::PLAYER_TTL_LEDGERS;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    pub session_id:  u32,
+    pub result_hash: BytesN<32>,
+    pub attestor:    Address,
+    pub ledger:      u32,
+}
+
+#[contractevent]
+pub struct ResultAttested {
+    #[topic]
+    pub session_id:  u32,
+    pub result_hash: BytesN<32>,
+    pub attestor:    Address,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Appoint (or remove with None) the result attestor.
+    pub fn set_attestor(env: Env, attestor: Option<Address>) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        match attestor {
+            Some(a) => env.storage().instance().set(&DataKey::Attestor, &a),
+            None    => env.storage().instance().remove(&DataKey::Attestor),
+        }
+        Ok(())
+    }
+
+    pub fn get_attestor(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Attestor)
+    }
+
+    /// Attestor co-signs the result of a finished session.
+    pub fn attest_result(env: Env, session_id: u32) -> Result<Attestation, Error> {
+        let attestor = Self::get_attestor(env.clone()).ok_or(Error::NoAttestor)?;
+        attestor.require_auth();
+
+        let result = Self::finished_result(&env, session_id)?;
+
+        let attestation = Attestation {
+            session_id,
+            result_hash: Self::result_hash(&env, &result),
+            attestor:    attestor.clone(),
+            ledger:      env.ledger().sequence(),
+        };
+
+        let key = DataKey::Attestation(session_id);
+        env.storage().persistent().set(&key, &attestation);
+        env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+
+        ResultAttested {
+            session_id,
+            result_hash: attestation.result_hash.clone(),
+            attestor,
+        }
+        .publish(&env);
+        Ok(attestation)
+    }
+
+    pub fn get_attestation(env: Env, session_id: u32) -> Option<Attestation> {
+        env.storage().persistent().get(&DataKey::Attestation(session_id))
+    }
+
+    /// keccak256 over the XDR of `(session_id, player1, player2,
+    /// player1_points, player2_points, winner, move_count, finished_ledger)`
+    /// of a finished session's archived result.
+    pub fn get_result_digest(env: Env, session_id: u32) -> Result<BytesN<32>, Error> {
+        let result = Self::finished_result(&env, session_id)?;
+        let preimage = (
+            result.session_id,
+            result.player1,
+            result.player2,
+            result.player1_points,
+            result.player2_points,
+            result.winner,
+            result.move_count,
+            result.finished_ledger,
+        )
+            .to_xdr(&env);
+        Ok(env.crypto().keccak256(&preimage).into())
+    }
+}
+
+impl PoisonGameContract {
+    /// Archived result of `session_id`; only finished games are archived.
+    fn finished_result(env: &Env, session_id: u32) -> Result<GameResult, Error> {
+        match Self::get_result(env.clone(), session_id) {
+            Some(r) => Ok(r),
+            None    => { Self::get_game(env.clone(), session_id)?; Err(Error::WrongPhase) }
+        }
+    }
+
+    /// keccak256 over the XDR of
+    /// `(session_id, player1, player2, player1_points, player2_points, winner, rules_hash)`.
+    pub(crate) fn result_hash(env: &Env, result: &GameResult) -> BytesN<32> {
+        let preimage = (
+            result.session_id,
+            result.player1.clone(),
+            result.player2.clone(),
+            result.player1_points,
+            result.player2_points,
+            result.winner,
+            result.rules_hash.clone(),
+        )
+            .to_xdr(env);
+        env.crypto().keccak256(&preimage).into()
+    }
+}
@@ -0,0 +1,149 @@
+//! # Spectator side bets
+//!
+//! Third parties can back either player of a live session with `place_bet`
+//! until `cutoff` tiles have been revealed across both boards. Bets are
+//! paid in the market's token, set by the operator with `set_bet_market`
+//! and snapshotted by a session's first bet, and escrowed in this contract.
+//! Players cannot bet on their own session, and a bettor backs one side.
+//!
+//! Bets resolve against the session's `SettlementReceipt`, so they follow
+//! overturned results and can be claimed after the game itself expires.
+//! Once the session settles each bettor calls `claim_bet`: backers of the
+//! winner get their bet back plus a pro-rata share of the losing side's
+//! pool, backers of the loser get nothing. A refunded session (cancelled
+//! or drawn), a win nobody backed, or a session whose game expired without
+//! ever settling returns every bet. Rounding dust stays in the contract.
+//!
+//! Gated by `FEATURE_SIDE_BETS`; claims stay open while it is disabled.
+
+use soroban_sdk::{contractevent, contractimpl, contracttype, token::TokenClient, vec, Address, Env, IntoVal};
+
+use crate::{DataKey, Error, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::{SettlementKind, FEATURE_SIDE_BETS, PLAYER_TTL_LEDGERS};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BetMarket {
+    pub token:  Address,
+    pub cutoff: u32, // betting closes once this many tiles are revealed
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BetPool {
+    pub token:       Address,
+    pub on_player1:  i128,
+    pub on_player2:  i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bet {
+    pub on_player: u32, // 1 or 2
+    pub amount:    i128,
+}
+
+#[contractevent]
+pub struct BetPlaced {
+    #[topic]
+    pub session_id: u32,
+    pub bettor:     Address,
+    pub on_player:  u32,
+    pub amount:     i128,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    pub fn set_bet_market(env: Env, token: Address, cutoff: u32) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_bet_market", vec![&env, token.clone().into_val(&env), cutoff.into_val(&env)])?;
+        if cutoff == 0 { return Err(Error::InvalidConfig); }
+        env.storage().instance().set(&DataKey::BetMarket, &BetMarket { token, cutoff });
+        Ok(())
+    }
+
+    pub fn get_bet_market(env: Env) -> Option<BetMarket> {
+        env.storage().instance().get(&DataKey::BetMarket)
+    }
+
+    /// Back `on_player` in a live session with `amount` of the market token.
+    pub fn place_bet(env: Env, session_id: u32, bettor: Address, on_player: u32, amount: i128) -> Result<(), Error> {
+        bettor.require_auth();
+        Self::check_operational(&env)?;
+        Self::check_feature(&env, FEATURE_SIDE_BETS)?;
+        let market = Self::get_bet_market(env.clone()).ok_or(Error::FeatureDisabled)?;
+
+        let game = Self::get_game(env.clone(), session_id)?;
+        if game.phase == Phase::Finished || game.winner != 0 { return Err(Error::BettingClosed); }
+        let revealed = if game.config.lite {
+            game.lite_boards.iter().map(|b| b.mask.count_ones()).sum()
+        } else {
+            game.p1_mask.revealed.count_ones() + game.p2_mask.revealed.count_ones()
+        };
+        if revealed >= market.cutoff                            { return Err(Error::BettingClosed);  }
+        if bettor == game.player1 || bettor == game.player2     { return Err(Error::SelfPlay);       }
+        if on_player != 1 && on_player != 2                     { return Err(Error::InvalidConfig);  }
+        if amount <= 0                                          { return Err(Error::NegativeStake);  }
+
+        let pool_key = DataKey::BetPool(session_id);
+        let mut pool: BetPool = env.storage().persistent().get(&pool_key)
+            .unwrap_or(BetPool { token: market.token, on_player1: 0, on_player2: 0 });
+        let bet_key = DataKey::Bet(session_id, bettor.clone());
+        let mut bet: Bet = env.storage().persistent().get(&bet_key).unwrap_or(Bet { on_player, amount: 0 });
+        if bet.on_player != on_player { return Err(Error::InvalidConfig); }
+
+        let this = env.current_contract_address();
+        TokenClient::new(&env, &pool.token).transfer(&bettor, &this, &amount);
+        bet.amount = bet.amount.checked_add(amount).ok_or(Error::StakeOverflow)?;
+        let side = if on_player == 1 { &mut pool.on_player1 } else { &mut pool.on_player2 };
+        *side = side.checked_add(amount).ok_or(Error::StakeOverflow)?;
+
+        env.storage().persistent().set(&pool_key, &pool);
+        env.storage().persistent().extend_ttl(&pool_key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        env.storage().persistent().set(&bet_key, &bet);
+        env.storage().persistent().extend_ttl(&bet_key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        BetPlaced { session_id, bettor, on_player, amount }.publish(&env);
+        Ok(())
+    }
+
+    pub fn get_bet(env: Env, session_id: u32, bettor: Address) -> Option<Bet> {
+        env.storage().persistent().get(&DataKey::Bet(session_id, bettor))
+    }
+
+    pub fn get_bet_pool(env: Env, session_id: u32) -> Option<BetPool> {
+        env.storage().persistent().get(&DataKey::BetPool(session_id))
+    }
+
+    /// Pay out `bettor`'s bet once the session has settled; returns the payout.
+    pub fn claim_bet(env: Env, session_id: u32, bettor: Address) -> Result<i128, Error> {
+        Self::check_operational(&env)?;
+
+        let bet_key = DataKey::Bet(session_id, bettor.clone());
+        let bet: Bet = env.storage().persistent().get(&bet_key).ok_or(Error::BetNotFound)?;
+        let pool = Self::get_bet_pool(env.clone(), session_id).ok_or(Error::InternalInvariant)?;
+        let (refund, winner) = match Self::get_settlement_receipt(env.clone(), session_id) {
+            Some(receipt) => (receipt.kind == SettlementKind::Refund, receipt.winner),
+            // Once the game is gone unsettled it can never settle
+            None if !Self::has_game(&env, session_id) => (true, 0),
+            None => return Err(Error::WrongPhase),
+        };
+
+        let (winning, losing) = if winner == 1 { (pool.on_player1, pool.on_player2) }
+                                else           { (pool.on_player2, pool.on_player1) };
+        let payout = if refund || winning == 0 {
+            bet.amount
+        } else if bet.on_player == winner {
+            let share = bet.amount.checked_mul(losing).ok_or(Error::StakeOverflow)? / winning;
+            bet.amount + share
+        } else {
+            0
+        };
+
+        env.storage().persistent().remove(&bet_key);
+        if payout > 0 {
+            let this = env.current_contract_address();
+            TokenClient::new(&env, &pool.token).transfer(&this, &bettor, &payout);
+        }
+        Ok(payout)
+    }
+}
@@ -0,0 +1,96 @@
+//! # Post-game board reveal
+//!
+//! After a game finishes either player may publish their full board and
+//! salt with `reveal_board`. The contract checks the board on-chain against
+//! everything it already knows — the board size, the configured Poison,
+//! Shield and Decoy counts, and every tile type that was proven during the
+//! game — and records the outcome in the archived result.
+//!
+//! The Pedersen opening itself (`pedersen_hash([tiles..., salt, owner,
+//! session_id])` against the committed hash, or `poseidon2` for
+//! Poseidon-committed games) cannot be recomputed here: barretenberg's
+//! Pedersen runs over Grumpkin, for which Soroban has no host functions.
+//! The tiles and salt are therefore published verbatim in storage and in
+//! the `BoardRevealed` event, so any observer can recompute it off-chain
+//! with the game's `board_owners` and session id.
+
+use soroban_sdk::{contractevent, contractimpl, contracttype, Address, BytesN, Env, Vec};
+
+use crate::{DataKey, Error, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::PLAYER_TTL_LEDGERS;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoardReveal {
+    pub tiles:      Vec<u32>,
+    pub salt:       BytesN<32>,
+    pub commitment: BytesN<32>,
+}
+
+#[contractevent]
+pub struct BoardRevealed {
+    #[topic]
+    pub session_id:  u32,
+    pub player_slot: u32,
+    pub tiles:       Vec<u32>,
+    pub salt:        BytesN<32>,
+    pub commitment:  BytesN<32>,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Publish `player`'s board and salt for a finished game. Fails with
+    /// `BoardMismatch` if the board contradicts the rules or any reveal
+    /// proven during play.
+    pub fn reveal_board(
+        env:        Env,
+        session_id: u32,
+        player:     Address,
+        tiles:      Vec<u32>,
+        salt:       BytesN<32>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        let game = Self::get_game(env.clone(), session_id)?;
+        if game.phase != Phase::Finished { return Err(Error::WrongPhase); }
+        let slot = if player == game.player1      { 1u32 }
+                   else if player == game.player2 { 2u32 }
+                   else                           { return Err(Error::NotPlayer); };
+
+        let key = DataKey::BoardReveal(session_id, slot);
+        if env.storage().persistent().has(&key) { return Err(Error::AlreadyCommitted); }
+
+        // Board shape and special counts
+        if tiles.len() != game.board_size { return Err(Error::BoardMismatch); }
+        let mut counts = [0u32; 4];
+        for t in tiles.iter() {
+            if t > 3 { return Err(Error::BoardMismatch); }
+            counts[t as usize] += 1;
+        }
+        let config = &game.config;
+        if counts[1] != config.poison_on(slot) || counts[2] != config.shield_count || counts[3] != config.decoy_count {
+            return Err(Error::BoardMismatch);
+        }
+
+        // Every reveal proven during the game
+        let revealed = if slot == 1 { &game.p1_revealed } else { &game.p2_revealed };
+        for r in revealed.iter() {
+            if tiles.get(r.tile_index) != Some(r.tile_type) { return Err(Error::BoardMismatch); }
+        }
+
+        let commitment = if slot == 1 { game.player1_commitment.clone() }
+                         else         { game.player2_commitment.clone() };
+        let reveal = BoardReveal { tiles: tiles.clone(), salt: salt.clone(), commitment: commitment.clone() };
+        env.storage().persistent().set(&key, &reveal);
+        env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        Self::mark_board_verified(&env, session_id, slot);
+
+        BoardRevealed { session_id, player_slot: slot, tiles, salt, commitment }.publish(&env);
+        Ok(())
+    }
+
+    pub fn get_board_reveal(env: Env, session_id: u32, player_slot: u32) -> Option<BoardReveal> {
+        env.storage().persistent().get(&DataKey::BoardReveal(session_id, player_slot))
+    }
+}
@@ -0,0 +1,90 @@
+//! # Bot opponents
+//!
+//! Practice matches against a bot contract implementing `BotPlayer`, which
+//! may play on its own logic or relay an operator's moves. The admin
+//! registers bots; `start_bot_game` then opens a friendly (zero-stake)
+//! session with the bot as player2 and commits the bot's board straight
+//! away.
+//!
+//! A bot contract can submit its own moves, since a contract authorizes
+//! the calls it makes itself. Alternatively anyone may call
+//! `play_bot_turn` whenever a registered bot is due to move; the game asks
+//! the bot for its attack or its answers and plays them on its behalf.
+//! Such moves need no signature: the bot's answers come straight from the
+//! registered contract, and reveals are proven like any other.
+
+use soroban_sdk::{contractclient, contractimpl, vec, Address, Bytes, BytesN, Env, Vec};
+
+use crate::{BoardCommitted, BoardMask, DataKey, Error, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+#[contractclient(name = "BotPlayerClient")]
+pub trait BotPlayer {
+    /// Commitment to the bot's board for a new session.
+    fn board_commitment(env: Env, session_id: u32) -> BytesN<32>;
+    /// Tile to attack, given what is revealed on the opponent's board.
+    fn choose_attack(env: Env, session_id: u32, revealed: BoardMask) -> u32;
+    /// `(tile_type, proof_blob)` answering an attack on `tile_index`.
+    fn respond(env: Env, session_id: u32, tile_index: u32) -> (u32, Bytes);
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    pub fn register_bot(env: Env, bot: Address) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::Bot(bot), &true);
+        Ok(())
+    }
+
+    pub fn unregister_bot(env: Env, bot: Address) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        env.storage().instance().remove(&DataKey::Bot(bot));
+        Ok(())
+    }
+
+    pub fn is_bot(env: Env, bot: Address) -> bool {
+        env.storage().instance().has(&DataKey::Bot(bot))
+    }
+
+    /// Open a friendly practice session between `player` and a registered `bot`.
+    pub fn start_bot_game(env: Env, session_id: u32, player: Address, bot: Address) -> Result<(), Error> {
+        player.require_auth();
+        if !Self::is_bot(env.clone(), bot.clone()) { return Err(Error::NotPlayer); }
+        Self::open_game(&env, session_id, player, bot.clone(), 0, 0, Self::default_config(&env), vec![&env])?;
+
+        let mut game = Self::get_game(env.clone(), session_id)?;
+        let board_hash = BotPlayerClient::new(&env, &bot).board_commitment(&session_id);
+        Self::check_commitment(&game, 2, &board_hash)?;
+        game.player2_commitment = board_hash;
+        game.player2_committed  = true;
+        game.move_count += 1;
+        Self::save_game(&env, session_id, &game);
+        BoardCommitted { session_id, move_number: game.move_count, player_slot: 2, phase: game.phase.clone() }
+            .publish(&env);
+        Ok(())
+    }
+
+    /// Play the registered bot's move in `session_id`: its answers to the
+    /// pending attacks, or its next attack when it is on turn.
+    pub fn play_bot_turn(env: Env, session_id: u32) -> Result<(), Error> {
+        let game = Self::get_game(env.clone(), session_id)?;
+        if game.phase != Phase::Playing { return Err(Error::WrongPhase); }
+
+        // The bot moves next: as attacker on turn, or as defender with answers due
+        let bot_num = if game.pending_tiles.is_empty() { game.current_turn } else { 3 - game.current_turn };
+        let bot = if bot_num == 1 { game.player1.clone() } else { game.player2.clone() };
+        if !Self::is_bot(env.clone(), bot.clone()) { return Err(Error::NotYourTurn); }
+        let client = BotPlayerClient::new(&env, &bot);
+
+        if game.pending_tiles.is_empty() {
+            let revealed = if bot_num == 1 { game.p2_mask } else { game.p1_mask };
+            let tile_index = client.choose_attack(&session_id, &revealed);
+            return Self::place_attacks(&env, session_id, bot, vec![&env, tile_index]);
+        }
+        let mut responses: Vec<(u32, Bytes)> = vec![&env];
+        for tile_index in game.pending_tiles.iter() {
+            responses.push_back(client.respond(&session_id, &tile_index));
+        }
+        Self::answer_attacks(&env, session_id, bot, responses)
+    }
+}
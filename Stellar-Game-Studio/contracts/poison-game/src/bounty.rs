@@ -0,0 +1,89 @@
+//! # Champion bounties
+//!
+//! The operator can designate champions, each with a standing bounty.
+//! Whoever beats a champion in a qualifying session — one where the
+//! challenger staked at least the champion's `min_stake` — is paid the
+//! bounty from the treasury on top of the stake when the session settles,
+//! so a result held in its dispute window is paid once finalized and an
+//! overturned one pays the challenger. A bounty the treasury cannot cover
+//! is simply not paid.
+
+use soroban_sdk::{contractevent, contractimpl, contracttype, vec, Address, Env, IntoVal};
+
+use crate::{DataKey, Error, GameState, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::PLAYER_TTL_LEDGERS;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Champion {
+    pub bounty:    i128,
+    pub min_stake: i128, // challenger's stake for the session to qualify
+    pub defeats:   u32,  // bounties paid out so far
+}
+
+#[contractevent]
+pub struct BountyPaid {
+    #[topic]
+    pub session_id: u32,
+    pub champion:   Address,
+    pub challenger: Address,
+    pub bounty:     i128,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Designate `champion` with a standing bounty, or retire them (None).
+    pub fn set_champion(
+        env:       Env,
+        champion:  Address,
+        bounty:    Option<i128>,
+        min_stake: i128,
+    ) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_champion", vec![&env, champion.clone().into_val(&env), bounty.into_val(&env), min_stake.into_val(&env)])?;
+        let key = DataKey::Champion(champion);
+        match bounty {
+            Some(b) => {
+                if b <= 0 || min_stake < 0 { return Err(Error::InvalidConfig); }
+                let defeats = env.storage().persistent().get::<_, Champion>(&key).map_or(0, |c| c.defeats);
+                env.storage().persistent().set(&key, &Champion { bounty: b, min_stake, defeats });
+                env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+        Ok(())
+    }
+
+    pub fn get_champion(env: Env, champion: Address) -> Option<Champion> {
+        env.storage().persistent().get(&DataKey::Champion(champion))
+    }
+}
+
+impl PoisonGameContract {
+    /// Called from `settle_win` once the winner is final.
+    pub(crate) fn pay_bounty(env: &Env, session_id: u32, game: &GameState) -> Result<(), Error> {
+        let (challenger, champion, stake) = if game.winner == 1 {
+            (&game.player1, &game.player2, game.player1_points)
+        } else {
+            (&game.player2, &game.player1, game.player2_points)
+        };
+        let key = DataKey::Champion(champion.clone());
+        let mut record: Champion = match env.storage().persistent().get(&key) {
+            Some(c) => c,
+            None    => return Ok(()),
+        };
+        if stake < record.min_stake { return Ok(()); }
+        if !Self::treasury_pay(env, challenger, record.bounty)? { return Ok(()); }
+
+        record.defeats += 1;
+        env.storage().persistent().set(&key, &record);
+        BountyPaid {
+            session_id,
+            champion:   champion.clone(),
+            challenger: challenger.clone(),
+            bounty:     record.bounty,
+        }
+        .publish(env);
+        Ok(())
+    }
+}
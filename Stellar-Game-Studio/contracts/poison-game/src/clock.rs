@@ -0,0 +1,77 @@
+//! # Chess-clock time control
+//!
+//! With `config.time_bank > 0` each player gets that many ledgers for the
+//! whole game. The clock starts once both boards are committed and runs for
+//! whoever is on move: the attacker while no attack or scan is pending,
+//! the defender while one is. A move made after the bank ran out is refused;
+//! instead the opponent can `claim_flag_fall` and take the win. Claims are
+//! refused while the contract is paused, since neither side can move.
+
+use soroban_sdk::{contractimpl, Address, Env};
+
+use crate::{Error, GameState, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Win on time: the opponent is on move and their bank is exhausted.
+    pub fn claim_flag_fall(env: Env, session_id: u32, claimer: Address) -> Result<(), Error> {
+        claimer.require_auth();
+        Self::check_operational(&env)?;
+
+        let mut game = Self::get_game(env.clone(), session_id)?;
+        if game.phase != Phase::Playing  { return Err(Error::WrongPhase);    }
+        if game.config.time_bank == 0    { return Err(Error::NoTimeControl); }
+
+        let claimer_num = if claimer == game.player1      { 1u32 }
+                          else if claimer == game.player2 { 2u32 }
+                          else                            { return Err(Error::NotPlayer); };
+
+        let on_move = Self::slot_on_move(&game);
+        if on_move == claimer_num                          { return Err(Error::NotYourTurn);     }
+        if Self::time_remaining(&env, &game, on_move) > 0 { return Err(Error::ClockNotExpired); }
+
+        Self::finish_game(&env, session_id, &mut game, claimer_num == 1)?;
+        Self::save_game(&env, session_id, &game);
+        Ok(())
+    }
+
+    /// Ledgers `player_slot` has left, counting the move in progress.
+    pub fn get_time_left(env: Env, session_id: u32, player_slot: u32) -> Result<u32, Error> {
+        let game = Self::get_game(env.clone(), session_id)?;
+        Ok(Self::time_remaining(&env, &game, player_slot))
+    }
+}
+
+impl PoisonGameContract {
+    /// Slot whose clock is running: the defender while an attack or scan is pending.
+    pub(crate) fn slot_on_move(game: &GameState) -> u32 {
+        match (!game.pending_tiles.is_empty() || game.pending_scan.is_some(), game.current_turn) {
+            (false, turn) => turn,
+            (true, 1)     => 2,
+            (true, _)     => 1,
+        }
+    }
+
+    pub(crate) fn time_remaining(env: &Env, game: &GameState, slot: u32) -> u32 {
+        let bank = if slot == 1 { game.player1_time_left } else { game.player2_time_left };
+        if game.config.time_bank == 0 || game.phase != Phase::Playing || Self::slot_on_move(game) != slot {
+            return bank;
+        }
+        let elapsed = env.ledger().sequence().saturating_sub(game.last_action_ledger);
+        bank.saturating_sub(elapsed)
+    }
+
+    /// Deduct the running move from `slot`'s bank before it acts and restart
+    /// the clock for whoever moves next. The restart also happens in untimed
+    /// games, where it anchors the response window.
+    pub(crate) fn charge_clock(env: &Env, game: &mut GameState, slot: u32) -> Result<(), Error> {
+        if game.config.time_bank > 0 {
+            let left = Self::time_remaining(env, game, slot);
+            if left == 0 { return Err(Error::OutOfTime); }
+            if slot == 1 { game.player1_time_left = left; } else { game.player2_time_left = left; }
+        }
+        game.last_action_ledger = env.ledger().sequence();
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+//! # Stable result codes
+//!
+//! Everything an indexer or client maps to user-facing text is a numeric
+//! code, never a string: `Phase`, `Outcome`, `Achievement`, `TileStatus`,
+//! `SettlementKind`, `SeriesPayout`, tile types (0=Normal 1=Poison 2=Shield
+//! 3=Decoy) and the `Error` discriminants together with their
+//! `ErrorCategory`. Codes are only ever appended; a code's meaning never
+//! changes. `codes_version` is bumped whenever a table gains entries so
+//! clients can tell whether they know every code a deployment may emit.
+
+use soroban_sdk::{contractevent, contractimpl, contracttype, Env};
+
+use crate::{attacker_won_mask, Error, GameState, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient, WinCondition};
+
+/// Version of the code tables above.
+pub const CODES_VERSION: u32 = 25;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Outcome {
+    AllSpecialsFound   = 0, // winner revealed every Poison + Shield on the loser's board
+    FlagFall           = 1, // loser's chess clock ran out
+    NonResponse        = 2, // loser let the response window lapse
+    Overturned         = 3, // the original winner lost an upheld challenge
+    TurnLimit          = 4, // the round limit ran out and the winner led on the tiebreak
+    DrawBoardExhausted = 5, // no winner: neither player could still find every special
+    PoisonFound        = 6, // first-poison-loses game: the loser uncovered a Poison
+    Cancelled          = 7, // no winner: the admin aborted the game and refunded both stakes
+    DrawLevelTiebreak  = 8, // no winner: the round limit ran out with level tiebreak scores
+    DrawSameRound      = 9, // no winner: both players found every special in the same simultaneous round
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ErrorCategory {
+    NotFound = 0, // the session, series or record does not exist
+    Rules    = 1, // the move breaks the game rules or is out of turn
+    Proof    = 2, // proof or verification key problem
+    Auth     = 3, // caller lacks the role or approvals
+    Config   = 4, // invalid parameters or configuration
+    Stakes   = 5, // wager limits and settlement
+    Halted   = 6, // contract paused, uninitialized, out of budget or feature disabled
+    Internal = 7,
+}
+
+#[contractevent]
+pub struct GameEnded {
+    #[topic]
+    pub session_id: u32,
+    pub winner:     u32, // 1=player1 2=player2, 0 = draw
+    pub outcome:    Outcome,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    pub fn codes_version(_env: Env) -> u32 {
+        CODES_VERSION
+    }
+}
+
+impl PoisonGameContract {
+    /// How a finished game was decided.
+    pub(crate) fn outcome_of(game: &GameState) -> Outcome {
+        if game.overturned     { return Outcome::Overturned;  }
+        if game.forfeited != 0 { return Outcome::NonResponse; }
+        // Lite games keep only the claimed counts per board
+        if game.config.lite {
+            let board = game.lite_boards.get_unchecked(2 - game.winner);
+            let found = board.poison >= game.config.poison_on(3 - game.winner) && board.shield >= game.config.shield_count;
+            return if found { Outcome::AllSpecialsFound } else { Outcome::FlagFall };
+        }
+        let (found, lost) = if game.winner == 1 { (&game.p2_mask, &game.p1_mask) } else { (&game.p1_mask, &game.p2_mask) };
+        let all_found = game.config.win_condition != WinCondition::MostSpecialsAfterNRounds
+            && attacker_won_mask(found, &game.config, 3 - game.winner);
+        if game.config.win_condition == WinCondition::FirstPoisonLoses && lost.poison != 0 { return Outcome::PoisonFound; }
+        if all_found { return Outcome::AllSpecialsFound; }
+        if Self::turn_limit_reached(game) { Outcome::TurnLimit } else { Outcome::FlagFall }
+    }
+}
+
+impl Error {
+    pub fn category(self) -> ErrorCategory {
+        use Error::*;
+        match self {
+            GameNotFound | SeriesNotFound | TutorialNotStarted | NoPendingUpgrade | BetNotFound
+                => ErrorCategory::NotFound,
+            WrongPhase | AlreadyCommitted | NotYourTurn | TileAlreadyRevealed | InvalidTileIndex
+            | GameAlreadyEnded | SelfPlay | SeriesFinished | SeriesGameActive | SeriesOnHold
+            | RotationCooldown | NoTimeControl | ClockNotExpired | OutOfTime | UpgradeNotReady
+            | BatchLengthMismatch | InvalidSalvo | ScanUsed | DisputeWindowOpen | BettingClosed
+            | StaleMove | PlayerEliminated | ZeroCommitment | DuplicateCommitment | SessionsActive
+                => ErrorCategory::Rules,
+            InvalidProof | VkNotSet | VkParseError | BoardMismatch | InvalidEvidence | AttackMismatch
+            | ProofWrongLength | PublicInputsMismatch
+                => ErrorCategory::Proof,
+            NotAdmin | NotPlayer | NoAttestor | NotOrganizer | NotSigner | NotEnoughApprovals
+            | TutorialRequired | NotBroadcast | SessionKeyInvalid
+                => ErrorCategory::Auth,
+            InvalidBestOf | InvalidConfig | TooManyWatchers | SessionExists | AlreadyInitialized
+            | AlreadyReferred | SessionIdReused | SeriesExists
+                => ErrorCategory::Config,
+            StakeCapExceeded | NegativeStake | StakeOverflow | AlreadySettled | InsufficientBalance
+            | StakeTooLow | StakeTooHigh
+                => ErrorCategory::Stakes,
+            ContractPaused | NotInitialized | InsufficientResources | FeatureDisabled
+                => ErrorCategory::Halted,
+            InternalInvariant
+                => ErrorCategory::Internal,
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! # Commit deadline
+//!
+//! With `config.commit_window` set, both boards must be committed within
+//! that many ledgers of the session opening. Once the window has passed
+//! with a board still missing, anyone may call `expire_uncommitted`: both
+//! stakes (and any bond already posted) are refunded and the session is
+//! closed, as `cancel_game` would, so a player who never commits cannot
+//! hold the other's stake hostage.
+
+use soroban_sdk::{contractevent, contractimpl, Env};
+
+use crate::{Error, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+#[contractevent]
+pub struct CommitExpired {
+    #[topic]
+    pub session_id:        u32,
+    pub player1_committed: bool,
+    pub player2_committed: bool,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Refund and close a session whose commit window lapsed before both
+    /// boards were committed. Callable by anyone.
+    pub fn expire_uncommitted(env: Env, session_id: u32) -> Result<(), Error> {
+        let mut game = Self::get_game(env.clone(), session_id)?;
+        if game.phase != Phase::WaitingForCommits { return Err(Error::WrongPhase);    }
+        if game.config.commit_window == 0         { return Err(Error::NoTimeControl); }
+
+        let deadline = game.last_action_ledger.saturating_add(game.config.commit_window);
+        if env.ledger().sequence() <= deadline { return Err(Error::ClockNotExpired); }
+
+        Self::settle_refund(&env, session_id, &mut game)?;
+        Self::remove_game(&env, session_id, &game);
+        CommitExpired {
+            session_id,
+            player1_committed: game.player1_committed,
+            player2_committed: game.player2_committed,
+        }
+        .publish(&env);
+        Ok(())
+    }
+}
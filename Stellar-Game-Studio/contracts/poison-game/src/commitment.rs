@@ -0,0 +1,49 @@
+//! # Commitment schemes
+//!
+//! Boards are committed to as `pedersen_hash([tiles..., salt, owner,
+//! session_id])` by default. A session with `config.commitment_scheme =
+//! Poseidon` commits with `poseidon2([tiles..., salt, owner, session_id])`
+//! instead, which is cheaper to hash inside the circuit. The reveal public
+//! inputs are built exactly as for Pedersen; only the circuit differs
+//! (`circuits/poison_poseidon`), so Poseidon reveals verify against their
+//! own VK slot, installed with `set_poseidon_vk`.
+//!
+//! The Poseidon slot is a single key, like the scan and lite keys: it is
+//! not versioned, so Poseidon games always verify against the key
+//! installed at the time. There is no Poseidon scan or lite circuit, so
+//! those features stay closed to Poseidon sessions.
+
+use soroban_sdk::{contractimpl, contracttype, vec, Bytes, Env, IntoVal};
+
+use crate::{DataKey, Error, GameConfig, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CommitmentScheme {
+    Pedersen = 0,
+    Poseidon = 1,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Install the Poseidon reveal circuit's VK.
+    pub fn set_poseidon_vk(env: Env, vk_bytes: Bytes) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_poseidon_vk", vec![&env, vk_bytes.clone().into_val(&env)])?;
+        Self::install_vk(&env, &DataKey::PoseidonVk, &vk_bytes)
+    }
+
+    pub fn has_poseidon_vk(env: Env) -> bool {
+        env.storage().persistent().has(&DataKey::PoseidonVk)
+    }
+}
+
+impl PoisonGameContract {
+    pub(crate) fn validate_commitment_scheme(env: &Env, config: &GameConfig) -> Result<(), Error> {
+        if config.commitment_scheme == CommitmentScheme::Pedersen { return Ok(()); }
+        if config.lite                                            { return Err(Error::InvalidConfig); }
+        if !Self::has_poseidon_vk(env.clone())                    { return Err(Error::VkNotSet);      }
+        Ok(())
+    }
+}
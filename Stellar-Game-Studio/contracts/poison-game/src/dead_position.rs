@@ -0,0 +1,44 @@
+//! # Dead positions
+//!
+//! An attacker still short of specials can only win while the opponent's
+//! board has enough unrevealed tiles left to hold them. Once neither
+//! player can reach every Poison + Shield any more — at the latest when
+//! both boards are exhausted — the game cannot be decided, so it ends
+//! straight away as a draw with both stakes refunded (`GameEnded` with
+//! winner 0 and `Outcome::DrawBoardExhausted`).
+//!
+//! Under `FirstPoisonLoses` a board stays live while it has unrevealed
+//! tiles that could hide a Poison. `MostSpecialsAfterNRounds` games whose
+//! boards are both exhausted before the round limit go to the tiebreak
+//! early instead of drawing.
+
+use soroban_sdk::Env;
+
+use crate::{BoardMask, Error, GameState, Outcome, Phase, PoisonGameContract, WinCondition};
+
+impl PoisonGameContract {
+    /// Draw an undecided game neither player can win any more. Returns
+    /// whether the game is now over.
+    pub(crate) fn resolve_dead_position(env: &Env, session_id: u32, game: &mut GameState) -> Result<bool, Error> {
+        if game.phase != Phase::Playing || game.winner != 0 { return Ok(false); }
+        if Self::still_decisive(game, &game.p2_mask, 2) || Self::still_decisive(game, &game.p1_mask, 1) {
+            return Ok(false);
+        }
+        if game.config.win_condition == WinCondition::MostSpecialsAfterNRounds {
+            Self::settle_tiebreak(env, session_id, game)?;
+            return Ok(true);
+        }
+        Self::finish_draw(env, session_id, game, Outcome::DrawBoardExhausted)?;
+        Ok(true)
+    }
+
+    /// Whether further reveals on `slot`'s board, `found` so far, can
+    /// still decide the game.
+    fn still_decisive(game: &GameState, found: &BoardMask, slot: u32) -> bool {
+        let unrevealed = game.board_size - found.revealed.count_ones();
+        if game.config.win_condition != WinCondition::AllSpecials { return unrevealed > 0; }
+        let (poison, shield) = found.specials();
+        let missing = game.config.poison_on(slot).saturating_sub(poison) + game.config.shield_count.saturating_sub(shield);
+        missing <= unrevealed
+    }
+}
@@ -0,0 +1,39 @@
+//! # Decoy tiles
+//!
+//! With `config.decoy_count > 0` each board also hides that many Decoy
+//! tiles, which look special when revealed but count toward nothing. The
+//! main reveal circuit only knows three tile types, so decoy games reveal
+//! with the decoy circuit (`circuits/poison_decoy`): the same public inputs
+//! plus `decoy_count`, verified against its own VK slot installed with
+//! `set_decoy_vk`.
+//!
+//! Like the scan and lite keys the decoy slot is a single, unversioned
+//! key. There is no Poseidon, scan or lite form of the decoy circuit, so
+//! decoy sessions commit with Pedersen and cannot be lite or scanned.
+
+use soroban_sdk::{contractimpl, vec, Bytes, Env, IntoVal};
+
+use crate::{CommitmentScheme, DataKey, Error, GameConfig, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Install the decoy reveal circuit's VK.
+    pub fn set_decoy_vk(env: Env, vk_bytes: Bytes) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_decoy_vk", vec![&env, vk_bytes.clone().into_val(&env)])?;
+        Self::install_vk(&env, &DataKey::DecoyVk, &vk_bytes)
+    }
+
+    pub fn has_decoy_vk(env: Env) -> bool {
+        env.storage().persistent().has(&DataKey::DecoyVk)
+    }
+}
+
+impl PoisonGameContract {
+    pub(crate) fn validate_decoys(env: &Env, config: &GameConfig) -> Result<(), Error> {
+        if config.decoy_count == 0 { return Ok(()); }
+        if config.lite || config.commitment_scheme != CommitmentScheme::Pedersen { return Err(Error::InvalidConfig); }
+        if !Self::has_decoy_vk(env.clone())                                      { return Err(Error::VkNotSet);      }
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+//! # Dispute window
+//!
+//! With `config.dispute_window > 0` a finished game is not paid out right
+//! away. `finish_game` records the winner, ratings and result as usual but
+//! holds settlement until `finalize_after`; until then the loser may
+//! `challenge` the result, and once it has passed anyone may `finalize`.
+//! Both stay open while the contract is paused, as refunds do, so a pause
+//! never strands a held pot or runs out a challenger's window.
+//!
+//! Evidence is a reveal proof against the winner's own board commitment
+//! for a tile the winner already answered differently during the game. Two
+//! valid proofs giving one committed tile two types mean the winner's
+//! answers cannot be trusted, so an upheld challenge overturns the result
+//! and pays the challenger. Ratings and stats recorded at finish are not
+//! rewound; the archived result is rewritten with `Outcome::Overturned`.
+
+use soroban_sdk::{contractevent, contractimpl, contracttype, Bytes, Env};
+use ultrahonk_soroban_verifier::PROOF_BYTES;
+
+use crate::{u32_field, Error, Phase, RevealCircuit, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::PUB_INPUT_BYTES;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Evidence {
+    pub tile_index: u32,
+    pub tile_type:  u32,
+    pub proof:      Bytes,
+}
+
+#[contractevent]
+pub struct ResultOverturned {
+    #[topic]
+    pub session_id: u32,
+    pub winner:     u32, // the successful challenger
+    pub tile_index: u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Loser contests a held result with a contradicting reveal proof.
+    pub fn challenge(env: Env, session_id: u32, evidence: Evidence) -> Result<(), Error> {
+        let mut game = Self::get_game(env.clone(), session_id)?;
+        if game.phase != Phase::Finished || game.settled  { return Err(Error::WrongPhase); }
+        if env.ledger().sequence() > game.finalize_after { return Err(Error::WrongPhase); }
+
+        let winner_num = game.winner;
+        let loser_num  = 3 - winner_num;
+        let loser = if loser_num == 1 { game.player1.clone() } else { game.player2.clone() };
+        loser.require_auth();
+
+        // The winner must already have answered this tile differently
+        let winner_board = if winner_num == 1 { &game.p1_revealed } else { &game.p2_revealed };
+        let contradicts = winner_board.iter()
+            .any(|r| r.tile_index == evidence.tile_index && r.tile_type != evidence.tile_type);
+        if !contradicts { return Err(Error::InvalidEvidence); }
+        if evidence.proof.len() != PROOF_BYTES as u32 { return Err(Error::ProofWrongLength); }
+        Self::check_verify_budget(&env, 1)?;
+
+        let mut pub_inputs = Self::board_inputs(&env, &game, session_id, winner_num);
+        pub_inputs.append(&u32_field(&env, evidence.tile_index));
+        pub_inputs.append(&u32_field(&env, evidence.tile_type));
+        pub_inputs.append(&u32_field(&env, game.config.poison_on(winner_num)));
+        pub_inputs.append(&u32_field(&env, game.config.shield_count));
+        let mut expected_len = PUB_INPUT_BYTES;
+        if game.config.decoy_count > 0 {
+            pub_inputs.append(&u32_field(&env, game.config.decoy_count));
+            expected_len += 32;
+        }
+        if pub_inputs.len() != expected_len { return Err(Error::InternalInvariant); }
+        Self::verify_reveal(&env, game.vk_version, RevealCircuit::of(&game.config), &evidence.proof, &pub_inputs)?;
+
+        game.winner     = loser_num;
+        game.overturned = true;
+        game.perfect    = false;
+        Self::settle_win(&env, session_id, &mut game, loser_num == 1)?;
+        Self::close_escrow(&env);
+        Self::mark_overturned(&env, session_id, &game);
+        Self::save_game(&env, session_id, &game);
+
+        ResultOverturned { session_id, winner: loser_num, tile_index: evidence.tile_index }.publish(&env);
+        Ok(())
+    }
+
+    /// Pay out a held result once its dispute window has passed unchallenged.
+    pub fn finalize(env: Env, session_id: u32) -> Result<(), Error> {
+        let mut game = Self::get_game(env.clone(), session_id)?;
+        if game.phase != Phase::Finished || game.settled   { return Err(Error::WrongPhase);        }
+        if env.ledger().sequence() <= game.finalize_after { return Err(Error::DisputeWindowOpen); }
+
+        let player1_won = game.winner == 1;
+        Self::settle_win(&env, session_id, &mut game, player1_won)?;
+        Self::close_escrow(&env);
+        Self::save_game(&env, session_id, &game);
+        Ok(())
+    }
+}
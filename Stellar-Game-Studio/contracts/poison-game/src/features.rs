@@ -0,0 +1,46 @@
+//! # Feature flags
+//!
+//! An admin-managed bitmap of *disabled* subsystems, so operators can roll
+//! a subsystem out gradually or kill-switch it without an upgrade. Nothing
+//! is disabled by default. A flag only gates the entrypoints that open new
+//! activity in its subsystem; sessions and series already under way can
+//! always play out and settle.
+
+use soroban_sdk::{contractimpl, vec, Env, IntoVal};
+
+use crate::{DataKey, Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+/// Best-of-N series (tournament play): `start_series`.
+pub const FEATURE_SERIES:    u32 = 1 << 0;
+/// Spectator side bets: `place_bet`.
+pub const FEATURE_SIDE_BETS: u32 = 1 << 1;
+/// Lite-mode sessions: `start_game_with_config` with `config.lite`.
+pub const FEATURE_LITE:      u32 = 1 << 2;
+/// Three- and four-player games: `start_ffa`.
+pub const FEATURE_FFA:       u32 = 1 << 3;
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Replace the disabled-feature bitmap (`FEATURE_*` bits).
+    pub fn set_disabled_features(env: Env, disabled: u32) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_disabled_features", vec![&env, disabled.into_val(&env)])?;
+        env.storage().instance().set(&DataKey::DisabledFeatures, &disabled);
+        Ok(())
+    }
+
+    pub fn get_disabled_features(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::DisabledFeatures).unwrap_or(0)
+    }
+
+    pub fn is_feature_enabled(env: Env, feature: u32) -> bool {
+        Self::get_disabled_features(env) & feature == 0
+    }
+}
+
+impl PoisonGameContract {
+    pub(crate) fn check_feature(env: &Env, feature: u32) -> Result<(), Error> {
+        if !Self::is_feature_enabled(env.clone(), feature) { return Err(Error::FeatureDisabled); }
+        Ok(())
+    }
+}
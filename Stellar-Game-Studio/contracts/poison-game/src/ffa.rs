@@ -0,0 +1,266 @@
+//! # Free-for-all
+//!
+//! Three- or four-player games. Every player commits a default board (15
+//! tiles, 2 Poison, 1 Shield, verified by the standard reveal circuit) and
+//! turns rotate through `players` in order. On their turn a player attacks
+//! one tile on any opponent still standing; that opponent answers with a
+//! proof exactly as in a two-player game, and a Shield keeps the turn with
+//! the attacker. A player is eliminated once every special on their board
+//! has been found, and the last board standing wins the combined pot.
+//!
+//! Free-for-all games are a separate entity from two-player sessions, with
+//! their own id space, and are untimed. Each player's stake is locked in
+//! this contract's GameHub escrow pool at start and the pot is released to
+//! the winner; until every board is committed any player may cancel and
+//! refund everyone. Gated by `FEATURE_FFA`.
+//!
+//! A board's commitment binds its player's address and the free-for-all
+//! id, where a two-player board binds its seat and session id.
+
+use soroban_sdk::{contractevent, contractimpl, contracttype, vec, Address, Bytes, BytesN, Env, IntoVal, Vec};
+
+use crate::{commitment_inputs, u32_field, BoardMask, DataKey, Error, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient, RevealCircuit};
+use crate::sandbox::Hub;
+use crate::{DEFAULT_POISON, DEFAULT_SHIELD, FEATURE_FFA, GAME_TTL_LEDGERS, PUB_INPUT_BYTES, TOTAL_TILES};
+
+pub const MIN_FFA_PLAYERS: u32 = 3;
+pub const MAX_FFA_PLAYERS: u32 = 4;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FfaGame {
+    pub players:        Vec<Address>,    // turn order; slots are 1-based indexes into it
+    pub stake:          i128,            // per player
+    pub commitments:    Vec<BytesN<32>>, // zero until that slot commits
+    pub committed:      u32,             // bit (slot - 1) set once committed
+    pub phase:          Phase,
+    pub current_turn:   u32,
+    // Slot whose board was attacked and the tile awaiting its answer (0 = none)
+    pub pending_target: u32,
+    pub pending_tile:   u32,
+    pub boards:         Vec<BoardMask>,  // reveals on each slot's board
+    pub eliminated:     u32,             // bit (slot - 1) set once eliminated
+    pub winner:         u32,             // 0 = none
+    pub vk_version:     u32,
+    pub move_count:     u32,
+    pub hub:            Address,         // GameHub the stakes were locked on
+}
+
+impl FfaGame {
+    fn slot_of(&self, player: &Address) -> Option<u32> {
+        self.players.first_index_of(player).map(|i| i + 1)
+    }
+
+    fn is_standing(&self, slot: u32) -> bool {
+        self.eliminated & (1 << (slot - 1)) == 0
+    }
+
+    /// Next standing slot after `slot` in turn order.
+    fn next_standing(&self, slot: u32) -> u32 {
+        let n = self.players.len();
+        let mut next = slot;
+        loop {
+            next = next % n + 1;
+            if self.is_standing(next) { return next; }
+        }
+    }
+}
+
+#[contractevent]
+pub struct FfaEliminated {
+    #[topic]
+    pub ffa_id:      u32,
+    pub player_slot: u32,
+    pub move_number: u32,
+}
+
+#[contractevent]
+pub struct FfaWon {
+    #[topic]
+    pub ffa_id:      u32,
+    pub winner:      Address,
+    pub pot:         i128,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Open a free-for-all between `players`, each locking `stake`. Every
+    /// player signs the id, the full player list and the stake.
+    pub fn start_ffa(env: Env, ffa_id: u32, players: Vec<Address>, stake: i128) -> Result<(), Error> {
+        if players.len() < MIN_FFA_PLAYERS || players.len() > MAX_FFA_PLAYERS { return Err(Error::InvalidConfig); }
+        // Checked before authorizing, as one address cannot authorize the same call twice
+        for (i, player) in players.iter().enumerate() {
+            if players.first_index_of(&player) != Some(i as u32) { return Err(Error::SelfPlay); }
+        }
+        for player in players.iter() {
+            player.require_auth_for_args(vec![
+                &env, ffa_id.into_val(&env), players.clone().into_val(&env), stake.into_val(&env),
+            ]);
+        }
+        Self::check_accepting_games(&env)?;
+        Self::check_feature(&env, FEATURE_FFA)?;
+        if stake < 0 { return Err(Error::NegativeStake); }
+        let key = DataKey::Ffa(ffa_id);
+        if env.storage().temporary().has(&key) { return Err(Error::SessionExists); }
+
+        let hub = Self::get_hub(env.clone())?;
+        if stake > 0 {
+            let (escrow, this) = (Hub::new(&env, &hub), env.current_contract_address());
+            for player in players.iter() { escrow.lock_points(&this, &player, &stake); }
+            Self::open_escrow(&env);
+        }
+
+        let n = players.len();
+        let zero = BytesN::from_array(&env, &[0u8; 32]);
+        let mut commitments = Vec::new(&env);
+        let mut boards = Vec::new(&env);
+        for _ in 0..n {
+            commitments.push_back(zero.clone());
+            boards.push_back(BoardMask::new());
+        }
+        let game = FfaGame {
+            players,
+            stake,
+            commitments,
+            committed:      0,
+            phase:          Phase::WaitingForCommits,
+            current_turn:   1,
+            pending_target: 0,
+            pending_tile:   0,
+            boards,
+            eliminated:     0,
+            winner:         0,
+            vk_version:     Self::get_vk_version(env.clone()),
+            move_count:     0,
+            hub,
+        };
+        Self::save_ffa(&env, ffa_id, &game);
+        Ok(())
+    }
+
+    pub fn commit_ffa_board(env: Env, ffa_id: u32, player: Address, board_hash: BytesN<32>) -> Result<(), Error> {
+        player.require_auth();
+        let mut game = Self::get_ffa(env.clone(), ffa_id)?;
+        if game.phase != Phase::WaitingForCommits { return Err(Error::WrongPhase); }
+        let slot = game.slot_of(&player).ok_or(Error::NotPlayer)?;
+        let bit = 1 << (slot - 1);
+        if game.committed & bit != 0 { return Err(Error::AlreadyCommitted); }
+
+        game.commitments.set(slot - 1, board_hash);
+        game.committed |= bit;
+        if game.committed.count_ones() == game.players.len() { game.phase = Phase::Playing; }
+        game.move_count += 1;
+        Self::save_ffa(&env, ffa_id, &game);
+        Ok(())
+    }
+
+    /// Before every board is committed, any player may call the game off
+    /// and every stake is returned.
+    pub fn cancel_ffa(env: Env, ffa_id: u32, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+        let game = Self::get_ffa(env.clone(), ffa_id)?;
+        if game.phase != Phase::WaitingForCommits { return Err(Error::WrongPhase); }
+        game.slot_of(&caller).ok_or(Error::NotPlayer)?;
+
+        if game.stake > 0 {
+            let (hub, this) = (Hub::new(&env, &game.hub), env.current_contract_address());
+            for player in game.players.iter() { hub.release_points(&this, &player, &game.stake); }
+            Self::close_escrow(&env);
+        }
+        env.storage().temporary().remove(&DataKey::Ffa(ffa_id));
+        Ok(())
+    }
+
+    /// The player on turn attacks `tile_index` on `target`'s board.
+    pub fn ffa_attack(env: Env, ffa_id: u32, attacker: Address, target: Address, tile_index: u32) -> Result<(), Error> {
+        attacker.require_auth();
+        Self::check_operational(&env)?;
+        let mut game = Self::get_ffa(env.clone(), ffa_id)?;
+        if game.phase != Phase::Playing    { return Err(Error::WrongPhase); }
+        if game.pending_target != 0        { return Err(Error::WrongPhase); }
+        let slot = game.slot_of(&attacker).ok_or(Error::NotPlayer)?;
+        if slot != game.current_turn       { return Err(Error::NotYourTurn); }
+        let target_slot = game.slot_of(&target).ok_or(Error::NotPlayer)?;
+        if target_slot == slot             { return Err(Error::SelfPlay); }
+        if !game.is_standing(target_slot)  { return Err(Error::PlayerEliminated); }
+        if tile_index >= TOTAL_TILES       { return Err(Error::InvalidTileIndex); }
+        if game.boards.get_unchecked(target_slot - 1).is_revealed(tile_index) {
+            return Err(Error::TileAlreadyRevealed);
+        }
+
+        game.pending_target = target_slot;
+        game.pending_tile   = tile_index;
+        game.move_count += 1;
+        Self::save_ffa(&env, ffa_id, &game);
+        Ok(())
+    }
+
+    /// The attacked player proves the pending tile's type.
+    pub fn ffa_respond(env: Env, ffa_id: u32, defender: Address, tile_type: u32, proof_blob: Bytes) -> Result<(), Error> {
+        defender.require_auth();
+        Self::check_operational(&env)?;
+        let mut game = Self::get_ffa(env.clone(), ffa_id)?;
+        if game.phase != Phase::Playing || game.pending_target == 0 { return Err(Error::WrongPhase); }
+        let slot = game.slot_of(&defender).ok_or(Error::NotPlayer)?;
+        if slot != game.pending_target { return Err(Error::NotYourTurn); }
+        if tile_type > 2               { return Err(Error::InvalidProof); }
+        Self::check_verify_budget(&env, 1)?;
+
+        let tile_index = game.pending_tile;
+        let mut pub_inputs = commitment_inputs(&env, &game.commitments.get_unchecked(slot - 1), &defender, ffa_id);
+        pub_inputs.append(&u32_field(&env, tile_index));
+        pub_inputs.append(&u32_field(&env, tile_type));
+        pub_inputs.append(&u32_field(&env, DEFAULT_POISON));
+        pub_inputs.append(&u32_field(&env, DEFAULT_SHIELD));
+        if pub_inputs.len() != PUB_INPUT_BYTES { return Err(Error::InternalInvariant); }
+        Self::verify_reveal(&env, game.vk_version, RevealCircuit::Main, &proof_blob, &pub_inputs)?;
+
+        let mut board = game.boards.get_unchecked(slot - 1);
+        board.reveal(tile_index, tile_type);
+        let (poison, shield) = board.specials();
+        game.boards.set(slot - 1, board);
+        game.pending_target = 0;
+        game.move_count += 1;
+
+        if poison >= DEFAULT_POISON && shield >= DEFAULT_SHIELD {
+            game.eliminated |= 1 << (slot - 1);
+            FfaEliminated { ffa_id, player_slot: slot, move_number: game.move_count }.publish(&env);
+        }
+        let standing = game.players.len() - game.eliminated.count_ones();
+        if standing == 1 {
+            let winner = game.next_standing(slot);
+            game.winner = winner;
+            game.phase  = Phase::Finished;
+            Self::save_ffa(&env, ffa_id, &game);
+            return Self::pay_ffa_pot(&env, ffa_id, &game);
+        }
+        // A Shield keeps the turn with the attacker
+        if tile_type != 2 { game.current_turn = game.next_standing(game.current_turn); }
+        Self::save_ffa(&env, ffa_id, &game);
+        Ok(())
+    }
+
+    pub fn get_ffa(env: Env, ffa_id: u32) -> Result<FfaGame, Error> {
+        env.storage().temporary().get(&DataKey::Ffa(ffa_id)).ok_or(Error::GameNotFound)
+    }
+}
+
+impl PoisonGameContract {
+    fn save_ffa(env: &Env, ffa_id: u32, game: &FfaGame) {
+        let key = DataKey::Ffa(ffa_id);
+        env.storage().temporary().set(&key, game);
+        env.storage().temporary().extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+    }
+
+    fn pay_ffa_pot(env: &Env, ffa_id: u32, game: &FfaGame) -> Result<(), Error> {
+        let winner = game.players.get_unchecked(game.winner - 1);
+        let pot = game.stake.checked_mul(game.players.len() as i128).ok_or(Error::StakeOverflow)?;
+        if pot > 0 {
+            Hub::new(env, &game.hub).release_points(&env.current_contract_address(), &winner, &pot);
+            Self::close_escrow(env);
+        }
+        FfaWon { ffa_id, winner, pot }.publish(env);
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+//! # Game storage
+//!
+//! Live games normally sit in temporary storage, which is cheap but is
+//! deleted if its TTL lapses. Sessions staking at least the operator's
+//! `persistent_stake` threshold (the larger of the two stakes) are flagged
+//! `persistent` at open and kept in persistent storage instead, which is
+//! archived rather than deleted and can always be restored, so a
+//! high-stakes game cannot be lost mid-game.
+//!
+//! Every read and write of a game goes through the helpers below, which
+//! pick the storage map from the game's flag. Loads try temporary storage
+//! first, since that is where almost every game lives.
+//!
+//! A `GameState` is stored as two entries. `GameMeta` holds the fields
+//! fixed once both boards are committed — players, stakes, commitments,
+//! rules — and is rewritten only when it actually changes (commits, seat
+//! rotation). `GameProgress` holds everything a move touches and is the
+//! only entry most moves write. `load_game` joins the two back into the
+//! `GameState` every entrypoint works with.
+
+use soroban_sdk::{contractimpl, contracttype, vec, Address, BytesN, Env, IntoVal, Symbol, Vec};
+
+use crate::{DataKey, Error, GameState, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::{BoardMask, GameConfig, LiteBoard, Phase, RevealedTile, SimulRound, Wager, PLAYER_TTL_LEDGERS};
+
+/// Cold half of a `GameState`, stored under `DataKey::Game`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameMeta {
+    pub player1:            Address,
+    pub player2:            Address,
+    pub player1_points:     i128,
+    pub player2_points:     i128,
+    pub player1_commitment: BytesN<32>,
+    pub player2_commitment: BytesN<32>,
+    pub board_owners:       Vec<Address>,
+    pub locale:             Option<Symbol>,
+    pub series_id:          Option<u32>,
+    pub board_size:         u32,
+    pub config:             GameConfig,
+    pub hub:                Address,
+    pub pooled:             bool,
+    pub fee_bps:            u32,
+    pub jackpot_bps:        u32,
+    pub wagers:             Vec<Wager>,
+    pub vk_version:         u32,
+    pub persistent:         bool,
+}
+
+/// Hot half of a `GameState`, stored under `DataKey::GameProgress`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameProgress {
+    pub player1_committed:  bool,
+    pub player2_committed:  bool,
+    pub phase:              Phase,
+    pub current_turn:       u32,
+    pub pending_tiles:      Vec<u32>,
+    pub p1_revealed:        Vec<RevealedTile>,
+    pub p2_revealed:        Vec<RevealedTile>,
+    pub p1_mask:            BoardMask,
+    pub p2_mask:            BoardMask,
+    pub winner:             u32,
+    pub move_count:         u32,
+    pub player1_time_left:  u32,
+    pub player2_time_left:  u32,
+    pub last_action_ledger: u32,
+    pub settled:            bool,
+    pub skip_next_turn:     u32,
+    pub consecutive_bonus:  u32,
+    pub pending_scan:       Option<u32>,
+    pub player1_scanned:    bool,
+    pub player2_scanned:    bool,
+    pub lite_boards:        Vec<LiteBoard>,
+    pub forfeited:          u32,
+    pub finalize_after:     u32,
+    pub overturned:         bool,
+    pub perfect:            bool,
+    pub round:              SimulRound,
+    pub rounds_played:      u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Sessions opened from now on with a stake of at least `min_stake`
+    /// are kept in persistent storage. 0 turns the threshold off.
+    pub fn set_persistent_stake(env: Env, min_stake: i128) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_persistent_stake", vec![&env, min_stake.into_val(&env)])?;
+        if min_stake < 0 { return Err(Error::NegativeStake); }
+        env.storage().instance().set(&DataKey::PersistentStake, &min_stake);
+        Ok(())
+    }
+
+    pub fn get_persistent_stake(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::PersistentStake).unwrap_or(0)
+    }
+}
+
+impl PoisonGameContract {
+    /// Does a session with this larger stake qualify for persistent storage?
+    pub(crate) fn is_persistent_stake(env: &Env, stake: i128) -> bool {
+        let threshold = Self::get_persistent_stake(env.clone());
+        threshold > 0 && stake >= threshold
+    }
+
+    pub(crate) fn load_game(env: &Env, session_id: u32) -> Option<GameState> {
+        Self::load_game_parts(env, session_id).map(|(meta, progress)| Self::join_game(meta, progress))
+    }
+
+    /// Both halves of a game as stored, for readers that need neither joined.
+    pub(crate) fn load_game_parts(env: &Env, session_id: u32) -> Option<(GameMeta, GameProgress)> {
+        let (meta_key, progress_key) = (DataKey::Game(session_id), DataKey::GameProgress(session_id));
+        let temporary = env.storage().temporary();
+        if let Some(meta) = temporary.get(&meta_key) {
+            return temporary.get(&progress_key).map(|progress| (meta, progress));
+        }
+        let persistent = env.storage().persistent();
+        let meta = persistent.get(&meta_key)?;
+        persistent.get(&progress_key).map(|progress| (meta, progress))
+    }
+
+    pub(crate) fn has_game(env: &Env, session_id: u32) -> bool {
+        let key = DataKey::Game(session_id);
+        env.storage().temporary().has(&key) || env.storage().persistent().has(&key)
+    }
+
+    /// Write the game's progress, and its meta only when that changed.
+    pub(crate) fn save_game(env: &Env, session_id: u32, game: &GameState) {
+        let (meta_key, progress_key) = (DataKey::Game(session_id), DataKey::GameProgress(session_id));
+        let (meta, progress) = Self::split_game(game);
+        if game.persistent {
+            let storage = env.storage().persistent();
+            if storage.get(&meta_key) != Some(meta.clone()) { storage.set(&meta_key, &meta); }
+            storage.set(&progress_key, &progress);
+            storage.extend_ttl(&meta_key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+            storage.extend_ttl(&progress_key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        } else {
+            let storage = env.storage().temporary();
+            if storage.get(&meta_key) != Some(meta.clone()) { storage.set(&meta_key, &meta); }
+            storage.set(&progress_key, &progress);
+        }
+    }
+
+    pub(crate) fn remove_game(env: &Env, session_id: u32, game: &GameState) {
+        let (meta_key, progress_key) = (DataKey::Game(session_id), DataKey::GameProgress(session_id));
+        if game.persistent {
+            env.storage().persistent().remove(&meta_key);
+            env.storage().persistent().remove(&progress_key);
+        } else {
+            env.storage().temporary().remove(&meta_key);
+            env.storage().temporary().remove(&progress_key);
+        }
+    }
+
+    fn split_game(game: &GameState) -> (GameMeta, GameProgress) {
+        let meta = GameMeta {
+            player1:            game.player1.clone(),
+            player2:            game.player2.clone(),
+            player1_points:     game.player1_points,
+            player2_points:     game.player2_points,
+            player1_commitment: game.player1_commitment.clone(),
+            player2_commitment: game.player2_commitment.clone(),
+            board_owners:       game.board_owners.clone(),
+            locale:             game.locale.clone(),
+            series_id:          game.series_id,
+            board_size:         game.board_size,
+            config:             game.config.clone(),
+            hub:                game.hub.clone(),
+            pooled:             game.pooled,
+            fee_bps:            game.fee_bps,
+            jackpot_bps:        game.jackpot_bps,
+            wagers:             game.wagers.clone(),
+            vk_version:         game.vk_version,
+            persistent:         game.persistent,
+        };
+        let progress = GameProgress {
+            player1_committed:  game.player1_committed,
+            player2_committed:  game.player2_committed,
+            phase:              game.phase.clone(),
+            current_turn:       game.current_turn,
+            pending_tiles:      game.pending_tiles.clone(),
+            p1_revealed:        game.p1_revealed.clone(),
+            p2_revealed:        game.p2_revealed.clone(),
+            p1_mask:            game.p1_mask.clone(),
+            p2_mask:            game.p2_mask.clone(),
+            winner:             game.winner,
+            move_count:         game.move_count,
+            player1_time_left:  game.player1_time_left,
+            player2_time_left:  game.player2_time_left,
+            last_action_ledger: game.last_action_ledger,
+            settled:            game.settled,
+            skip_next_turn:     game.skip_next_turn,
+            consecutive_bonus:  game.consecutive_bonus,
+            pending_scan:       game.pending_scan,
+            player1_scanned:    game.player1_scanned,
+            player2_scanned:    game.player2_scanned,
+            lite_boards:        game.lite_boards.clone(),
+            forfeited:          game.forfeited,
+            finalize_after:     game.finalize_after,
+            overturned:         game.overturned,
+            perfect:            game.perfect,
+            round:              game.round.clone(),
+            rounds_played:      game.rounds_played,
+        };
+        (meta, progress)
+    }
+
+    fn join_game(meta: GameMeta, progress: GameProgress) -> GameState {
+        GameState {
+            player1:            meta.player1,
+            player2:            meta.player2,
+            player1_points:     meta.player1_points,
+            player2_points:     meta.player2_points,
+            player1_commitment: meta.player1_commitment,
+            player2_commitment: meta.player2_commitment,
+            board_owners:       meta.board_owners,
+            player1_committed:  progress.player1_committed,
+            player2_committed:  progress.player2_committed,
+            phase:              progress.phase,
+            current_turn:       progress.current_turn,
+            pending_tiles:      progress.pending_tiles,
+            p1_revealed:        progress.p1_revealed,
+            p2_revealed:        progress.p2_revealed,
+            p1_mask:            progress.p1_mask,
+            p2_mask:            progress.p2_mask,
+            winner:             progress.winner,
+            locale:             meta.locale,
+            series_id:          meta.series_id,
+            board_size:         meta.board_size,
+            config:             meta.config,
+            hub:                meta.hub,
+            move_count:         progress.move_count,
+            pooled:             meta.pooled,
+            fee_bps:            meta.fee_bps,
+            jackpot_bps:        meta.jackpot_bps,
+            wagers:             meta.wagers,
+            player1_time_left:  progress.player1_time_left,
+            player2_time_left:  progress.player2_time_left,
+            last_action_ledger: progress.last_action_ledger,
+            settled:            progress.settled,
+            vk_version:         meta.vk_version,
+            skip_next_turn:     progress.skip_next_turn,
+            consecutive_bonus:  progress.consecutive_bonus,
+            pending_scan:       progress.pending_scan,
+            player1_scanned:    progress.player1_scanned,
+            player2_scanned:    progress.player2_scanned,
+            lite_boards:        progress.lite_boards,
+            forfeited:          progress.forfeited,
+            finalize_after:     progress.finalize_after,
+            overturned:         progress.overturned,
+            perfect:            progress.perfect,
+            persistent:         meta.persistent,
+            round:              progress.round,
+            rounds_played:      progress.rounds_played,
+        }
+    }
+}
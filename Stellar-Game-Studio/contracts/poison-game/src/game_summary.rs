@@ -0,0 +1,42 @@
+//! # Game summaries
+//!
+//! `get_game` returns the whole `GameState`, both reveal vectors included,
+//! which is far more than a lobby or history list needs per row.
+//! `get_game_summary` reads the stored halves directly and returns only
+//! what a list view shows; specials per side come from the packed
+//! bitmasks (or lite digests), so the reveal vectors are never copied.
+
+use soroban_sdk::{contractimpl, contracttype, Address, Env};
+
+use crate::{Error, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameSummary {
+    pub session_id:       u32,
+    pub player1:          Address,
+    pub player2:          Address,
+    pub phase:            Phase,
+    pub current_turn:     u32, // 1=player1, 2=player2
+    pub player1_specials: u32, // Poison + Shield player1 found on player2's board
+    pub player2_specials: u32,
+    pub winner:           u32, // 0 = none yet
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    pub fn get_game_summary(env: Env, session_id: u32) -> Result<GameSummary, Error> {
+        let (meta, progress) = Self::load_game_parts(&env, session_id).ok_or(Error::GameNotFound)?;
+        Ok(GameSummary {
+            session_id,
+            player1:          meta.player1,
+            player2:          meta.player2,
+            phase:            progress.phase,
+            current_turn:     progress.current_turn,
+            player1_specials: Self::board_specials(&progress.lite_boards, &progress.p2_mask, 2),
+            player2_specials: Self::board_specials(&progress.lite_boards, &progress.p1_mask, 1),
+            winner:           progress.winner,
+        })
+    }
+}
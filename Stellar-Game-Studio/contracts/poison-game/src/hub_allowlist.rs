@@ -0,0 +1,63 @@
+//! # Per-session GameHubs
+//!
+//! One deployment can escrow sessions on more than one GameHub — a
+//! staging and a production hub, or several point economies. The admin
+//! keeps an allowlist with `allow_hub` / `disallow_hub`, and a session
+//! picks its hub with `config.hub` at `start_game_with_config`; `None`
+//! means the deployment's GameHub, which is always allowed.
+//!
+//! The resolved hub is recorded in `GameState.hub` and every stake lock,
+//! payout and refund of the session goes to it, so neither `set_hub` nor
+//! removing the hub from the allowlist strands a live session. Series
+//! (`Series.hub`), lobby listings (`OpenGame.hub`) and free-for-all games
+//! (`FfaGame.hub`) record the hub they locked on the same way, and the
+//! pools stay on the pool hub (`treasury.rs`) while they hold points.
+//! Sessions off the pool hub are never raked.
+
+use soroban_sdk::{contractimpl, vec, Address, Env, IntoVal, Vec};
+
+use crate::{DataKey, Error, GameConfig, GameState, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::sandbox::Hub;
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Let sessions escrow their stakes on `hub`.
+    pub fn allow_hub(env: Env, hub: Address) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "allow_hub", vec![&env, hub.clone().into_val(&env)])?;
+        let mut hubs = Self::get_allowed_hubs(env.clone());
+        if !hubs.contains(&hub) { hubs.push_back(hub); }
+        env.storage().instance().set(&DataKey::HubAllowlist, &hubs);
+        Ok(())
+    }
+
+    /// Stop new sessions from picking `hub`; sessions already on it still settle there.
+    pub fn disallow_hub(env: Env, hub: Address) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "disallow_hub", vec![&env, hub.clone().into_val(&env)])?;
+        let mut hubs = Self::get_allowed_hubs(env.clone());
+        if let Some(i) = hubs.first_index_of(&hub) { hubs.remove(i); }
+        env.storage().instance().set(&DataKey::HubAllowlist, &hubs);
+        Ok(())
+    }
+
+    /// Hubs a session may pick besides the deployment's GameHub.
+    pub fn get_allowed_hubs(env: Env) -> Vec<Address> {
+        env.storage().instance().get(&DataKey::HubAllowlist).unwrap_or(vec![&env])
+    }
+}
+
+impl PoisonGameContract {
+    /// The hub a session opened with `config` escrows on.
+    pub(crate) fn session_hub(env: &Env, config: &GameConfig) -> Result<Address, Error> {
+        let global = Self::get_hub(env.clone())?;
+        let Some(hub) = &config.hub else { return Ok(global) };
+        if *hub != global && !Self::get_allowed_hubs(env.clone()).contains(hub) {
+            return Err(Error::InvalidConfig);
+        }
+        Ok(hub.clone())
+    }
+
+    pub(crate) fn game_hub<'a>(env: &'a Env, game: &GameState) -> Hub<'a> {
+        Hub::new(env, &game.hub)
+    }
+}
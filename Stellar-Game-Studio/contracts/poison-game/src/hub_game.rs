@@ -0,0 +1,118 @@
+//! # HubGame interface
+//!
+//! The game-facing surface a GameHub or tournament contract needs from any
+//! game: open a staked session, poll its status, read its result and cancel
+//! it before play starts. Integrators call it through `HubGameClient`, so
+//! nothing on their side depends on poison-game's own types.
+//!
+//! This contract exports every `HubGame` function. The Rust `impl` below
+//! only forwards to those entrypoints so the compiler keeps them in step
+//! with the trait; contract errors surface to callers as the usual
+//! contract-error invocation failures.
+
+use soroban_sdk::{contractclient, contractimpl, contracttype, panic_with_error, Address, Env};
+
+use crate::{Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::SettlementKind;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SessionStatus {
+    Unknown   = 0, // never opened, or expired from storage
+    Pending   = 1, // opened, waiting for the players to be ready
+    Active    = 2,
+    Finished  = 3,
+    Cancelled = 4, // stakes refunded without a winner
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionResult {
+    pub session_id: u32,
+    pub player1:    Address,
+    pub player2:    Address,
+    pub winner:     u32, // 1 or 2
+}
+
+#[contractclient(name = "HubGameClient")]
+pub trait HubGame {
+    fn start_game(
+        env: Env,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    );
+    fn session_status(env: Env, session_id: u32) -> SessionStatus;
+    fn session_result(env: Env, session_id: u32) -> Option<SessionResult>;
+    fn cancel_game(env: Env, session_id: u32, caller: Address);
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    pub fn session_status(env: Env, session_id: u32) -> SessionStatus {
+        if let Ok(game) = Self::get_game(env.clone(), session_id) {
+            return match game.phase {
+                Phase::WaitingForCommits => SessionStatus::Pending,
+                Phase::Playing           => SessionStatus::Active,
+                Phase::Finished          => SessionStatus::Finished,
+            };
+        }
+        // Expired sessions are known from their receipt or archived result
+        let archived = Self::get_result(env.clone(), session_id).is_some();
+        match Self::get_settlement_receipt(env, session_id) {
+            Some(r) if r.kind == SettlementKind::Refund => SessionStatus::Cancelled,
+            Some(_)                                     => SessionStatus::Finished,
+            None if archived                            => SessionStatus::Finished,
+            None                                        => SessionStatus::Unknown,
+        }
+    }
+
+    /// Winner of a finished session, from the live game or the archive.
+    pub fn session_result(env: Env, session_id: u32) -> Option<SessionResult> {
+        if let Ok(game) = Self::get_game(env.clone(), session_id) {
+            if game.winner == 0 { return None; }
+            return Some(SessionResult {
+                session_id,
+                player1: game.player1,
+                player2: game.player2,
+                winner:  game.winner,
+            });
+        }
+        Self::get_result(env, session_id).map(|r| SessionResult {
+            session_id,
+            player1: r.player1,
+            player2: r.player2,
+            winner:  r.winner,
+        })
+    }
+}
+
+impl HubGame for PoisonGameContract {
+    fn start_game(
+        env:            Env,
+        session_id:     u32,
+        player1:        Address,
+        player2:        Address,
+        player1_points: i128,
+        player2_points: i128,
+    ) {
+        let result = Self::start_game(env.clone(), session_id, player1, player2, player1_points, player2_points);
+        if let Err(e) = result { panic_with_error!(&env, e); }
+    }
+
+    fn session_status(env: Env, session_id: u32) -> SessionStatus {
+        Self::session_status(env, session_id)
+    }
+
+    fn session_result(env: Env, session_id: u32) -> Option<SessionResult> {
+        Self::session_result(env, session_id)
+    }
+
+    fn cancel_game(env: Env, session_id: u32, caller: Address) {
+        if let Err(e) = Self::cancel_game(env.clone(), session_id, caller) { panic_with_error!(&env, e); }
+    }
+}
@@ -0,0 +1,75 @@
+//! # GameHub interface versions
+//!
+//! A v1 hub only learns who won: `end_game(session_id, player1_won)`, or
+//! `refund_game` for a draw. A hub registered as v2 with `set_hub_version`
+//! is sent the full `GameScore` through `end_game_v2` instead, for drawn
+//! games too, so it can pay performance-based rewards. Cancelled sessions
+//! are refunded with `refund_game` on either version.
+//!
+//! Versions are stored per hub address, so a split session's second hub
+//! keeps its own. Hubs default to v1.
+
+use soroban_sdk::{contractimpl, contracttype, vec, Address, Env, IntoVal, Vec};
+
+use crate::{BoardMask, DataKey, Error, GameState, LiteBoard, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+pub const HUB_V1: u32 = 1;
+pub const HUB_V2: u32 = 2;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameScore {
+    pub winner:           u32, // 1|2, 0 on a draw
+    pub loser:            u32, // 1|2, 0 on a draw
+    pub player1_specials: u32, // Poison + Shield player1 found on player2's board
+    pub player2_specials: u32,
+    pub moves:            u32,
+    pub draw:             bool,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Set the GameHub interface version `hub` implements (`HUB_V1` or `HUB_V2`).
+    pub fn set_hub_version(env: Env, hub: Address, version: u32) -> Result<(), Error> {
+        Self::authorize_admin_action(
+            &env, "set_hub_version", vec![&env, hub.clone().into_val(&env), version.into_val(&env)],
+        )?;
+        if version != HUB_V1 && version != HUB_V2 { return Err(Error::InvalidConfig); }
+        env.storage().instance().set(&DataKey::HubVersion(hub), &version);
+        Ok(())
+    }
+
+    pub fn get_hub_version(env: Env, hub: Address) -> u32 {
+        env.storage().instance().get(&DataKey::HubVersion(hub)).unwrap_or(HUB_V1)
+    }
+}
+
+impl PoisonGameContract {
+    /// The score of a finished game, as reported to v2 hubs.
+    pub(crate) fn game_score(game: &GameState) -> GameScore {
+        GameScore {
+            winner:           game.winner,
+            loser:            if game.winner == 0 { 0 } else { 3 - game.winner },
+            player1_specials: Self::specials_found(game, 2),
+            player2_specials: Self::specials_found(game, 1),
+            moves:            game.move_count,
+            draw:             game.winner == 0,
+        }
+    }
+
+    /// Specials revealed so far on `slot`'s board (claimed ones in lite games).
+    fn specials_found(game: &GameState, slot: u32) -> u32 {
+        let mask = if slot == 1 { &game.p1_mask } else { &game.p2_mask };
+        Self::board_specials(&game.lite_boards, mask, slot)
+    }
+
+    /// `specials_found` from a board's stored parts, for readers without a `GameState`.
+    pub(crate) fn board_specials(lite_boards: &Vec<LiteBoard>, mask: &BoardMask, slot: u32) -> u32 {
+        let (poison, shield) = match lite_boards.get(slot - 1) {
+            Some(board) => (board.poison, board.shield),
+            None        => mask.specials(),
+        };
+        poison + shield
+    }
+}
@@ -0,0 +1,85 @@
+//! # Progressive jackpot
+//!
+//! With `jackpot_bps > 0` each new session snapshots the rate and, when it
+//! settles with a winner, that share of the pot is kept back in a
+//! contract-held pool in the GameHub's escrow, next to the protocol fee.
+//! Like the fee, the slice can only come from pots this contract releases
+//! itself, so such sessions are pooled; split-hub, friendly, token sessions
+//! and series legs neither pay in nor win.
+//!
+//! A winner who found every special on the loser's board without ever
+//! revealing one of their own plays a perfect game. `finish_game` flags it,
+//! and settlement pays the whole pool, this game's slice included, to that
+//! winner. An overturned result is never perfect.
+
+use soroban_sdk::{contractevent, contractimpl, vec, Address, Env, IntoVal};
+
+use crate::{DataKey, Error, GameState, Outcome, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::{MAX_BPS, MAX_RAKE_BPS};
+
+#[contractevent]
+pub struct JackpotWon {
+    #[topic]
+    pub session_id: u32,
+    pub winner:     Address,
+    pub points:     i128,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Share of each pot fed to the jackpot in sessions opened from now on.
+    pub fn set_jackpot_bps(env: Env, jackpot_bps: u32) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_jackpot_bps", vec![&env, jackpot_bps.into_val(&env)])?;
+        // The fee and the jackpot slice come out of the same pot
+        if jackpot_bps + Self::get_fee_bps(env.clone()) > MAX_RAKE_BPS { return Err(Error::InvalidConfig); }
+        env.storage().instance().set(&DataKey::JackpotBps, &jackpot_bps);
+        Ok(())
+    }
+
+    pub fn get_jackpot_bps(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::JackpotBps).unwrap_or(0)
+    }
+
+    /// Points waiting for the next perfect game.
+    pub fn get_jackpot(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::Jackpot).unwrap_or(0)
+    }
+}
+
+impl PoisonGameContract {
+    /// The winner found every special and kept all of their own hidden.
+    pub(crate) fn is_perfect_game(game: &GameState) -> bool {
+        if Self::outcome_of(game) != Outcome::AllSpecialsFound { return false; }
+        if game.config.lite {
+            let own = game.lite_boards.get_unchecked(game.winner - 1);
+            return own.poison == 0 && own.shield == 0;
+        }
+        let own = if game.winner == 1 { &game.p1_mask } else { &game.p2_mask };
+        own.poison == 0 && own.shield == 0
+    }
+
+    /// Add this session's slice of `pot` to the jackpot; returns the slice.
+    pub(crate) fn take_jackpot_slice(env: &Env, game: &GameState, pot: i128) -> Result<i128, Error> {
+        if game.hub != Self::pool_hub(env)? { return Ok(0); }
+        let slice = pot.checked_mul(game.jackpot_bps as i128).ok_or(Error::StakeOverflow)? / MAX_BPS as i128;
+        if slice > 0 {
+            let jackpot = Self::get_jackpot(env.clone()) + slice;
+            env.storage().instance().set(&DataKey::Jackpot, &jackpot);
+        }
+        Ok(slice)
+    }
+
+    /// Pay the whole pool to a perfect game's winner.
+    pub(crate) fn pay_jackpot(env: &Env, session_id: u32, winner: &Address) -> Result<(), Error> {
+        let points = Self::get_jackpot(env.clone());
+        if points == 0 { return Ok(()); }
+
+        // Resolved before the pool empties and stops pinning its hub
+        let pool = Self::pool(env)?;
+        env.storage().instance().set(&DataKey::Jackpot, &0i128);
+        pool.release_points(&env.current_contract_address(), winner, &points);
+        JackpotWon { session_id, winner: winner.clone(), points }.publish(env);
+        Ok(())
+    }
+}
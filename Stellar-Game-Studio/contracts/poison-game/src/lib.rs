@@ -1,17 +1,141 @@
 #![no_std]
+#![allow(clippy::too_many_arguments)] // Soroban entrypoints take their arguments flat
 
 //! # ZK Poison Game Contract
 //!
-//! Win condition: first player to find all 3 special tiles
-//! (2 Poison + 1 Shield) on the opponent's board wins immediately.
+//! Win condition: first player to find all special tiles on the opponent's
+//! board (2 Poison + 1 Shield by default, configurable per game) wins immediately.
 //! Points wager is locked / paid out via GameHub.
 //! ZK proof enforced via UltraHonk (bb v0.87.0, keccak oracle).
 
 use soroban_sdk::{
-    contract, contractclient, contracterror, contractimpl, contracttype,
-    Address, Bytes, BytesN, Env, IntoVal, Vec, vec,
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype, xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, Vec, vec,
 };
-use ultrahonk_soroban_verifier::{UltraHonkVerifier, PROOF_BYTES};
+use ultrahonk_soroban_verifier::PROOF_BYTES;
+
+/// A verification key parsed into a ready-to-use verifier.
+#[cfg(not(feature = "testutils"))]
+type ParsedVk = ultrahonk_soroban_verifier::UltraHonkVerifier;
+/// Test builds run on placeholder keys, which are never parsed.
+#[cfg(feature = "testutils")]
+type ParsedVk = ();
+
+mod achievements;
+mod admin_cancel;
+mod active;
+mod archive;
+mod attack_queue;
+mod attestation;
+mod bets;
+mod board_reveal;
+mod bot;
+mod bounty;
+mod clock;
+mod codes;
+mod commit_window;
+mod commitment;
+mod dead_position;
+mod decoy;
+mod dispute;
+mod features;
+mod ffa;
+mod game_store;
+mod game_summary;
+mod hub_game;
+mod hub_allowlist;
+mod hub_version;
+mod jackpot;
+mod limits;
+mod lite;
+mod lobby;
+mod multisig;
+mod nonresponse;
+mod player_games;
+mod postmortem;
+mod practice;
+mod rating;
+mod reconcile;
+mod referral;
+mod relay;
+mod replay;
+mod sandbox;
+mod scan;
+mod season;
+mod seats;
+mod series;
+mod session_key;
+mod settlement;
+mod simultaneous;
+mod spectator;
+mod stats;
+#[cfg(feature = "stress")]
+mod stress;
+mod templates;
+#[cfg(feature = "testutils")]
+pub mod testutils;
+mod token_escrow;
+mod treasury;
+mod trophy;
+mod ttl;
+mod turn_limit;
+mod tutorial;
+mod upgrade;
+mod view;
+mod vk;
+mod win_condition;
+pub use achievements::{Achievement, AchievementUnlocked, Badge, BadgeClient, LEDGERS_PER_DAY};
+pub use admin_cancel::GameCancelled;
+pub use archive::{GameResult, RESULTS_PAGE_SIZE};
+pub use attestation::{Attestation, ResultAttested};
+pub use bets::{Bet, BetMarket, BetPlaced, BetPool};
+pub use board_reveal::{BoardReveal, BoardRevealed};
+pub use bot::{BotPlayer, BotPlayerClient};
+pub use bounty::{BountyPaid, Champion};
+pub use codes::{ErrorCategory, GameEnded, Outcome, CODES_VERSION};
+pub use commit_window::CommitExpired;
+pub use commitment::CommitmentScheme;
+pub use dispute::{Evidence, ResultOverturned};
+pub use features::{FEATURE_FFA, FEATURE_LITE, FEATURE_SERIES, FEATURE_SIDE_BETS};
+pub use ffa::{FfaEliminated, FfaGame, FfaWon, MAX_FFA_PLAYERS, MIN_FFA_PLAYERS};
+pub use game_summary::GameSummary;
+pub use hub_game::{HubGame, HubGameClient, SessionResult, SessionStatus};
+pub use hub_version::{GameScore, HUB_V1, HUB_V2};
+pub use jackpot::JackpotWon;
+pub use lite::LiteBoard;
+pub use limits::{PendingStakeCap, RatingStakeScale, StakeLimits, STAKE_CAP_DELAY_LEDGERS};
+pub use lobby::{OpenGame, OPEN_GAME_LEDGERS};
+pub use player_games::PlayerSession;
+pub use postmortem::Postmortem;
+pub use practice::{HouseBoard, PracticeGame};
+pub use reconcile::{Discrepancy, Drift, EscrowState};
+pub use rating::RatingDecay;
+pub use replay::{GameChanges, MoveRecord};
+pub use sandbox::{HubCall, HubIntent, MAX_HUB_INTENTS};
+pub use scan::{ScanAnswered, ScanRequested, SCAN_WIDTH};
+pub use season::{SeasonRecord, SeasonStarted};
+pub use seats::{SeatRotated, SEAT_ROTATION_COOLDOWN};
+pub use series::{Series, SeriesPayout, SERIES_IDLE_LEDGERS};
+pub use session_key::{SessionKey, SessionKeySet, MAX_SESSION_KEY_LEDGERS};
+pub use settlement::{SettlementKind, SettlementReceipt};
+pub use simultaneous::{RoundDrawn, RoundStep, SimulRound};
+pub use spectator::{BoardView, EmbargoedReveal, RevealReleased, TileStatus, TILE_EMBARGOED};
+pub use stats::PlayerStats;
+#[cfg(feature = "stress")]
+pub use stress::MAX_STRESS_SESSIONS;
+pub use templates::GameTemplate;
+pub use token_escrow::Wager;
+pub use trophy::{Trophy, TrophyMinter, TrophyMinterClient};
+pub use turn_limit::TurnLimitReached;
+pub use tutorial::TutorialState;
+pub use upgrade::{PendingUpgrade, UpgradeScheduled, UPGRADE_DELAY_LEDGERS};
+pub use view::{PoisonGameView, PoisonGameViewClient};
+pub use vk::{StoredVk, VkRotated, VkRotation};
+pub use win_condition::WinCondition;
+
+use vk::RevealCircuit;
+#[cfg(not(feature = "testutils"))]
+use vk::VK_POINTS;
 
 // ============================================================================
 // GameHub Client
@@ -29,6 +153,15 @@ pub trait GameHub {
         player2_points: i128,
     );
     fn end_game(env: Env, session_id: u32, player1_won: bool);
+    /// v2 hubs (see `hub_version.rs`): settle a finished or drawn game with
+    /// its full score, in place of `end_game` and a draw's `refund_game`.
+    fn end_game_v2(env: Env, session_id: u32, score: GameScore);
+    /// Release both players' locked points back to them (no winner).
+    fn refund_game(env: Env, session_id: u32);
+    /// Extended escrow: lock `points` from `player` into this game's pool.
+    fn lock_points(env: Env, game_id: Address, player: Address, points: i128);
+    /// Extended escrow: pay `points` out of this game's pool to `player`.
+    fn release_points(env: Env, game_id: Address, player: Address, points: i128);
 }
 
 // ============================================================================
@@ -39,19 +172,73 @@ pub trait GameHub {
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum Error {
-    GameNotFound        = 1,
-    NotPlayer           = 2,
-    WrongPhase          = 3,
-    AlreadyCommitted    = 4,
-    NotYourTurn         = 5,
-    TileAlreadyRevealed = 6,
-    InvalidTileIndex    = 7,
-    InvalidProof        = 8,
-    GameAlreadyEnded    = 9,
-    SelfPlay            = 10,
-    VkNotSet            = 11,
-    VkParseError        = 12,
-    NotAdmin            = 13,
+    GameNotFound          = 1,
+    NotPlayer             = 2,
+    WrongPhase            = 3,
+    AlreadyCommitted      = 4,
+    NotYourTurn           = 5,
+    TileAlreadyRevealed   = 6,
+    InvalidTileIndex      = 7,
+    InvalidProof          = 8,
+    GameAlreadyEnded      = 9,
+    SelfPlay              = 10,
+    VkNotSet              = 11,
+    VkParseError          = 12,
+    NotAdmin              = 13,
+    SeriesNotFound        = 14,
+    SeriesFinished        = 15,
+    SeriesGameActive      = 16,
+    InvalidBestOf         = 17,
+    InvalidConfig         = 18,
+    TutorialNotStarted    = 19,
+    TutorialRequired      = 20,
+    NoAttestor            = 21,
+    StakeCapExceeded      = 22,
+    NegativeStake         = 23,
+    StakeOverflow         = 24,
+    SessionExists         = 25,
+    InsufficientResources = 26,
+    NotBroadcast          = 27,
+    TooManyWatchers       = 28,
+    SeriesOnHold          = 29,
+    NotOrganizer          = 30,
+    RotationCooldown      = 31,
+    NoTimeControl         = 32,
+    ClockNotExpired       = 33,
+    OutOfTime             = 34,
+    ContractPaused        = 35,
+    AlreadySettled        = 36,
+    NotSigner             = 37,
+    NotEnoughApprovals    = 38,
+    NotInitialized        = 39,
+    InternalInvariant     = 40,
+    NoPendingUpgrade      = 41,
+    UpgradeNotReady       = 42,
+    AlreadyInitialized    = 43,
+    BatchLengthMismatch   = 44,
+    InvalidSalvo          = 45,
+    ScanUsed              = 46,
+    BoardMismatch         = 47,
+    FeatureDisabled       = 48,
+    InvalidEvidence       = 49,
+    DisputeWindowOpen     = 50,
+    InsufficientBalance   = 51,
+    StakeTooLow           = 52,
+    StakeTooHigh          = 53,
+    BettingClosed         = 54,
+    BetNotFound           = 55,
+    AlreadyReferred       = 56,
+    SessionIdReused       = 57,
+    StaleMove             = 58,
+    SessionKeyInvalid     = 59,
+    PlayerEliminated      = 60,
+    AttackMismatch        = 61,
+    ProofWrongLength      = 62,
+    PublicInputsMismatch  = 63,
+    ZeroCommitment        = 64,
+    DuplicateCommitment   = 65,
+    SeriesExists          = 66,
+    SessionsActive        = 67,
 }
 
 // ============================================================================
@@ -71,7 +258,40 @@ pub enum Phase {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RevealedTile {
     pub tile_index: u32,
-    pub tile_type:  u32, // 0=Normal 1=Poison 2=Shield
+    pub tile_type:  u32, // 0=Normal 1=Poison 2=Shield 3=Decoy (counts toward nothing)
+}
+
+/// Packed view of one board's reveals: bit i is set once tile i has been
+/// revealed, and again in `poison`/`shield` when it was that type. Boards
+/// are at most `MAX_BOARD_TILES` (32) tiles, so each mask fits a u32.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoardMask {
+    pub revealed: u32,
+    pub poison:   u32,
+    pub shield:   u32,
+}
+
+impl BoardMask {
+    fn new() -> Self {
+        BoardMask { revealed: 0, poison: 0, shield: 0 }
+    }
+
+    pub fn is_revealed(&self, tile_index: u32) -> bool {
+        self.revealed & (1 << tile_index) != 0
+    }
+
+    fn reveal(&mut self, tile_index: u32, tile_type: u32) {
+        let bit = 1 << tile_index;
+        self.revealed |= bit;
+        if tile_type == 1 { self.poison |= bit; }
+        if tile_type == 2 { self.shield |= bit; }
+    }
+
+    /// Returns (poison_found, shield_found).
+    pub fn specials(&self) -> (u32, u32) {
+        (self.poison.count_ones(), self.shield.count_ones())
+    }
 }
 
 #[contracttype]
@@ -81,23 +301,188 @@ pub struct GameState {
     pub player2:          Address,
     pub player1_points:   i128,
     pub player2_points:   i128,
-    // Board commitments — pedersen_hash([tiles..., salt])
+    // Board commitments — pedersen_hash([tiles..., salt, owner, session_id]),
+    // or poseidon_hash under `config.commitment_scheme = Poseidon`
     pub player1_commitment: BytesN<32>,
     pub player2_commitment: BytesN<32>,
+    // Addresses the boards are bound to: the seats as opened, kept through `rotate_seat`
+    pub board_owners:       Vec<Address>,
     pub player1_committed:  bool,
     pub player2_committed:  bool,
     // Phase & turn
     pub phase:              Phase,
     pub current_turn:       u32,   // 1=player1, 2=player2
-    // Pending attack
-    pub pending_attack_tile: u32,
-    pub has_pending_attack:  bool,
+    // Attacked tiles awaiting the defender's answer, oldest first
+    pub pending_tiles: Vec<u32>,
     // Revealed tiles per board
     pub p1_revealed: Vec<RevealedTile>, // tiles revealed ON player1's board (by player2)
     pub p2_revealed: Vec<RevealedTile>, // tiles revealed ON player2's board (by player1)
+    // The same reveals packed as bitmasks, for O(1) move checks
+    pub p1_mask:     BoardMask,
+    pub p2_mask:     BoardMask,
     // Shield skip flag
     // Winner: 0=none 1=player1 2=player2
     pub winner: u32,
+    // Locale/region hint copied from the players' preferences (no effect on rules)
+    pub locale: Option<Symbol>,
+    // Set when this game is one leg of a best-of-N series (stakes held by the series)
+    pub series_id: Option<u32>,
+    // Number of tiles on each board (picked from the stake tier at start)
+    pub board_size: u32,
+    pub config:     GameConfig,
+    // GameHub escrowing this session, resolved from `config.hub` at open
+    pub hub:        Address,
+    // Moves made so far (commits, attacks and responses), stamped on every event
+    pub move_count: u32,
+    // Stakes sit in this contract's hub escrow pool (lobby and raked games), not a hub session
+    pub pooled:     bool,
+    // Protocol fee rate snapshotted at open, in basis points of the winner's pot
+    pub fee_bps:    u32,
+    // Jackpot slice snapshotted at open, in basis points of the winner's pot
+    pub jackpot_bps: u32,
+    // Token sessions: player1's and player2's wager legs, held by this contract (empty = GameHub points)
+    pub wagers:     Vec<Wager>,
+    // Chess clock (config.time_bank > 0): banks and when the running move began
+    // (until both boards are committed: when the session opened)
+    pub player1_time_left:  u32,
+    pub player2_time_left:  u32,
+    pub last_action_ledger: u32,
+    // Stakes paid out or refunded; nothing may settle this session again
+    pub settled:            bool,
+    // VK version pinned at start; reveals verify against this circuit
+    pub vk_version:         u32,
+    // Poison penalty: slot (1|2) whose next turn is skipped, 0 if none
+    pub skip_next_turn:     u32,
+    // Shield bonus attacks the current attacker has taken in a row
+    pub consecutive_bonus:  u32,
+    // Scan: range start awaiting the defender's count; each player scans once
+    pub pending_scan:       Option<u32>,
+    pub player1_scanned:    bool,
+    pub player2_scanned:    bool,
+    // Lite mode: running reveal digest per board (player1's, player2's)
+    pub lite_boards:        Vec<LiteBoard>,
+    // Slot that forfeited by letting the response window lapse (0 = none)
+    pub forfeited:          u32,
+    // Dispute window: settlement is held until this ledger (0 = paid at finish)
+    pub finalize_after:     u32,
+    pub overturned:         bool,
+    // Winner found every special without revealing one of their own
+    pub perfect:            bool,
+    // High-stakes session kept in persistent storage instead of temporary
+    pub persistent:         bool,
+    // Simultaneous sessions: where the current round stands
+    pub round:              SimulRound,
+    // Rounds completed so far, counted toward config.max_rounds
+    pub rounds_played:      u32,
+}
+
+/// Stakes at or above `min_stake` (the larger of the two wagers) play on
+/// boards of `board_size` tiles under the tier's time control. Tiers are
+/// kept sorted by `min_stake`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeTier {
+    pub min_stake:  i128,
+    // Only `TOTAL_TILES` until a circuit exists for each board size
+    pub board_size: u32,
+    // Longest chess clock a game at this tier may run on (0 = any, untimed allowed);
+    // an untimed session is given this clock
+    pub time_bank:  u32,
+}
+
+/// Rule options both players agree to at `start_game_with_config`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameConfig {
+    // Special tiles hidden on each board; the attacker must find all of them
+    pub poison_count:      u32,
+    pub shield_count:      u32,
+    // Lock player2's stake on this hub instead of the deployment's GameHub
+    pub player2_hub:       Option<Address>,
+    // Spectator embargo: ledgers before reveal events carry the tile type (0 = live)
+    pub reveal_delay:      u32,
+    // Produced/streamed game: embargo capped, watcher registry open
+    pub broadcast:         bool,
+    // Hash of the human-readable rules / ToS version both players agreed to
+    pub rules_hash:        Option<BytesN<32>>,
+    // Chess clock: ledgers each player may spend on move all game (0 = untimed)
+    pub time_bank:         u32,
+    // Salvo variant: tiles an attacker may pick per turn (0/1 = one at a time)
+    pub salvo_size:        u32,
+    // Finding a Poison costs the attacker their next turn
+    pub poison_penalty:    bool,
+    // Most Shield bonus attacks in a row before the turn passes (0 = no cap)
+    pub max_bonus_attacks: u32,
+    // Decoy tiles: look special when revealed but count toward nothing
+    pub decoy_count:       u32,
+    // Low-bandwidth mode: reveals are event-only, proven once at the win claim
+    pub lite:              bool,
+    // Ledgers a defender has to answer before the attacker may claim the
+    // game (0 = no deadline), and the share of the defender's stake, in
+    // basis points, bonded at commit and slashed to the attacker on forfeit
+    pub response_window:   u32,
+    pub slash_bps:         u32,
+    // Ledgers a finished game's payout is held open to challenges (0 = none)
+    pub dispute_window:    u32,
+    // Double-blind rounds: both players attack at once (see `simultaneous.rs`)
+    pub simultaneous:      bool,
+    // Turn limit: rounds before the game goes to the tiebreak (0 = no limit)
+    pub max_rounds:        u32,
+    // Variant the game is decided by (see `win_condition.rs`)
+    pub win_condition:     WinCondition,
+    // Handicap: Poison hidden on player1's and player2's board (empty = poison_count on both)
+    pub board_poison:      Vec<u32>,
+    // Hash the boards are committed with; Poseidon reveals verify against their own VK
+    pub commitment_scheme: CommitmentScheme,
+    // Ledgers after the start both boards must be committed by (0 = no deadline)
+    pub commit_window:     u32,
+    // Allowlisted GameHub to escrow the session on (None = the deployment's GameHub)
+    pub hub:               Option<Address>,
+}
+
+impl GameConfig {
+    /// Poison hidden on `slot`'s board.
+    pub fn poison_on(&self, slot: u32) -> u32 {
+        self.board_poison.get(slot - 1).unwrap_or(self.poison_count)
+    }
+}
+
+// ============================================================================
+// Move events — each carries move number, acting slot (1|2) and the phase
+// after the move, so clients can follow the game from events alone.
+// ============================================================================
+
+#[contractevent]
+pub struct BoardCommitted {
+    #[topic]
+    pub session_id:  u32,
+    pub move_number: u32,
+    pub player_slot: u32,
+    pub phase:       Phase,
+}
+
+#[contractevent]
+pub struct AttackMade {
+    #[topic]
+    pub session_id:  u32,
+    pub move_number: u32,
+    pub player_slot: u32,
+    pub tile_index:  u32,
+    pub phase:       Phase,
+}
+
+#[contractevent]
+pub struct AttackAnswered {
+    #[topic]
+    pub session_id:  u32,
+    pub move_number: u32,
+    pub player_slot: u32, // defender
+    pub tile_index:  u32,
+    pub tile_type:   u32, // TILE_EMBARGOED while a delayed reveal is withheld
+    pub next_turn:   u32,
+    pub phase:       Phase,
+    pub winner:      u32,
+    pub proof_hash:  BytesN<32>, // keccak256 of the verified proof blob
 }
 
 #[contracttype]
@@ -106,12 +491,107 @@ pub enum DataKey {
     Game(u32),
     GameHubAddress,
     Admin,
-    Vk,
+    Vk(u32),
+    VkVersion,
+    LocalePref(Address),
+    Series(u32),
+    StakeTiers,
+    Tutorial(Address),
+    TutorialDone(Address),
+    TutorialRequired,
+    Attestor,
+    Attestation(u32),
+    StakeCap(Address, Option<Address>),
+    Rating(Address),
+    Stats(Address),
+    MaxProofsPerCall,
+    OpenGame(u32),
+    Lobby,
+    Embargo(u32),
+    Watchers(u32),
+    Result(u32),
+    PlayerResults(Address, u32),
+    PlayerResultCount(Address),
+    SeatRotation(u32, u32),
+    MoveLog(u32),
+    Paused,
+    Receipt(u32),
+    AdminSigners,
+    AdminThreshold,
+    Approvals(BytesN<32>),
+    PendingUpgrade,
+    Initialized,
+    Sandbox,
+    HubIntents,
+    VkRotation,
+    RatingDecay,
+    LastRated(Address),
+    Treasury,
+    Champion(Address),
+    BadgeContract,
+    Achievements(Address),
+    Beaten(Address, Address),
+    ScanVk,
+    BoardReveal(u32, u32),
+    LiteVk,
+    FeeBps,
+    FeeBalance,
+    StakeLimits,
+    DisabledFeatures,
+    JackpotBps,
+    Jackpot,
+    BetMarket,
+    BetPool(u32),
+    Bet(u32, Address),
+    TrophyContract,
+    Referrer(Address),
+    ReferralRewards(Address),
+    ReferralBps,
+    Season,
+    SeasonRating(u32, Address),
+    SeasonRecord(u32, Address),
+    PlayerGames(Address, u32),
+    PlayerGameCount(Address),
+    ActiveSlot(u32),
+    ActivePos(u32),
+    ActiveCount,
+    UsedSession(u32),
+    PersistentStake,
+    GameProgress(u32),
+    SessionKey(Address),
+    Ffa(u32),
+    Bot(Address),
+    HouseBoard,
+    HouseCommitment(u32),
+    HouseReveal(u32),
+    Practice(Address),
+    RatingStakeScale,
+    Template(u32),
+    TemplateIds,
+    PoseidonVk,
+    AttackQueue(u32, u32),
+    HubVersion(Address),
+    HubAllowlist,
+    PendingStakeCap(Address, Option<Address>),
+    DecoyVk,
+    PoolHub,
+    ReferralOwed,
+    OpenEscrows,
 }
 
-const GAME_TTL_LEDGERS: u32 = 518_400; // ~30 days
-const TOTAL_TILES:      u32 = 15;
-const PUB_INPUT_BYTES:  u32 = 96;      // 3 × 32-byte field elements
+const GAME_TTL_LEDGERS:   u32 = 518_400;   // ~30 days
+const PLAYER_TTL_LEDGERS: u32 = 3_110_400; // ~180 days
+const TOTAL_TILES:        u32 = 15;        // default board size (matches the v1 circuit)
+const MAX_BOARD_TILES:    u32 = 32;
+const DEFAULT_POISON:     u32 = 2;
+const DEFAULT_SHIELD:     u32 = 1;
+const PUB_INPUT_BYTES:    u32 = 224;       // 7 × 32-byte field elements
+// One UltraHonk verification uses a large share of the per-transaction
+// instruction limit, so by default a call may verify a single proof.
+const DEFAULT_MAX_PROOFS_PER_CALL: u32 = 1;
+const MAX_PAGE_SIZE:      u32 = 50;
+const MAX_BPS:            u32 = 10_000;    // 100% in basis points
+const MAX_RAKE_BPS:       u32 = 2_500;     // fee plus jackpot slice, a quarter of a pot
 
 // ============================================================================
 // Win-condition helper
@@ -123,18 +603,51 @@ const PUB_INPUT_BYTES:  u32 = 96;      // 3 × 32-byte field elements
 fn count_specials(revealed: &Vec<RevealedTile>) -> (u32, u32) {
     let mut poison: u32 = 0;
     let mut shield: u32 = 0;
-    for i in 0..revealed.len() {
-        let t = revealed.get(i).unwrap().tile_type;
+    for t in revealed.iter().map(|r| r.tile_type) {
         if t == 1 { poison += 1; }
         if t == 2 { shield += 1; }
     }
     (poison, shield)
 }
 
-/// Did the attacker win? — found 2 Poison AND 1 Shield.
-fn attacker_won(revealed: &Vec<RevealedTile>) -> bool {
+/// Did the attacker win? — found every Poison AND every Shield
+/// `defender`'s board was configured with (2 + 1 by default).
+fn attacker_won(revealed: &Vec<RevealedTile>, config: &GameConfig, defender: u32) -> bool {
     let (p, s) = count_specials(revealed);
-    p >= 2 && s >= 1
+    p >= config.poison_on(defender) && s >= config.shield_count
+}
+
+/// `attacker_won` for a game's packed board.
+fn attacker_won_mask(mask: &BoardMask, config: &GameConfig, defender: u32) -> bool {
+    let (p, s) = mask.specials();
+    p >= config.poison_on(defender) && s >= config.shield_count
+}
+
+/// Encode a u32 as a 32-byte big-endian field element.
+fn u32_field(env: &Env, value: u32) -> Bytes {
+    let mut be = [0u8; 32];
+    be[28..].copy_from_slice(&value.to_be_bytes());
+    Bytes::from_array(env, &be)
+}
+
+/// Encode an address as a field element: sha256 of its XDR with the top
+/// byte cleared, so it is below the BN254 modulus. Clients compute the same
+/// from the address's `ScVal` XDR when hashing their board.
+fn address_field(env: &Env, address: &Address) -> Bytes {
+    let mut digest: [u8; 32] = env.crypto().sha256(&address.clone().to_xdr(env)).into();
+    digest[0] = 0;
+    Bytes::from_array(env, &digest)
+}
+
+/// Leading public inputs of every board proof: the commitment and the
+/// owner and session its preimage is bound to. A commitment copied from
+/// the opponent or reused from another session cannot be opened under
+/// these, so proofs against it fail.
+fn commitment_inputs(env: &Env, commitment: &BytesN<32>, owner: &Address, session_id: u32) -> Bytes {
+    let mut inputs = Bytes::from(commitment.clone());
+    inputs.append(&address_field(env, owner));
+    inputs.append(&u32_field(env, session_id));
+    inputs
 }
 
 // ============================================================================
@@ -149,9 +662,18 @@ impl PoisonGameContract {
 
     /// Deploy: set admin + GameHub address.
     /// Then call init_vk() with the UltraHonk VK bytes.
-    pub fn __constructor(env: Env, admin: Address, game_hub: Address) {
+    /// Runs once at deploy. The `Initialized` flag survives upgrades, so
+    /// the setup can never be replayed over a live deployment.
+    pub fn __constructor(env: Env, admin: Address, game_hub: Address) -> Result<(), Error> {
+        if Self::is_initialized(env.clone()) { return Err(Error::AlreadyInitialized); }
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::GameHubAddress, &game_hub);
+        env.storage().instance().set(&DataKey::Initialized, &true);
+        Ok(())
+    }
+
+    pub fn is_initialized(env: Env) -> bool {
+        env.storage().instance().get(&DataKey::Initialized).unwrap_or(false)
     }
 
     // ========================================================================
@@ -159,19 +681,33 @@ impl PoisonGameContract {
     // ========================================================================
 
     /// Store verification key after deploy (or when circuit changes).
-    /// Only callable by admin.
+    /// Only callable by admin, or by a council signer once the council
+    /// has approved it.
     /// vk_bytes = raw bytes from `bb write_vk_ultra_honk -b target/poison_game.json`
-    pub fn init_vk(env: Env, caller: Address, vk_bytes: Bytes) -> Result<(), Error> {
+    ///
+    /// Each call stores a new VK version and makes it current for games
+    /// started from now on; games already running keep verifying against
+    /// the version they started with (see `rotate_vk` to migrate them).
+    /// Returns the new version.
+    pub fn init_vk(env: Env, caller: Address, vk_bytes: Bytes) -> Result<u32, Error> {
         caller.require_auth();
-        let admin: Address = env.storage().instance()
-            .get(&DataKey::Admin).expect("Admin not set");
-        if caller != admin { return Err(Error::NotAdmin); }
-        env.storage().instance().set(&DataKey::Vk, &vk_bytes);
-        Ok(())
+        let (signers, _) = Self::get_admin_council(env.clone());
+        if signers.is_empty() {
+            if caller != Self::get_admin(env.clone())? { return Err(Error::NotAdmin); }
+        } else {
+            if !signers.contains(&caller) { return Err(Error::NotSigner); }
+            Self::authorize_admin_action(&env, "init_vk", vec![&env, vk_bytes.clone().into_val(&env)])?;
+        }
+        Self::store_vk(&env, &vk_bytes)
     }
 
     pub fn has_vk(env: Env) -> bool {
-        env.storage().instance().has(&DataKey::Vk)
+        Self::get_vk_version(env) > 0
+    }
+
+    /// Version new games are pinned to (0 = no VK yet).
+    pub fn get_vk_version(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::VkVersion).unwrap_or(0)
     }
 
     // ========================================================================
@@ -186,8 +722,6 @@ impl PoisonGameContract {
         player1_points: i128,
         player2_points: i128,
     ) -> Result<(), Error> {
-        if player1 == player2 { return Err(Error::SelfPlay); }
-
         // Each player signs only their own session_id + points
         player1.require_auth_for_args(
             vec![&env, session_id.into_val(&env), player1_points.into_val(&env)]
@@ -196,49 +730,62 @@ impl PoisonGameContract {
             vec![&env, session_id.into_val(&env), player2_points.into_val(&env)]
         );
 
-        // Tell GameHub to lock both players' points into escrow
-        let hub_addr: Address = env.storage().instance()
-            .get(&DataKey::GameHubAddress).expect("GameHub not set");
-        GameHubClient::new(&env, &hub_addr).start_game(
-            &env.current_contract_address(),
-            &session_id,
-            &player1,
-            &player2,
-            &player1_points,
-            &player2_points,
-        );
+        Self::open_game(&env, session_id, player1, player2, player1_points, player2_points, Self::default_config(&env), vec![&env])
+    }
 
-        let zero = BytesN::from_array(&env, &[0u8; 32]);
-        let game = GameState {
-            player1,
-            player2,
-            player1_points,
-            player2_points,
-            player1_commitment: zero.clone(),
-            player2_commitment: zero,
-            player1_committed: false,
-            player2_committed: false,
-            phase:              Phase::WaitingForCommits,
-            current_turn:       1,
-            pending_attack_tile: 0,
-            has_pending_attack:  false,
-            p1_revealed: vec![&env],
-            p2_revealed: vec![&env],
-            
-            winner: 0,
-        };
+    /// Like `start_game`, but with custom rules. Both players also sign the config.
+    pub fn start_game_with_config(
+        env: Env,
+        session_id:     u32,
+        player1:        Address,
+        player2:        Address,
+        player1_points: i128,
+        player2_points: i128,
+        config:         GameConfig,
+    ) -> Result<(), Error> {
+        player1.require_auth_for_args(vec![
+            &env, session_id.into_val(&env), player1_points.into_val(&env), config.clone().into_val(&env),
+        ]);
+        player2.require_auth_for_args(vec![
+            &env, session_id.into_val(&env), player2_points.into_val(&env), config.clone().into_val(&env),
+        ]);
+
+        Self::open_game(&env, session_id, player1, player2, player1_points, player2_points, config, vec![&env])
+    }
 
-        let key = DataKey::Game(session_id);
-        env.storage().temporary().set(&key, &game);
-        env.storage().temporary().extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
-        Ok(())
+    // ========================================================================
+    // Locale preference — matchmaking hint only, never consulted by rules
+    // ========================================================================
+
+    /// Set (or clear with None) the player's preferred locale/region tag,
+    /// e.g. `eu`, `us_east`. Copied into new sessions as `GameState.locale`
+    /// so matchmaking can prefer same-region pairings.
+    pub fn set_locale(env: Env, player: Address, locale: Option<Symbol>) {
+        player.require_auth();
+        let key = DataKey::LocalePref(player);
+        match locale {
+            Some(tag) => {
+                env.storage().persistent().set(&key, &tag);
+                env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn get_locale(env: Env, player: Address) -> Option<Symbol> {
+        env.storage().persistent().get(&DataKey::LocalePref(player))
     }
 
     // ========================================================================
     // commit_board — each player hashes their board before play begins
     // ========================================================================
 
-    /// board_hash = pedersen_hash([tile0..tile14, salt]) computed in the browser.
+    /// board_hash = pedersen_hash([tile0..tile14, salt, owner, session_id])
+    /// computed in the browser (poseidon_hash for Poseidon-committed games),
+    /// where `owner` is the seat's address as the game was opened, encoded
+    /// like the proofs' public inputs (see circuits/poison_lib/src/commitment.nr).
+    /// Binding both stops a player mirroring the opponent's commitment or
+    /// reusing a board across sessions.
     /// Once both players commit, phase moves to Playing.
     pub fn commit_board(
         env: Env,
@@ -247,106 +794,310 @@ impl PoisonGameContract {
         board_hash: BytesN<32>,
     ) -> Result<(), Error> {
         player.require_auth();
+        Self::place_commitment(&env, session_id, player, board_hash)
+    }
+
+    // ========================================================================
+    // cancel_game — abort before play begins, refunding both stakes
+    // ========================================================================
+
+    /// Either player may cancel while the game is still `WaitingForCommits`,
+    /// e.g. when the opponent never commits. GameHub releases both stakes and
+    /// the session entry is deleted.
+    pub fn cancel_game(env: Env, session_id: u32, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
 
-        let key = DataKey::Game(session_id);
-        let mut game: GameState = env.storage().temporary()
-            .get(&key).ok_or(Error::GameNotFound)?;
+        let mut game = Self::get_game(env.clone(), session_id)?;
+
+        if caller != game.player1 && caller != game.player2 { return Err(Error::NotPlayer); }
+        if game.phase != Phase::WaitingForCommits           { return Err(Error::WrongPhase); }
+
+        Self::settle_refund(&env, session_id, &mut game)?;
+
+        Self::remove_game(&env, session_id, &game);
+        Ok(())
+    }
+
+    // ========================================================================
+    // attack — current-turn player picks a tile on the opponent's board
+    // ========================================================================
+
+    pub fn attack(
+        env:        Env,
+        session_id: u32,
+        attacker:   Address,
+        tile_index: u32,
+    ) -> Result<(), Error> {
+        attacker.require_auth();
+        Self::place_attacks(&env, session_id, attacker, vec![&env, tile_index])
+    }
+
+    /// Salvo games (`config.salvo_size > 1`): attack up to `salvo_size`
+    /// distinct tiles at once. The defender answers them all together with
+    /// `respond_batch` before the turn can pass.
+    pub fn attack_salvo(
+        env:        Env,
+        session_id: u32,
+        attacker:   Address,
+        tiles:      Vec<u32>,
+    ) -> Result<(), Error> {
+        attacker.require_auth();
+        Self::place_attacks(&env, session_id, attacker, tiles)
+    }
+
+    // ========================================================================
+    // respond_to_attack — defender proves their tile type with a ZK proof
+    //
+    // Win condition: if after recording this tile the attacker has found
+    // 2 Poison + 1 Shield on the defender's board → attacker wins immediately.
+    //
+    // Proof format: PROOF_BYTES (14592) raw bytes from bb v0.87.0 keccak oracle.
+    // Public inputs (224 bytes, built entirely from on-chain state):
+    //   [0..32]    = defender's board commitment
+    //   [32..64]   = defender's address (sha256 of its XDR, top byte cleared)
+    //   [64..96]   = session_id   — the commitment preimage binds both
+    //   [96..128]  = tile_index   (u32, big-endian padded to 32 bytes)
+    //   [128..160] = tile_type
+    //   [160..192] = poison_count (board must hide exactly this many Poison; per board under a handicap)
+    //   [192..224] = shield_count (board must hide exactly this many Shield)
+    // Games with decoys (config.decoy_count > 0) use the decoy circuit
+    // and its own VK (see `decoy.rs`), which take one more input (256 bytes):
+    //   [224..256] = decoy_count  (board must hide exactly this many Decoy)
+    // ========================================================================
+
+    pub fn respond_to_attack(
+        env:        Env,
+        session_id: u32,
+        defender:   Address,
+        tile_type:  u32,   // 0=Normal 1=Poison 2=Shield 3=Decoy
+        proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        defender.require_auth();
+        Self::answer_attacks(&env, session_id, defender, vec![&env, (tile_type, proof_blob)])
+    }
+
+    /// Answer every pending attack in one transaction, in the order they
+    /// were made. Each proof is verified and each reveal recorded and
+    /// announced as with `respond_to_attack`; the turn is decided once, after
+    /// the last reveal — the attacker keeps it if any answer was a Shield.
+    pub fn respond_batch(
+        env:        Env,
+        session_id: u32,
+        defender:   Address,
+        responses:  Vec<(u32, Bytes)>, // (tile_type, proof_blob) per pending tile
+    ) -> Result<(), Error> {
+        defender.require_auth();
+        Self::answer_attacks(&env, session_id, defender, responses)
+    }
+
+    // ========================================================================
+    // rematch — same players, same wagers, same rules, fresh boards
+    // ========================================================================
+
+    /// Start a new game from a finished one. Both players sign; the GameHub
+    /// re-locks the original wagers under a session id derived from the old
+    /// one (first 4 bytes of keccak256 over `(session_id, player1, player2)`).
+    /// Returns the new session id.
+    pub fn rematch(env: Env, session_id: u32) -> Result<u32, Error> {
+        let game = Self::get_game(env.clone(), session_id)?;
+        if game.phase != Phase::Finished { return Err(Error::WrongPhase); }
+        // Series legs are opened through start_series_game instead
+        if game.series_id.is_some()      { return Err(Error::WrongPhase); }
+
+        game.player1.require_auth();
+        game.player2.require_auth();
+
+        let seed = (session_id, game.player1.clone(), game.player2.clone()).to_xdr(&env);
+        let digest: [u8; 32] = env.crypto().keccak256(&seed).into();
+        let new_session_id = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+
+        Self::check_session_unused(&env, new_session_id)?;
+
+        Self::open_game(
+            &env,
+            new_session_id,
+            game.player1,
+            game.player2,
+            game.player1_points,
+            game.player2_points,
+            game.config,
+            game.wagers,
+        )?;
+        Ok(new_session_id)
+    }
+
+    // ========================================================================
+    // get_game
+    // ========================================================================
+
+    pub fn get_game(env: Env, session_id: u32) -> Result<GameState, Error> {
+        Self::load_game(&env, session_id).ok_or(Error::GameNotFound)
+    }
+
+    /// Rules/ToS hash the session was started under, from the live game or,
+    /// once it has expired, from the archive.
+    pub fn get_rules_hash(env: Env, session_id: u32) -> Result<Option<BytesN<32>>, Error> {
+        match Self::get_game(env.clone(), session_id) {
+            Ok(game) => Ok(game.config.rules_hash),
+            Err(e)   => Self::get_result(env, session_id).map(|r| r.rules_hash).ok_or(e),
+        }
+    }
+
+    // ========================================================================
+    // Internal helpers
+    // ========================================================================
+
+    fn finish_game(
+        env:          &Env,
+        session_id:   u32,
+        game:         &mut GameState,
+        player1_won:  bool,
+    ) -> Result<(), Error> {
+        game.winner  = if player1_won { 1 } else { 2 };
+        game.phase   = Phase::Finished;
+        game.perfect = Self::is_perfect_game(game);
+        Self::deactivate(env, session_id);
+        if game.config.dispute_window == 0 {
+            Self::settle_win(env, session_id, game, player1_won)?;
+        } else {
+            game.finalize_after = env.ledger().sequence().saturating_add(game.config.dispute_window);
+            Self::open_escrow(env);
+        }
+
+        let (winner, loser) = if player1_won { (&game.player1, &game.player2) }
+                              else           { (&game.player2, &game.player1) };
+        Self::update_ratings(env, game, winner, loser);
+        Self::record_result_stats(env, winner, loser);
+
+        Self::archive_result(env, session_id, game);
+        Self::evaluate_achievements(env, game);
+        Self::mint_trophy(env, session_id, game);
+        GameEnded { session_id, winner: game.winner, outcome: Self::outcome_of(game) }.publish(env);
+        Ok(())
+    }
+
+    /// Callers authorize `player` first (see `commit_board`, `stress.rs`).
+    pub(crate) fn place_commitment(
+        env:        &Env,
+        session_id: u32,
+        player:     Address,
+        board_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        let mut game = Self::get_game(env.clone(), session_id)?;
 
         if game.phase != Phase::WaitingForCommits { return Err(Error::WrongPhase); }
 
+        let player_slot = if player == game.player1 { 1u32 } else { 2u32 };
         if player == game.player1 {
             if game.player1_committed { return Err(Error::AlreadyCommitted); }
+            Self::check_commitment(&game, 1, &board_hash)?;
             game.player1_commitment = board_hash;
             game.player1_committed  = true;
         } else if player == game.player2 {
             if game.player2_committed { return Err(Error::AlreadyCommitted); }
+            Self::check_commitment(&game, 2, &board_hash)?;
             game.player2_commitment = board_hash;
             game.player2_committed  = true;
         } else {
             return Err(Error::NotPlayer);
         }
+        Self::lock_response_bond(env, &game, player_slot)?;
 
         if game.player1_committed && game.player2_committed {
             game.phase = Phase::Playing;
+            game.last_action_ledger = env.ledger().sequence();
         }
-
-        env.storage().temporary().set(&key, &game);
+        game.move_count += 1;
+
+        Self::save_game(env, session_id, &game);
+        BoardCommitted {
+            session_id,
+            move_number: game.move_count,
+            player_slot,
+            phase:       game.phase.clone(),
+        }
+        .publish(env);
         Ok(())
     }
 
-    // ========================================================================
-    // attack — current-turn player picks a tile on the opponent's board
-    // ========================================================================
-
-    pub fn attack(
-        env:        Env,
+    /// Callers authorize `attacker` first (see `relay.rs`, `session_key.rs`).
+    pub(crate) fn place_attacks(
+        env:        &Env,
         session_id: u32,
         attacker:   Address,
-        tile_index: u32,
+        tiles:      Vec<u32>,
     ) -> Result<(), Error> {
-        attacker.require_auth();
+        Self::check_operational(env)?;
 
-        let key = DataKey::Game(session_id);
-        let mut game: GameState = env.storage().temporary()
-            .get(&key).ok_or(Error::GameNotFound)?;
+        let mut game = Self::get_game(env.clone(), session_id)?;
 
-        if game.phase != Phase::Playing  { return Err(Error::WrongPhase);       }
-        if game.winner != 0              { return Err(Error::GameAlreadyEnded); }
-        if game.has_pending_attack       { return Err(Error::WrongPhase);       }
-        if tile_index >= TOTAL_TILES     { return Err(Error::InvalidTileIndex); }
+        if game.phase != Phase::Playing      { return Err(Error::WrongPhase);       }
+        if game.winner != 0                  { return Err(Error::GameAlreadyEnded); }
+        if game.config.simultaneous          { return Err(Error::WrongPhase);       }
+        if !game.pending_tiles.is_empty()    { return Err(Error::WrongPhase);       }
+        if game.pending_scan.is_some()       { return Err(Error::WrongPhase);       }
+        if tiles.is_empty() || tiles.len() > game.config.salvo_size.max(1) {
+            return Err(Error::InvalidSalvo);
+        }
 
         let attacker_num = if attacker == game.player1 { 1u32 }
                            else if attacker == game.player2 { 2u32 }
                            else { return Err(Error::NotPlayer); };
 
         if attacker_num != game.current_turn { return Err(Error::NotYourTurn); }
+        Self::charge_clock(env, &mut game, attacker_num)?;
+
+        for tile_index in tiles.iter() {
+            if tile_index >= game.board_size { return Err(Error::InvalidTileIndex); }
+
+            if game.config.lite {
+                Self::check_lite_attack(&game, 3 - attacker_num, tile_index)?;
+            }
 
-        // Ensure this tile has not already been revealed on defender's board
-        let defender_revealed = if attacker_num == 1 { &game.p2_revealed }
-                                else                  { &game.p1_revealed };
-        for i in 0..defender_revealed.len() {
-            if defender_revealed.get(i).unwrap().tile_index == tile_index {
+            // Ensure this tile has not already been revealed on defender's board
+            // (or picked twice in the same salvo)
+            let defender_mask = if attacker_num == 1 { &game.p2_mask } else { &game.p1_mask };
+            if defender_mask.is_revealed(tile_index) || game.pending_tiles.contains(tile_index)
+            {
                 return Err(Error::TileAlreadyRevealed);
             }
-        }
 
-        game.pending_attack_tile = tile_index;
-        game.has_pending_attack  = true;
+            game.pending_tiles.push_back(tile_index);
+            game.move_count += 1;
+            AttackMade {
+                session_id,
+                move_number: game.move_count,
+                player_slot: attacker_num,
+                tile_index,
+                phase:       game.phase.clone(),
+            }
+            .publish(env);
+        }
 
-        env.storage().temporary().set(&key, &game);
+        Self::save_game(env, session_id, &game);
+        Self::bump_game_ttl(env, session_id);
         Ok(())
     }
 
-    // ========================================================================
-    // respond_to_attack — defender proves their tile type with a ZK proof
-    //
-    // Win condition: if after recording this tile the attacker has found
-    // 2 Poison + 1 Shield on the defender's board → attacker wins immediately.
-    //
-    // Proof format: PROOF_BYTES (14592) raw bytes from bb v0.87.0 keccak oracle.
-    // Public inputs (96 bytes, built entirely from on-chain state):
-    //   [0..32]  = defender's board commitment
-    //   [32..64] = tile_index (u32, big-endian padded to 32 bytes)
-    //   [64..96] = tile_type  (u32, big-endian padded to 32 bytes)
-    // ========================================================================
-
-    pub fn respond_to_attack(
-        env:        Env,
+    /// Callers authorize `defender` first (see `relay.rs`, `session_key.rs`).
+    pub(crate) fn answer_attacks(
+        env:        &Env,
         session_id: u32,
         defender:   Address,
-        tile_type:  u32,   // 0=Normal 1=Poison 2=Shield
-        proof_blob: Bytes,
+        responses:  Vec<(u32, Bytes)>,
     ) -> Result<(), Error> {
-        defender.require_auth();
+        Self::check_operational(env)?;
 
-        let key = DataKey::Game(session_id);
-        let mut game: GameState = env.storage().temporary()
-            .get(&key).ok_or(Error::GameNotFound)?;
+        let mut game = Self::get_game(env.clone(), session_id)?;
 
         if game.phase != Phase::Playing { return Err(Error::WrongPhase);       }
         if game.winner != 0             { return Err(Error::GameAlreadyEnded); }
-        if !game.has_pending_attack     { return Err(Error::WrongPhase);       }
-        if tile_type > 2               { return Err(Error::InvalidProof);      }
+
+        let pending = game.pending_tiles.clone();
+        if pending.is_empty()                  { return Err(Error::WrongPhase);          }
+        if responses.len() != pending.len()    { return Err(Error::BatchLengthMismatch); }
+        let max_type = if game.config.decoy_count > 0 { 3 } else { 2 };
+        if responses.iter().any(|(t, _)| t > max_type) { return Err(Error::InvalidProof); }
 
         let defender_num = if defender == game.player1 { 1u32 }
                            else if defender == game.player2 { 2u32 }
@@ -355,143 +1106,573 @@ impl PoisonGameContract {
         // Attacker is whoever has current_turn; defender must be the OTHER player
         let attacker_num = if defender_num == 1 { 2u32 } else { 1u32 };
         if attacker_num != game.current_turn { return Err(Error::NotYourTurn); }
+        Self::charge_clock(env, &mut game, defender_num)?;
+        if game.config.lite {
+            return Self::answer_lite(env, session_id, game, defender_num, responses);
+        }
+        Self::check_verify_budget(env, responses.len())?;
+        // Parsed once for the whole batch rather than per answer
+        let keys = Self::reveal_keys(env, game.vk_version, RevealCircuit::of(&game.config))?;
+        Self::apply_answers(env, session_id, game, defender_num, responses, |proof, pub_inputs| {
+            Self::verify_with_keys(env, &keys, proof, pub_inputs)
+        })
+    }
+
+    /// Record the defender's answers to the pending attacks in order and
+    /// move the game on. `verify` checks each proof against the public
+    /// inputs rebuilt here from on-chain state.
+    pub(crate) fn apply_answers(
+        env:          &Env,
+        session_id:   u32,
+        mut game:     GameState,
+        defender_num: u32,
+        responses:    Vec<(u32, Bytes)>,
+        verify:       impl Fn(&Bytes, &Bytes) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let attacker_num = 3 - defender_num;
+        let pending = game.pending_tiles.clone();
+        let mut shield_found = false;
+        for (i, (tile_type, proof_blob)) in responses.iter().enumerate() {
+            let tile_index = pending.get_unchecked(i as u32);
+
+            // Proof must be exactly PROOF_BYTES long
+            if proof_blob.len() != PROOF_BYTES as u32 { return Err(Error::ProofWrongLength); }
+
+            // ── Build public inputs from on-chain state (defender cannot lie) ─
+            // [0..96]    commitment, owner, session_id
+            let mut pub_inputs = Self::board_inputs(env, &game, session_id, defender_num);
+
+            // [96..128]  tile_index   — big-endian u32 in 32 bytes
+            // [128..160] tile_type
+            // [160..192] poison_count — board-validity thresholds from GameState (per board)
+            // [192..224] shield_count
+            // [224..256] decoy_count  — decoy circuit only
+            pub_inputs.append(&u32_field(env, tile_index));
+            pub_inputs.append(&u32_field(env, tile_type));
+            pub_inputs.append(&u32_field(env, game.config.poison_on(defender_num)));
+            pub_inputs.append(&u32_field(env, game.config.shield_count));
+            let mut expected_len = PUB_INPUT_BYTES;
+            if game.config.decoy_count > 0 {
+                pub_inputs.append(&u32_field(env, game.config.decoy_count));
+                expected_len += 32;
+            }
+
+            if pub_inputs.len() != expected_len { return Err(Error::InternalInvariant); }
 
-        // Proof must be exactly PROOF_BYTES long
-        if proof_blob.len() != PROOF_BYTES as u32 { return Err(Error::InvalidProof); }
+            // ── UltraHonk verification against the game's VK version ─────────
+            verify(&proof_blob, &pub_inputs)?;
 
-        // ── Load VK ───────────────────────────────────────────────────────
-        let vk_bytes: Bytes = env.storage().instance()
-            .get(&DataKey::Vk).ok_or(Error::VkNotSet)?;
+            // Anchor the exact proof that justified this reveal for later audits
+            let proof_hash: BytesN<32> = env.crypto().keccak256(&proof_blob).into();
+
+            // ── ZK verified — record the tile on the DEFENDER's revealed list ─
+            Self::record_reveal(&mut game, defender_num, tile_index, tile_type);
+            shield_found |= tile_type == 2;
+
+            if tile_type == 1 {
+                let attacker = if attacker_num == 1 { &game.player1 } else { &game.player2 };
+                Self::record_poison_found(env, attacker);
+                if game.config.poison_penalty { game.skip_next_turn = attacker_num; }
+            }
 
-        // ── Build public inputs from on-chain state (defender cannot lie) ─
-        let defender_commitment = if defender_num == 1 {
-            game.player1_commitment.clone()
+            // ── Check win condition ───────────────────────────────────────────
+            // The session's evaluator reads the attacker's "found" list =
+            // defender's revealed board, and names the winner it decides.
+            let winner = Self::evaluate_win(&game, attacker_num);
+            let last = i as u32 + 1 == responses.len();
+            // Counted before finishing, so a finished game's total includes the winning answer
+            game.move_count += 1;
+
+            if winner != 0 {
+                Self::finish_game(env, session_id, &mut game, winner == 1)?;
+            } else if last {
+                Self::advance_turn(&mut game, shield_found);
+                if !Self::resolve_dead_position(env, session_id, &mut game)? {
+                    Self::resolve_turn_limit(env, session_id, &mut game)?;
+                }
+            }
+
+            Self::log_move(env, session_id, MoveRecord {
+                turn:       game.move_count,
+                attacker:   attacker_num,
+                tile_index,
+                tile_type,
+                ledger:     env.ledger().sequence(),
+                proof_hash: proof_hash.clone(),
+            });
+
+            // Delayed-reveal sessions keep the tile type off the live feed until
+            // the embargo lapses (a finished game releases everything at once)
+            let delay = Self::effective_reveal_delay(&game.config);
+            let embargoed = delay > 0 && game.phase != Phase::Finished;
+            if embargoed {
+                Self::embargo_reveal(env, session_id, defender_num, tile_index, tile_type, delay);
+            }
+
+            AttackAnswered {
+                session_id,
+                move_number: game.move_count,
+                player_slot: defender_num,
+                tile_index,
+                tile_type:   if embargoed { TILE_EMBARGOED } else { tile_type },
+                next_turn:   game.current_turn,
+                phase:       game.phase.clone(),
+                winner:      game.winner,
+                proof_hash,
+            }
+            .publish(env);
+
+            // A decided game ignores any remaining answers
+            if game.winner != 0 { break; }
+        }
+        game.pending_tiles = vec![env];
+
+        Self::save_game(env, session_id, &game);
+        Self::bump_game_ttl(env, session_id);
+        Self::run_attack_queue(env, session_id, &game);
+        Ok(())
+    }
+
+    /// Turn logic after the defender's answer: shield = same player attacks
+    /// again (up to the bonus cap), otherwise switch — unless the next player
+    /// forfeited it to a Poison. A round ends with each of player2's turns.
+    fn advance_turn(game: &mut GameState, shield_found: bool) {
+        let cap = game.config.max_bonus_attacks;
+        if shield_found && (cap == 0 || game.consecutive_bonus < cap) {
+            game.consecutive_bonus += 1;
+            return;
+        }
+        game.consecutive_bonus = 0;
+        if game.current_turn == 2 { game.rounds_played += 1; }
+        let next = if game.current_turn == 1 { 2 } else { 1 };
+        if game.skip_next_turn == next {
+            game.skip_next_turn = 0;
         } else {
-            game.player2_commitment.clone()
+            game.current_turn = next;
+        }
+    }
+
+    /// Shared start path: validate, lock stakes on the GameHub, store the game.
+    fn open_game(
+        env:            &Env,
+        session_id:     u32,
+        player1:        Address,
+        player2:        Address,
+        player1_points: i128,
+        player2_points: i128,
+        config:         GameConfig,
+        wagers:         Vec<Wager>,
+    ) -> Result<(), Error> {
+        Self::open_game_with(env, session_id, player1, player2, player1_points, player2_points, config, wagers, 0, 0)
+    }
+
+    /// `open_game` on a fixed board size and VK version (0 = by stake tier / current).
+    fn open_game_with(
+        env:            &Env,
+        session_id:     u32,
+        player1:        Address,
+        player2:        Address,
+        player1_points: i128,
+        player2_points: i128,
+        config:         GameConfig,
+        wagers:         Vec<Wager>,
+        board_size:     u32,
+        vk_version:     u32,
+    ) -> Result<(), Error> {
+        Self::check_accepting_games(env)?;
+        if player1 == player2 { return Err(Error::SelfPlay); }
+        Self::check_session_unused(env, session_id)?;
+        Self::validate_stakes(player1_points, player2_points)?;
+        Self::check_stake_limits(env, player1_points, player2_points)?;
+        // Friendly (zero-stake) games are the onboarding path, so they skip the tutorial gate
+        if player1_points > 0 || player2_points > 0 {
+            Self::check_tutorial_gate(env, &player1, &player2)?;
+        }
+        Self::check_stake_cap(env, &player1, &player2, player1_points)?;
+        Self::check_stake_cap(env, &player2, &player1, player2_points)?;
+
+        if config.lite { Self::check_feature(env, FEATURE_LITE)?; }
+
+        let mut config = config;
+        let tier = Self::stake_tier(env, player1_points.max(player2_points));
+        Self::apply_tier_clock(&mut config, &tier)?;
+        let board_size = if board_size == 0 { tier.board_size } else { board_size };
+        Self::validate_config(env, &config, board_size)?;
+
+        let hub      = Self::session_hub(env, &config)?;
+        let mut game = Self::new_game(env, player1, player2, player1_points, player2_points, hub);
+        game.board_size = board_size;
+        if vk_version != 0 { game.vk_version = vk_version; }
+        game.player1_time_left = config.time_bank;
+        game.player2_time_left = config.time_bank;
+        if config.lite { game.lite_boards = Self::lite_boards(env); }
+        game.config     = config;
+        // A raked pot has to be released by this contract, so it is pooled
+        // instead of escrowed by the hub; split-hub, friendly and token sessions are not raked,
+        // nor are sessions off the pool hub, whose points the fee and jackpot pools cannot hold
+        let other_hub = game.hub != Self::pool_hub(env)?;
+        if game.config.player2_hub.is_some() || other_hub || Self::is_friendly(&game) || !wagers.is_empty() {
+            game.fee_bps     = 0;
+            game.jackpot_bps = 0;
+        }
+        // Token stakes are held by this contract itself
+        game.pooled     = game.fee_bps > 0 || game.jackpot_bps > 0 || !wagers.is_empty();
+        game.wagers     = wagers;
+        game.persistent = Self::is_persistent_stake(env, player1_points.max(player2_points));
+
+        Self::lock_stakes(env, session_id, &game)?;
+        Self::store_new_game(env, session_id, &game);
+        Ok(())
+    }
+
+    fn default_config(env: &Env) -> GameConfig {
+        GameConfig {
+            poison_count:      DEFAULT_POISON,
+            shield_count:      DEFAULT_SHIELD,
+            player2_hub:       None,
+            reveal_delay:      0,
+            broadcast:         false,
+            rules_hash:        None,
+            time_bank:         0,
+            salvo_size:        0,
+            poison_penalty:    false,
+            max_bonus_attacks: 0,
+            decoy_count:       0,
+            lite:              false,
+            response_window:   0,
+            slash_bps:         0,
+            dispute_window:    0,
+            simultaneous:      false,
+            max_rounds:        0,
+            win_condition:     WinCondition::AllSpecials,
+            board_poison:      vec![env],
+            commitment_scheme: CommitmentScheme::Pedersen,
+            commit_window:     0,
+            hub:               None,
+        }
+    }
+
+    fn validate_config(env: &Env, config: &GameConfig, board_size: u32) -> Result<(), Error> {
+        // A handicap names both boards' Poison counts
+        if !config.board_poison.is_empty() && config.board_poison.len() != 2 { return Err(Error::InvalidConfig); }
+        for slot in [1, 2] {
+            let poison = config.poison_on(slot);
+            if poison == 0                                          { return Err(Error::InvalidConfig); }
+            if poison + config.shield_count + config.decoy_count > board_size {
+                return Err(Error::InvalidConfig);
+            }
+        }
+        if config.slash_bps > MAX_BPS                               { return Err(Error::InvalidConfig); }
+        // A bond is only ever slashed through an expired response window
+        if config.slash_bps > 0 && config.response_window == 0      { return Err(Error::InvalidConfig); }
+        // Every salvo must be answerable within one call's proof budget
+        if config.salvo_size > Self::get_max_proofs_per_call(env.clone()) { return Err(Error::InvalidConfig); }
+        // A split session must really use two hubs, or the same hub would see the session twice
+        if config.player2_hub.as_ref() == Some(&Self::session_hub(env, config)?) { return Err(Error::InvalidConfig); }
+        // Lite reveals are unproven until the win claim, so they cannot decide a tiebreak
+        if config.max_rounds > 0 && config.lite                     { return Err(Error::InvalidConfig); }
+        Self::validate_win_condition(config)?;
+        Self::validate_commitment_scheme(env, config)?;
+        Self::validate_decoys(env, config)?;
+        Self::validate_simultaneous(config)
+    }
+
+    /// Contracts cannot read the host's remaining budget, so the guard is a
+    /// configured cap on proofs verified per invocation; exceeding it fails
+    /// early with `InsufficientResources` instead of trapping mid-verify.
+    #[cfg(not(feature = "testutils"))]
+    fn verify_proof(_env: &Env, vk: &ParsedVk, proof: &Bytes, pub_inputs: &Bytes) -> Result<(), Error> {
+        let inputs = vk.get_vk().public_inputs_size
+            .checked_sub(ultrahonk_soroban_verifier::types::PAIRING_POINTS_SIZE as u64)
+            .ok_or(Error::VkParseError)?;
+        Self::check_proof_shape(proof, pub_inputs, inputs as u32 * 32)?;
+        vk.verify(proof, pub_inputs)
+            .map_err(|_| Error::InvalidProof)
+    }
+
+    /// Test builds accept a proof whose first 32 bytes are
+    /// keccak256(public inputs) — see `testutils::mock_proof`.
+    #[cfg(feature = "testutils")]
+    fn verify_proof(env: &Env, _vk: &ParsedVk, proof: &Bytes, pub_inputs: &Bytes) -> Result<(), Error> {
+        Self::check_proof_shape(proof, pub_inputs, pub_inputs.len())?;
+        let expected: BytesN<32> = env.crypto().keccak256(pub_inputs).into();
+        if proof.slice(0..32) != Bytes::from(expected) { return Err(Error::InvalidProof); }
+        Ok(())
+    }
+
+    /// Encoding checks ahead of the verifier, so a malformed submission is
+    /// told apart from a proof that does not verify.
+    fn check_proof_shape(proof: &Bytes, pub_inputs: &Bytes, input_bytes: u32) -> Result<(), Error> {
+        if proof.len() != PROOF_BYTES as u32                                       { return Err(Error::ProofWrongLength);     }
+        if !pub_inputs.len().is_multiple_of(32) || pub_inputs.len() != input_bytes { return Err(Error::PublicInputsMismatch); }
+        Ok(())
+    }
+
+    /// Parse raw VK bytes into the form kept in storage. Keys are parsed
+    /// when installed, so a malformed one is rejected up front.
+    #[cfg(not(feature = "testutils"))]
+    fn parse_vk(env: &Env, vk_bytes: &Bytes) -> Result<StoredVk, Error> {
+        let vk = ultrahonk_soroban_verifier::utils::load_vk_from_bytes(vk_bytes).ok_or(Error::VkParseError)?;
+        Ok(StoredVk::from_key(env, &vk))
+    }
+
+    /// Test builds run on placeholder keys and never parse them.
+    #[cfg(feature = "testutils")]
+    fn parse_vk(env: &Env, _vk_bytes: &Bytes) -> Result<StoredVk, Error> {
+        Ok(StoredVk { circuit_size: 0, log_circuit_size: 0, public_inputs_size: 0, points: Vec::new(env) })
+    }
+
+    /// Ready-to-use verifier for a stored key.
+    #[cfg(not(feature = "testutils"))]
+    fn verifier_of(env: &Env, vk: &StoredVk) -> Result<ParsedVk, Error> {
+        if vk.points.len() != VK_POINTS { return Err(Error::VkParseError); }
+        Ok(ParsedVk::new_with_vk(env, vk.to_key()))
+    }
+
+    #[cfg(feature = "testutils")]
+    fn verifier_of(_env: &Env, _vk: &StoredVk) -> Result<ParsedVk, Error> {
+        Ok(())
+    }
+
+    /// Gate for every entrypoint that opens a game or moves one forward.
+    pub(crate) fn check_operational(env: &Env) -> Result<(), Error> {
+        if !Self::is_initialized(env.clone()) { return Err(Error::NotInitialized); }
+        if Self::is_paused(env.clone())       { return Err(Error::ContractPaused); }
+        Ok(())
+    }
+
+    /// New stakes are only accepted once a VK exists to verify the game.
+    pub(crate) fn check_accepting_games(env: &Env) -> Result<(), Error> {
+        Self::check_operational(env)?;
+        if !Self::has_vk(env.clone()) { return Err(Error::VkNotSet); }
+        Ok(())
+    }
+
+    fn check_verify_budget(env: &Env, proofs: u32) -> Result<(), Error> {
+        let max: u32 = env.storage().instance()
+            .get(&DataKey::MaxProofsPerCall).unwrap_or(DEFAULT_MAX_PROOFS_PER_CALL);
+        if proofs > max { return Err(Error::InsufficientResources); }
+        Ok(())
+    }
+
+    pub(crate) fn require_admin(env: &Env) -> Result<(), Error> {
+        Self::get_admin(env.clone())?.require_auth();
+        Ok(())
+    }
+
+    /// Refuse a board hash for `slot` that is the all-zero sentinel of an
+    /// uncommitted board, or a copy of the opponent's committed one.
+    pub(crate) fn check_commitment(game: &GameState, slot: u32, board_hash: &BytesN<32>) -> Result<(), Error> {
+        if board_hash.to_array() == [0u8; 32] { return Err(Error::ZeroCommitment); }
+        let (opponent_committed, opponent_hash) = if slot == 1 {
+            (game.player2_committed, &game.player2_commitment)
+        } else {
+            (game.player1_committed, &game.player1_commitment)
         };
+        if opponent_committed && opponent_hash == board_hash { return Err(Error::DuplicateCommitment); }
+        Ok(())
+    }
+
+    /// `commitment_inputs` for `slot`'s board in a two-player session.
+    pub(crate) fn board_inputs(env: &Env, game: &GameState, session_id: u32, slot: u32) -> Bytes {
+        let commitment = if slot == 1 { &game.player1_commitment } else { &game.player2_commitment };
+        commitment_inputs(env, commitment, &game.board_owners.get_unchecked(slot - 1), session_id)
+    }
 
-        let mut pub_inputs = Bytes::new(&env);
-
-        // [0..32] commitment
-        pub_inputs.append(&Bytes::from(defender_commitment));
-
-        // [32..64] tile_index — big-endian u32 in 32 bytes
-        let mut tile_idx_be = [0u8; 32];
-        tile_idx_be[31] = game.pending_attack_tile as u8;
-        // handle values > 255 properly (tile index 0-14 so u8 is fine, but be safe)
-        tile_idx_be[28] = ((game.pending_attack_tile >> 24) & 0xff) as u8;
-        tile_idx_be[29] = ((game.pending_attack_tile >> 16) & 0xff) as u8;
-        tile_idx_be[30] = ((game.pending_attack_tile >>  8) & 0xff) as u8;
-        tile_idx_be[31] = ( game.pending_attack_tile        & 0xff) as u8;
-        pub_inputs.append(&Bytes::from_array(&env, &tile_idx_be));
-
-        // [64..96] tile_type — big-endian u32 in 32 bytes
-        let mut tile_type_be = [0u8; 32];
-        tile_type_be[28] = ((tile_type >> 24) & 0xff) as u8;
-        tile_type_be[29] = ((tile_type >> 16) & 0xff) as u8;
-        tile_type_be[30] = ((tile_type >>  8) & 0xff) as u8;
-        tile_type_be[31] = ( tile_type        & 0xff) as u8;
-        pub_inputs.append(&Bytes::from_array(&env, &tile_type_be));
-
-        assert!(pub_inputs.len() == PUB_INPUT_BYTES);
-
-        // ── UltraHonk verification ────────────────────────────────────────
-        let verifier = UltraHonkVerifier::new(&env, &vk_bytes)
-            .map_err(|_| Error::VkParseError)?;
-        verifier.verify(&proof_blob, &pub_inputs)
-            .map_err(|_| Error::InvalidProof)?;
-
-        // ── ZK verified — record the tile on the DEFENDER's revealed list ─
-        let tile_index = game.pending_attack_tile;
+    /// Record a verified reveal on the `defender` slot's board.
+    pub(crate) fn record_reveal(game: &mut GameState, defender: u32, tile_index: u32, tile_type: u32) {
         let revealed = RevealedTile { tile_index, tile_type };
-        if defender_num == 1 {
+        if defender == 1 {
             game.p1_revealed.push_back(revealed);
+            game.p1_mask.reveal(tile_index, tile_type);
         } else {
             game.p2_revealed.push_back(revealed);
+            game.p2_mask.reveal(tile_index, tile_type);
+        }
+    }
+
+    fn new_game(
+        env:            &Env,
+        player1:        Address,
+        player2:        Address,
+        player1_points: i128,
+        player2_points: i128,
+        hub:            Address,
+    ) -> GameState {
+        // Prefer player1's locale hint, fall back to player2's
+        let locale = Self::get_locale(env.clone(), player1.clone())
+            .or_else(|| Self::get_locale(env.clone(), player2.clone()));
+
+        let zero = BytesN::from_array(env, &[0u8; 32]);
+        GameState {
+            board_owners:       vec![env, player1.clone(), player2.clone()],
+            player1,
+            player2,
+            player1_points,
+            player2_points,
+            player1_commitment: zero.clone(),
+            player2_commitment: zero,
+            player1_committed: false,
+            player2_committed: false,
+            phase:              Phase::WaitingForCommits,
+            current_turn:       1,
+            pending_tiles:      vec![env],
+            p1_revealed: vec![env],
+            p2_revealed: vec![env],
+            p1_mask:     BoardMask::new(),
+            p2_mask:     BoardMask::new(),
+            
+            winner: 0,
+            locale,
+            series_id: None,
+            board_size: TOTAL_TILES,
+            config:     Self::default_config(env),
+            hub,
+            move_count: 0,
+            pooled:     false,
+            fee_bps:    Self::get_fee_bps(env.clone()),
+            jackpot_bps: Self::get_jackpot_bps(env.clone()),
+            wagers:     vec![env],
+            player1_time_left:  0,
+            player2_time_left:  0,
+            last_action_ledger: env.ledger().sequence(),
+            settled:            false,
+            vk_version:         Self::get_vk_version(env.clone()),
+            skip_next_turn:     0,
+            consecutive_bonus:  0,
+            pending_scan:       None,
+            player1_scanned:    false,
+            player2_scanned:    false,
+            lite_boards:        vec![env],
+            forfeited:          0,
+            finalize_after:     0,
+            overturned:         false,
+            perfect:            false,
+            persistent:         false,
+            round:              SimulRound::new(env),
+            rounds_played:      0,
         }
-        game.has_pending_attack = false;
-
-        // ── Check win condition ───────────────────────────────────────────
-        // Winner is the ATTACKER who just found the tile.
-        // Check attacker's "found" list = defender's revealed board.
-        let attacker_found = if attacker_num == 1 { &game.p2_revealed }
-                             else                  { &game.p1_revealed };
-
-        if attacker_won(attacker_found) {
-            // Attacker found 2 Poison + 1 Shield — they win immediately
-            let player1_won = attacker_num == 1;
-            Self::finish_game(&env, session_id, &mut game, player1_won)?;
-        } else {
-    // Turn logic: shield = same player attacks again, otherwise switch
-    if tile_type != 2 {
-        game.current_turn = if game.current_turn == 1 { 2 } else { 1 };
     }
-    // If tile_type == 2, turn stays the same – attacker gets another attack
-}
 
-        env.storage().temporary().set(&key, &game);
+    /// Highest tier `stake` reaches; below every tier it is the default
+    /// untimed 15-tile ruleset.
+    fn stake_tier(env: &Env, stake: i128) -> StakeTier {
+        let tiers: Vec<StakeTier> = env.storage().instance()
+            .get(&DataKey::StakeTiers).unwrap_or(vec![env]);
+        let mut current = StakeTier { min_stake: 0, board_size: TOTAL_TILES, time_bank: 0 };
+        for tier in tiers.iter() {
+            if stake >= tier.min_stake { current = tier; }
+        }
+        current
+    }
+
+    /// Hold a session to its tier's time control: untimed sessions get the
+    /// tier clock, and a longer clock than the tier allows is rejected.
+    fn apply_tier_clock(config: &mut GameConfig, tier: &StakeTier) -> Result<(), Error> {
+        if tier.time_bank == 0 { return Ok(()); }
+        if config.time_bank == 0 { config.time_bank = tier.time_bank; }
+        if config.time_bank > tier.time_bank { return Err(Error::InvalidConfig); }
         Ok(())
     }
 
-    // ========================================================================
-    // get_game
-    // ========================================================================
+    /// Session ids are single-use. Live and settled sessions are caught
+    /// by their game or receipt; the persistent `UsedSession` marker also
+    /// catches ids whose live game expired without settling.
+    pub(crate) fn check_session_unused(env: &Env, session_id: u32) -> Result<(), Error> {
+        if Self::has_game(env, session_id)
+            || Self::get_settlement_receipt(env.clone(), session_id).is_some()
+            || env.storage().persistent().has(&DataKey::OpenGame(session_id))
+        {
+            return Err(Error::SessionExists);
+        }
+        if env.storage().persistent().has(&DataKey::UsedSession(session_id)) { return Err(Error::SessionIdReused); }
+        Ok(())
+    }
 
-    pub fn get_game(env: Env, session_id: u32) -> Result<GameState, Error> {
-        env.storage().temporary()
-            .get(&DataKey::Game(session_id)).ok_or(Error::GameNotFound)
+    fn store_new_game(env: &Env, session_id: u32, game: &GameState) {
+        Self::save_game(env, session_id, game);
+        Self::bump_game_ttl(env, session_id);
+        let used = DataKey::UsedSession(session_id);
+        env.storage().persistent().set(&used, &true);
+        env.storage().persistent().extend_ttl(&used, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        Self::index_game(env, &game.player1, session_id);
+        Self::index_game(env, &game.player2, session_id);
+        if game.phase != Phase::Finished { Self::activate(env, session_id); }
     }
 
     // ========================================================================
-    // Internal helpers
+    // Admin
     // ========================================================================
 
-    fn finish_game(
-        env:          &Env,
-        session_id:   u32,
-        game:         &mut GameState,
-        player1_won:  bool,
-    ) -> Result<(), Error> {
-        // Tell GameHub to pay out the winner from escrow
-        let hub_addr: Address = env.storage().instance()
-            .get(&DataKey::GameHubAddress).expect("GameHub not set");
-        GameHubClient::new(env, &hub_addr).end_game(&session_id, &player1_won);
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)
+    }
 
-        game.winner = if player1_won { 1 } else { 2 };
-        game.phase  = Phase::Finished;
+    pub fn set_hub(env: Env, new_hub: Address) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_hub", vec![&env, new_hub.clone().into_val(&env)])?;
+        Self::pin_pool_hub(&env)?;
+        env.storage().instance().set(&DataKey::GameHubAddress, &new_hub);
         Ok(())
     }
 
-    // ========================================================================
-    // Admin
-    // ========================================================================
+    /// Replace the stake-tier table. Tiers must be sorted by ascending
+    /// `min_stake`. The reveal circuit proves 15-tile boards only, so every
+    /// tier names that board size; tiers differ by their time control.
+    /// An empty list restores the default untimed 15-tile ruleset.
+    pub fn set_stake_tiers(env: Env, tiers: Vec<StakeTier>) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_stake_tiers", vec![&env, tiers.clone().into_val(&env)])?;
+
+        let mut prev: Option<i128> = None;
+        for tier in tiers.iter() {
+            if tier.board_size != TOTAL_TILES            { return Err(Error::InvalidConfig); }
+            if prev.is_some_and(|p| tier.min_stake <= p) { return Err(Error::InvalidConfig); }
+            prev = Some(tier.min_stake);
+        }
+        env.storage().instance().set(&DataKey::StakeTiers, &tiers);
+        Ok(())
+    }
 
-    pub fn get_admin(env: Env) -> Address {
-        env.storage().instance().get(&DataKey::Admin).expect("Admin not set")
+    /// Raise or lower how many proofs one invocation may verify.
+    pub fn set_max_proofs_per_call(env: Env, max: u32) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_max_proofs_per_call", vec![&env, max.into_val(&env)])?;
+        if max == 0 { return Err(Error::InvalidConfig); }
+        env.storage().instance().set(&DataKey::MaxProofsPerCall, &max);
+        Ok(())
     }
 
-    pub fn set_hub(env: Env, new_hub: Address) {
-        let admin: Address = env.storage().instance()
-            .get(&DataKey::Admin).expect("Admin not set");
-        admin.require_auth();
-        env.storage().instance().set(&DataKey::GameHubAddress, &new_hub);
+    pub fn get_max_proofs_per_call(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::MaxProofsPerCall).unwrap_or(DEFAULT_MAX_PROOFS_PER_CALL)
     }
 
-    pub fn get_hub(env: Env) -> Address {
-        env.storage().instance().get(&DataKey::GameHubAddress).expect("GameHub not set")
+    pub fn get_stake_tiers(env: Env) -> Vec<StakeTier> {
+        env.storage().instance().get(&DataKey::StakeTiers).unwrap_or(vec![&env])
     }
 
-    pub fn set_admin(env: Env, new_admin: Address) {
-        let admin: Address = env.storage().instance()
-            .get(&DataKey::Admin).expect("Admin not set");
-        admin.require_auth();
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+    /// Emergency stop: blocks new games and moves; cancellations and
+    /// refunds keep working so no stake is trapped.
+    pub fn pause(env: Env) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "pause", vec![&env])?;
+        env.storage().instance().set(&DataKey::Paused, &true);
+        Ok(())
+    }
+
+    pub fn unpause(env: Env) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "unpause", vec![&env])?;
+        env.storage().instance().remove(&DataKey::Paused);
+        Ok(())
     }
 
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        let admin: Address = env.storage().instance()
-            .get(&DataKey::Admin).expect("Admin not set");
-        admin.require_auth();
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    pub fn is_paused(env: Env) -> bool {
+        env.storage().instance().get(&DataKey::Paused).unwrap_or(false)
+    }
+
+    pub fn get_hub(env: Env) -> Result<Address, Error> {
+        env.storage().instance().get(&DataKey::GameHubAddress).ok_or(Error::NotInitialized)
+    }
+
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_admin", vec![&env, new_admin.clone().into_val(&env)])?;
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,189 @@
+//! # Stake limits
+//!
+//! Boundary invariants on wagered points, checked before anything reaches
+//! the GameHub, plus player-side safety caps on wagers. A player can pin a
+//! persistent maximum stake, either globally or against one specific
+//! opponent; sessions that exceed it are rejected at start even when a
+//! (possibly compromised) hot or session key signed for them.
+//!
+//! A cap takes effect at once when it is lowered, but raising or clearing
+//! it only lands `STAKE_CAP_DELAY_LEDGERS` later, queued where
+//! `get_pending_stake_cap` shows it, so a stolen key cannot lift the cap
+//! and stake past it in one go.
+//!
+//! The deployment's own risk limits sit on top: a minimum and maximum
+//! stake per player and, optionally, how many times the smaller stake the
+//! larger one may be.
+//!
+//! Stakes can also be scaled to rating (`set_rating_stake_scale`): a
+//! player at or below `DEFAULT_RATING` — every fresh wallet — may wager at
+//! most `base_stake`, plus `stake_per_point` for each point they are rated
+//! above it, so a new account cannot put up a huge stake to lure strong
+//! players. Ratings are per season, so a new season caps everyone low again,
+//! and friendly games do not move them.
+
+use soroban_sdk::{contractimpl, contracttype, vec, Address, Env, IntoVal};
+
+use crate::rating::DEFAULT_RATING;
+use crate::{DataKey, Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::PLAYER_TTL_LEDGERS;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeLimits {
+    pub min_stake: i128,
+    pub max_stake: i128, // 0 = no maximum
+    pub max_ratio: u32,  // larger stake / smaller stake; 0 = any asymmetry
+}
+
+/// Delay before a raised or cleared stake cap applies (~1 day).
+pub const STAKE_CAP_DELAY_LEDGERS: u32 = 17_280;
+
+/// A raised (Some) or cleared (None) stake cap waiting out the delay.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingStakeCap {
+    pub max_stake:    Option<i128>,
+    pub effective_at: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RatingStakeScale {
+    pub base_stake:      i128, // cap at or below DEFAULT_RATING; 0 = no rating gate
+    pub stake_per_point: i128, // added to the cap per rating point above DEFAULT_RATING
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    pub fn set_stake_limits(env: Env, limits: StakeLimits) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_stake_limits", vec![&env, limits.clone().into_val(&env)])?;
+        if limits.min_stake < 0 || limits.max_stake < 0 { return Err(Error::InvalidConfig); }
+        if limits.max_stake > 0 && limits.min_stake > limits.max_stake { return Err(Error::InvalidConfig); }
+        env.storage().instance().set(&DataKey::StakeLimits, &limits);
+        Ok(())
+    }
+
+    pub fn get_stake_limits(env: Env) -> StakeLimits {
+        env.storage().instance().get(&DataKey::StakeLimits)
+            .unwrap_or(StakeLimits { min_stake: 0, max_stake: 0, max_ratio: 0 })
+    }
+
+    pub fn set_rating_stake_scale(env: Env, scale: RatingStakeScale) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_rating_stake_scale", vec![&env, scale.clone().into_val(&env)])?;
+        if scale.base_stake < 0 || scale.stake_per_point < 0 { return Err(Error::InvalidConfig); }
+        env.storage().instance().set(&DataKey::RatingStakeScale, &scale);
+        Ok(())
+    }
+
+    pub fn get_rating_stake_scale(env: Env) -> RatingStakeScale {
+        env.storage().instance().get(&DataKey::RatingStakeScale)
+            .unwrap_or(RatingStakeScale { base_stake: 0, stake_per_point: 0 })
+    }
+
+    /// Most `player` may stake at their current rating (None = no rating gate).
+    pub fn get_rating_stake_cap(env: Env, player: Address) -> Option<i128> {
+        let scale = Self::get_rating_stake_scale(env.clone());
+        if scale.base_stake == 0 { return None; }
+        let above = Self::get_rating(env, player).saturating_sub(DEFAULT_RATING);
+        Some(scale.base_stake.saturating_add(scale.stake_per_point.saturating_mul(above as i128)))
+    }
+
+    /// Set (Some) or clear (None) `player`'s max stake. With `opponent` set
+    /// the cap only applies to games against that address; otherwise it is
+    /// the player's global cap. A lower cap applies immediately; a higher
+    /// one, or clearing it, is queued for `STAKE_CAP_DELAY_LEDGERS`.
+    pub fn set_stake_cap(
+        env:       Env,
+        player:    Address,
+        opponent:  Option<Address>,
+        max_stake: Option<i128>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        if max_stake.is_some_and(|cap| cap < 0) { return Err(Error::InvalidConfig); }
+
+        let current = Self::get_stake_cap(env.clone(), player.clone(), opponent.clone());
+        let key     = DataKey::StakeCap(player.clone(), opponent.clone());
+        let pending = DataKey::PendingStakeCap(player, opponent);
+        let tighter = match (current, max_stake) {
+            (None, Some(_))        => true,
+            (Some(cur), Some(cap)) => cap <= cur,
+            (_, None)              => current.is_none(),
+        };
+        if tighter {
+            match max_stake {
+                Some(cap) => {
+                    env.storage().persistent().set(&key, &cap);
+                    env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+                }
+                None => env.storage().persistent().remove(&key),
+            }
+            env.storage().persistent().remove(&pending);
+        } else {
+            let change = PendingStakeCap {
+                max_stake,
+                effective_at: env.ledger().sequence() + STAKE_CAP_DELAY_LEDGERS,
+            };
+            env.storage().persistent().set(&pending, &change);
+            env.storage().persistent().extend_ttl(&pending, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        }
+        Ok(())
+    }
+
+    /// The cap in force now: a queued change counts once its delay has passed.
+    pub fn get_stake_cap(env: Env, player: Address, opponent: Option<Address>) -> Option<i128> {
+        if let Some(change) = Self::get_pending_stake_cap(env.clone(), player.clone(), opponent.clone()) {
+            if env.ledger().sequence() >= change.effective_at { return change.max_stake; }
+        }
+        env.storage().persistent().get(&DataKey::StakeCap(player, opponent))
+    }
+
+    pub fn get_pending_stake_cap(env: Env, player: Address, opponent: Option<Address>) -> Option<PendingStakeCap> {
+        env.storage().persistent().get(&DataKey::PendingStakeCap(player, opponent))
+    }
+}
+
+impl PoisonGameContract {
+    /// Stakes must be non-negative and the combined pot must fit in an i128.
+    pub(crate) fn validate_stakes(player1_points: i128, player2_points: i128) -> Result<(), Error> {
+        if player1_points < 0 || player2_points < 0             { return Err(Error::NegativeStake); }
+        if player1_points.checked_add(player2_points).is_none() { return Err(Error::StakeOverflow); }
+        Ok(())
+    }
+
+    /// Apply the deployment's stake limits to a pair of stakes.
+    pub(crate) fn check_stake_limits(env: &Env, player1_points: i128, player2_points: i128) -> Result<(), Error> {
+        // Friendly games wager nothing, so there is no risk to bound
+        if player1_points == 0 && player2_points == 0 { return Ok(()); }
+        let limits = Self::get_stake_limits(env.clone());
+        let (low, high) = (player1_points.min(player2_points), player1_points.max(player2_points));
+        if low < limits.min_stake                          { return Err(Error::StakeTooLow);  }
+        if limits.max_stake > 0 && high > limits.max_stake { return Err(Error::StakeTooHigh); }
+        if limits.max_ratio > 0 && high > low.saturating_mul(limits.max_ratio as i128) {
+            return Err(Error::StakeTooHigh);
+        }
+        Ok(())
+    }
+
+    /// Reject `stake` if it exceeds `player`'s per-opponent or global cap,
+    /// or what their rating allows.
+    pub(crate) fn check_stake_cap(
+        env:      &Env,
+        player:   &Address,
+        opponent: &Address,
+        stake:    i128,
+    ) -> Result<(), Error> {
+        let caps = [
+            Self::get_stake_cap(env.clone(), player.clone(), Some(opponent.clone())),
+            Self::get_stake_cap(env.clone(), player.clone(), None),
+        ];
+        for cap in caps.into_iter().flatten() {
+            if stake > cap { return Err(Error::StakeCapExceeded); }
+        }
+        match Self::get_rating_stake_cap(env.clone(), player.clone()) {
+            Some(cap) if stake > cap => Err(Error::StakeTooHigh),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! # Lite mode
+//!
+//! A session opened with `config.lite` trades per-move proofs for a single
+//! proof at the end. Answers to attacks are not verified and not stored as
+//! reveal lists: each one is published as an `AttackAnswered` event and
+//! folded into a running digest of the defender's board,
+//! `digest' = keccak256(digest || tile_index || tile_type)` (32-byte fields,
+//! starting from 32 zero bytes), alongside a bitmask of revealed tiles and
+//! the special counts claimed so far.
+//!
+//! Attacks stop once either board is settled — its claimed Poison and Shield
+//! counts are complete, or every tile has been revealed. The player facing a
+//! settled board then calls `claim_lite_win` with an aggregated proof from
+//! the lite circuit (`circuits/poison_lite`) that every answer they gave on
+//! their *own* board was honest: it replays the digest chain with the tile
+//! types of the committed board. Admissions of
+//! defeat need no proof; a player who hid specials to survive cannot produce
+//! the claim proof and is left to the clock.
+//!
+//! Claim public inputs (224 bytes, built from on-chain state):
+//!   [0..32]    = claimer's board commitment
+//!   [32..64]   = claimer's board owner (address field, see `commit_board`)
+//!   [64..96]   = session_id
+//!   [96..128]  = claimer's board digest, top byte cleared to fit a field
+//!   [128..160] = number of reveals folded into the digest
+//!   [160..192] = poison_count
+//!   [192..224] = shield_count
+
+use soroban_sdk::{contractimpl, contracttype, vec, Address, Bytes, BytesN, Env, IntoVal, Vec};
+use ultrahonk_soroban_verifier::PROOF_BYTES;
+
+use crate::{u32_field, AttackAnswered, DataKey, Error, GameState, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::PUB_INPUT_BYTES;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiteBoard {
+    pub digest:   BytesN<32>,
+    pub mask:     u32, // bit i set once tile i has been revealed
+    pub poison:   u32,
+    pub shield:   u32,
+}
+
+impl LiteBoard {
+    fn new(env: &Env) -> Self {
+        LiteBoard { digest: BytesN::from_array(env, &[0u8; 32]), mask: 0, poison: 0, shield: 0 }
+    }
+
+    fn reveals(&self) -> u32 {
+        self.mask.count_ones()
+    }
+
+    /// `slot` is the board's owner.
+    fn settled(&self, game: &GameState, slot: u32) -> bool {
+        let complete = self.poison >= game.config.poison_on(slot) && self.shield >= game.config.shield_count;
+        complete || self.reveals() >= game.board_size
+    }
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Install the aggregated reveal-set circuit's VK.
+    pub fn set_lite_vk(env: Env, vk_bytes: Bytes) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_lite_vk", vec![&env, vk_bytes.clone().into_val(&env)])?;
+        Self::install_vk(&env, &DataKey::LiteVk, &vk_bytes)
+    }
+
+    /// Claim a lite game once the opponent's board is settled, proving the
+    /// claimer's own reveal set in one aggregated proof.
+    pub fn claim_lite_win(env: Env, session_id: u32, claimer: Address, proof_blob: Bytes) -> Result<(), Error> {
+        claimer.require_auth();
+        Self::check_operational(&env)?;
+
+        let mut game = Self::get_game(env.clone(), session_id)?;
+        if !game.config.lite            { return Err(Error::WrongPhase);       }
+        if game.phase != Phase::Playing { return Err(Error::WrongPhase);       }
+        if game.winner != 0             { return Err(Error::GameAlreadyEnded); }
+
+        let claimer_num = if claimer == game.player1 { 1u32 }
+                          else if claimer == game.player2 { 2u32 }
+                          else { return Err(Error::NotPlayer); };
+        let own      = game.lite_boards.get_unchecked(claimer_num - 1);
+        let opponent = game.lite_boards.get_unchecked(2 - claimer_num);
+        if !opponent.settled(&game, 3 - claimer_num) { return Err(Error::WrongPhase); }
+
+        if proof_blob.len() != PROOF_BYTES as u32 { return Err(Error::ProofWrongLength); }
+        Self::check_verify_budget(&env, 1)?;
+
+        let mut pub_inputs = Self::board_inputs(&env, &game, session_id, claimer_num);
+        pub_inputs.append(&digest_field(&env, &own.digest));
+        pub_inputs.append(&u32_field(&env, own.reveals()));
+        pub_inputs.append(&u32_field(&env, game.config.poison_on(claimer_num)));
+        pub_inputs.append(&u32_field(&env, game.config.shield_count));
+        if pub_inputs.len() != PUB_INPUT_BYTES { return Err(Error::InternalInvariant); }
+
+        Self::verify_proof(&env, &Self::load_verifier(&env, &DataKey::LiteVk)?, &proof_blob, &pub_inputs)?;
+
+        game.pending_tiles = vec![&env];
+        Self::finish_game(&env, session_id, &mut game, claimer_num == 1)?;
+        Self::save_game(&env, session_id, &game);
+        Ok(())
+    }
+}
+
+/// A board digest as a public input: the top byte is cleared so the value
+/// is below the circuit's field modulus.
+pub(crate) fn digest_field(env: &Env, digest: &BytesN<32>) -> Bytes {
+    let mut bytes = digest.to_array();
+    bytes[0] = 0;
+    Bytes::from_array(env, &bytes)
+}
+
+impl PoisonGameContract {
+    /// Fresh per-board digests for a lite session.
+    pub(crate) fn lite_boards(env: &Env) -> Vec<LiteBoard> {
+        vec![env, LiteBoard::new(env), LiteBoard::new(env)]
+    }
+
+    /// Lite-session attack checks: the tile is unrevealed and no board has
+    /// settled yet.
+    pub(crate) fn check_lite_attack(game: &GameState, defender_num: u32, tile_index: u32) -> Result<(), Error> {
+        if game.lite_boards.iter().zip(1..).any(|(b, slot)| b.settled(game, slot)) { return Err(Error::WrongPhase); }
+        let board = game.lite_boards.get_unchecked(defender_num - 1);
+        if board.mask & (1 << tile_index) != 0 { return Err(Error::TileAlreadyRevealed); }
+        Ok(())
+    }
+
+    /// Lite counterpart of the proven answer loop: fold each answer into the
+    /// defender's digest and publish it, without verifying or storing it.
+    pub(crate) fn answer_lite(
+        env:          &Env,
+        session_id:   u32,
+        mut game:     GameState,
+        defender_num: u32,
+        responses:    Vec<(u32, Bytes)>,
+    ) -> Result<(), Error> {
+        let attacker_num = if defender_num == 1 { 2u32 } else { 1u32 };
+        let slot = defender_num - 1;
+        let mut board = game.lite_boards.get_unchecked(slot);
+        let mut shield_found = false;
+
+        for (i, (tile_type, proof_blob)) in responses.iter().enumerate() {
+            let tile_index = game.pending_tiles.get_unchecked(i as u32);
+
+            let mut preimage = Bytes::from(board.digest.clone());
+            preimage.append(&u32_field(env, tile_index));
+            preimage.append(&u32_field(env, tile_type));
+            board.digest = env.crypto().keccak256(&preimage).into();
+            board.mask  |= 1 << tile_index;
+            if tile_type == 1 {
+                board.poison += 1;
+                if game.config.poison_penalty { game.skip_next_turn = attacker_num; }
+            }
+            if tile_type == 2 { board.shield += 1; }
+            shield_found |= tile_type == 2;
+
+            if i as u32 + 1 == responses.len() {
+                Self::advance_turn(&mut game, shield_found);
+            }
+            game.move_count += 1;
+
+            AttackAnswered {
+                session_id,
+                move_number: game.move_count,
+                player_slot: defender_num,
+                tile_index,
+                tile_type,
+                next_turn:   game.current_turn,
+                phase:       game.phase.clone(),
+                winner:      game.winner,
+                proof_hash:  env.crypto().keccak256(&proof_blob).into(),
+            }
+            .publish(env);
+        }
+
+        game.lite_boards.set(slot, board);
+        game.pending_tiles = vec![env];
+        Self::save_game(env, session_id, &game);
+        Self::bump_game_ttl(env, session_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+//! # Open matchmaking lobby
+//!
+//! `create_open_game` locks only the creator's points (via the GameHub's
+//! extended escrow) and lists the session; anyone can `join_open_game` by
+//! locking a matching stake, which opens a normal game in
+//! `WaitingForCommits`. Stakes of lobby games stay in the contract's escrow
+//! pool and are released directly at settlement. A listing reserves its
+//! session id, so no other game can start under it while it is open.
+//!
+//! Listings live in persistent storage, so the creator's locked stake can
+//! always be found again. One left unjoined for `OPEN_GAME_LEDGERS` can no
+//! longer be joined; anyone may then `expire_open_game` it, refunding the
+//! creator and dropping it from the lobby.
+
+use soroban_sdk::{contractimpl, contracttype, vec, Address, Env, IntoVal, Symbol, Vec};
+
+use crate::{DataKey, Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::sandbox::Hub;
+use crate::{GAME_TTL_LEDGERS, MAX_PAGE_SIZE, PLAYER_TTL_LEDGERS};
+
+/// How long a listing stays joinable.
+pub const OPEN_GAME_LEDGERS: u32 = GAME_TTL_LEDGERS;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OpenGame {
+    pub session_id:     u32,
+    pub creator:        Address,
+    pub stake:          i128,
+    pub locale:         Option<Symbol>,
+    pub created_ledger: u32,
+    // GameHub the creator's stake was locked on; the game settles there too
+    pub hub:            Address,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// List a joinable session, locking the creator's stake up front.
+    pub fn create_open_game(
+        env:        Env,
+        session_id: u32,
+        creator:    Address,
+        stake:      i128,
+    ) -> Result<(), Error> {
+        creator.require_auth_for_args(vec![&env, session_id.into_val(&env), stake.into_val(&env)]);
+        Self::check_accepting_games(&env)?;
+        Self::validate_stakes(stake, stake)?;
+        Self::check_stake_limits(&env, stake, stake)?;
+
+        Self::check_session_unused(&env, session_id)?;
+
+        let hub = Self::get_hub(env.clone())?;
+        Hub::new(&env, &hub).lock_points(&env.current_contract_address(), &creator, &stake);
+
+        let open = OpenGame {
+            session_id,
+            locale: Self::get_locale(env.clone(), creator.clone()),
+            creator,
+            stake,
+            created_ledger: env.ledger().sequence(),
+            hub,
+        };
+        let key = DataKey::OpenGame(session_id);
+        env.storage().persistent().set(&key, &open);
+        env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+
+        let mut lobby = Self::lobby(&env);
+        lobby.push_back(session_id);
+        Self::save_lobby(&env, &lobby);
+        Ok(())
+    }
+
+    /// Join a listed session with a matching stake; the creator is player1.
+    pub fn join_open_game(env: Env, session_id: u32, joiner: Address) -> Result<(), Error> {
+        let open = Self::get_open_game(env.clone(), session_id)?;
+        joiner.require_auth_for_args(vec![&env, session_id.into_val(&env), open.stake.into_val(&env)]);
+
+        Self::check_accepting_games(&env)?;
+        if Self::listing_expired(&env, &open) { return Err(Error::GameNotFound); }
+        if joiner == open.creator { return Err(Error::SelfPlay); }
+        Self::check_tutorial_gate(&env, &open.creator, &joiner)?;
+        Self::check_stake_cap(&env, &open.creator, &joiner, open.stake)?;
+        Self::check_stake_cap(&env, &joiner, &open.creator, open.stake)?;
+
+        // The listing reserved the id; nothing else may have claimed it since
+        Self::delist(&env, session_id);
+        Self::check_session_unused(&env, session_id)?;
+
+        Hub::new(&env, &open.hub).lock_points(&env.current_contract_address(), &joiner, &open.stake);
+
+        let mut game = Self::new_game(&env, open.creator, joiner, open.stake, open.stake, open.hub);
+        let tier = Self::stake_tier(&env, open.stake);
+        Self::apply_tier_clock(&mut game.config, &tier)?;
+        game.board_size        = tier.board_size;
+        game.player1_time_left = game.config.time_bank;
+        game.player2_time_left = game.config.time_bank;
+        game.pooled     = true;
+        Self::store_new_game(&env, session_id, &game);
+        Ok(())
+    }
+
+    /// Creator withdraws an unjoined listing and gets their stake back.
+    pub fn cancel_open_game(env: Env, session_id: u32) -> Result<(), Error> {
+        let open = Self::get_open_game(env.clone(), session_id)?;
+        open.creator.require_auth();
+
+        Hub::new(&env, &open.hub).release_points(&env.current_contract_address(), &open.creator, &open.stake);
+        Self::delist(&env, session_id);
+        Ok(())
+    }
+
+    /// Refund and delist a listing nobody joined in time. Anyone may call it.
+    pub fn expire_open_game(env: Env, session_id: u32) -> Result<(), Error> {
+        let open = Self::get_open_game(env.clone(), session_id)?;
+        if !Self::listing_expired(&env, &open) { return Err(Error::ClockNotExpired); }
+
+        Hub::new(&env, &open.hub).release_points(&env.current_contract_address(), &open.creator, &open.stake);
+        Self::delist(&env, session_id);
+        Ok(())
+    }
+
+    pub fn get_open_game(env: Env, session_id: u32) -> Result<OpenGame, Error> {
+        env.storage().persistent()
+            .get(&DataKey::OpenGame(session_id)).ok_or(Error::GameNotFound)
+    }
+
+    /// Page through joinable sessions in listing order. Listings whose
+    /// creator shares `locale` are moved to the front of the page, so
+    /// clients can prefer same-region opponents. `limit` is capped at 50.
+    pub fn list_open_games(
+        env:    Env,
+        locale: Option<Symbol>,
+        cursor: u32,
+        limit:  u32,
+    ) -> Vec<OpenGame> {
+        let lobby = Self::lobby(&env);
+        let end   = lobby.len().min(cursor.saturating_add(limit.min(MAX_PAGE_SIZE)));
+
+        let mut preferred = Vec::new(&env);
+        let mut others    = Vec::new(&env);
+        for id in lobby.iter().skip(cursor as usize).take(end.saturating_sub(cursor) as usize) {
+            // Expired listings are waiting for `expire_open_game`
+            if let Ok(open) = Self::get_open_game(env.clone(), id) {
+                if Self::listing_expired(&env, &open) { continue; }
+                if locale.is_some() && open.locale == locale { preferred.push_back(open); }
+                else                                         { others.push_back(open);    }
+            }
+        }
+        preferred.append(&others);
+        preferred
+    }
+}
+
+impl PoisonGameContract {
+    pub(crate) fn lobby(env: &Env) -> Vec<u32> {
+        env.storage().persistent().get(&DataKey::Lobby).unwrap_or(vec![env])
+    }
+
+    fn save_lobby(env: &Env, lobby: &Vec<u32>) {
+        env.storage().persistent().set(&DataKey::Lobby, lobby);
+        env.storage().persistent().extend_ttl(&DataKey::Lobby, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+    }
+
+    fn listing_expired(env: &Env, open: &OpenGame) -> bool {
+        env.ledger().sequence() > open.created_ledger.saturating_add(OPEN_GAME_LEDGERS)
+    }
+
+    fn delist(env: &Env, session_id: u32) {
+        env.storage().persistent().remove(&DataKey::OpenGame(session_id));
+        let mut lobby = Self::lobby(env);
+        if let Some(i) = lobby.first_index_of(session_id) {
+            lobby.remove(i);
+            Self::save_lobby(env, &lobby);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! # Threshold admin
+//!
+//! The admin role can be handed to a council of signers with an M-of-N
+//! threshold. While a council is set, the sensitive operations — `init_vk`,
+//! `rotate_vk`, `set_scan_vk`, `set_lite_vk`, `set_poseidon_vk`,
+//! `set_decoy_vk`, scheduling or cancelling an upgrade, `set_hub`,
+//! `set_hub_version`, `allow_hub`, `disallow_hub`, `admin_cancel`, rating
+//! decay, `start_season`, `withdraw_fees`, `set_admin`, `pause` /
+//! `unpause`, `set_stake_tiers`, `set_sandbox_mode`, the rake rates
+//! (`set_fee_bps`, `set_jackpot_bps`, `set_referral_bps`), the trophy and
+//! badge contracts, `set_bet_market`, `set_champion`, the stake limits and
+//! rating scale, `set_max_proofs_per_call`, `set_persistent_stake`,
+//! `set_disabled_features` and changing the council itself — only execute once `threshold` signers have called
+//! `approve_action` with that operation's action hash. Approvals are
+//! consumed when the action runs.
+//!
+//! An action hash is keccak256 over the XDR of `(Symbol, args...)`:
+//! `("init_vk", vk_bytes)`, `("rotate_vk", vk_bytes, grace_ledgers)`,
+//! `("upgrade", wasm_hash)`, `("cancel_upgrade", wasm_hash)`,
+//! `("set_hub", hub)`, `("set_hub_version", hub, version)`,
+//! `("allow_hub", hub)`, `("disallow_hub", hub)`,
+//! `("admin_cancel", session_id, reason)`,
+//! `("set_rating_decay", decay)`, `("start_season", season)`,
+//! `("set_scan_vk", vk_bytes)`, `("set_lite_vk", vk_bytes)`,
+//! `("set_poseidon_vk", vk_bytes)`, `("set_decoy_vk", vk_bytes)`,
+//! `("withdraw_fees", to, points)`, `("set_admin", admin)`, `("pause")`,
+//! `("unpause")`, `("set_stake_tiers", tiers)`,
+//! `("set_sandbox_mode", enabled)`, `("set_fee_bps", bps)`,
+//! `("set_jackpot_bps", bps)`, `("set_referral_bps", bps)`,
+//! `("set_trophy_contract", trophy)`, `("set_badge_contract", badge)`,
+//! `("set_bet_market", token, cutoff)`,
+//! `("set_champion", champion, bounty, min_stake)`,
+//! `("set_stake_limits", limits)`, `("set_rating_stake_scale", scale)`,
+//! `("set_max_proofs_per_call", max)`, `("set_persistent_stake", min_stake)`,
+//! `("set_disabled_features", disabled)` or
+//! `("set_council", signers, threshold)`.
+
+use soroban_sdk::{contractimpl, vec, xdr::ToXdr, Address, BytesN, Env, IntoVal, Symbol, Val, Vec};
+
+use crate::{DataKey, Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::GAME_TTL_LEDGERS;
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Install (or, with an empty list, dissolve) the admin council. The
+    /// single admin configures the first council; after that the current
+    /// council must approve the change.
+    pub fn set_admin_council(env: Env, signers: Vec<Address>, threshold: u32) -> Result<(), Error> {
+        let args = vec![&env, signers.clone().into_val(&env), threshold.into_val(&env)];
+        Self::authorize_admin_action(&env, "set_council", args)?;
+
+        if signers.is_empty() {
+            env.storage().instance().remove(&DataKey::AdminSigners);
+            env.storage().instance().remove(&DataKey::AdminThreshold);
+            return Ok(());
+        }
+        if threshold == 0 || threshold > signers.len() { return Err(Error::InvalidConfig); }
+        for i in 0..signers.len() {
+            for j in (i + 1)..signers.len() {
+                if signers.get(i) == signers.get(j) { return Err(Error::InvalidConfig); }
+            }
+        }
+        env.storage().instance().set(&DataKey::AdminSigners, &signers);
+        env.storage().instance().set(&DataKey::AdminThreshold, &threshold);
+        Ok(())
+    }
+
+    pub fn get_admin_council(env: Env) -> (Vec<Address>, u32) {
+        let signers = env.storage().instance().get(&DataKey::AdminSigners).unwrap_or(vec![&env]);
+        let threshold = env.storage().instance().get(&DataKey::AdminThreshold).unwrap_or(0);
+        (signers, threshold)
+    }
+
+    /// Council signer approves a pending sensitive action.
+    pub fn approve_action(env: Env, signer: Address, action_hash: BytesN<32>) -> Result<u32, Error> {
+        signer.require_auth();
+        let (signers, _) = Self::get_admin_council(env.clone());
+        if !signers.contains(&signer) { return Err(Error::NotSigner); }
+
+        let mut approvals = Self::get_approvals(env.clone(), action_hash.clone());
+        if !approvals.contains(&signer) { approvals.push_back(signer); }
+
+        let key = DataKey::Approvals(action_hash);
+        env.storage().temporary().set(&key, &approvals);
+        env.storage().temporary().extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+        Ok(approvals.len())
+    }
+
+    pub fn get_approvals(env: Env, action_hash: BytesN<32>) -> Vec<Address> {
+        env.storage().temporary()
+            .get(&DataKey::Approvals(action_hash)).unwrap_or(vec![&env])
+    }
+}
+
+impl PoisonGameContract {
+    /// Single-admin auth, or — with a council — enough approvals for this
+    /// exact action, which are then consumed.
+    pub(crate) fn authorize_admin_action(env: &Env, action: &str, args: Vec<Val>) -> Result<(), Error> {
+        let (signers, threshold) = Self::get_admin_council(env.clone());
+        if signers.is_empty() { return Self::require_admin(env); }
+
+        let hash = Self::action_hash(env, action, args);
+        let key = DataKey::Approvals(hash.clone());
+        let approvals = Self::get_approvals(env.clone(), hash);
+        let valid = approvals.iter().filter(|a| signers.contains(a)).count() as u32;
+        if valid < threshold { return Err(Error::NotEnoughApprovals); }
+
+        env.storage().temporary().remove(&key);
+        Ok(())
+    }
+
+    pub(crate) fn action_hash(env: &Env, action: &str, args: Vec<Val>) -> BytesN<32> {
+        let mut preimage: Vec<Val> = vec![env, Symbol::new(env, action).into_val(env)];
+        preimage.append(&args);
+        env.crypto().keccak256(&preimage.to_xdr(env)).into()
+    }
+}
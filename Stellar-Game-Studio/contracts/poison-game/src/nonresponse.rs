@@ -0,0 +1,90 @@
+//! # Non-response forfeits
+//!
+//! A defender who knows the next proof gives away their last special could
+//! simply never answer. With `config.response_window > 0` the attacker can
+//! `claim_nonresponse` once that many ledgers pass without an answer to a
+//! pending attack or scan: the defender forfeits the game. In simultaneous
+//! sessions the window covers each round step instead: a player who has
+//! committed, revealed or answered can claim once the window passes with
+//! the opponent's half of the step still missing.
+//!
+//! On top of the forfeited wager, `config.slash_bps` of each player's stake
+//! is bonded from their free balance when they commit a board (via
+//! `lock_points`, or a transfer of their wager token in token sessions).
+//! Bonds go back to their owners whenever the session settles, except a
+//! forfeiting defender's, which is paid to the attacker.
+
+use soroban_sdk::{contractimpl, vec, Env};
+
+use crate::{Error, GameState, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::MAX_BPS;
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Attacker (or, in a simultaneous round, the player who acted) takes the
+    /// game after the opponent let the response window lapse.
+    pub fn claim_nonresponse(env: Env, session_id: u32) -> Result<(), Error> {
+        Self::check_operational(&env)?;
+
+        let mut game = Self::get_game(env.clone(), session_id)?;
+        if game.phase != Phase::Playing { return Err(Error::WrongPhase); }
+        // In a simultaneous round the claimant is whoever already acted this step
+        let claimant_num = if game.config.simultaneous {
+            match game.round.done {
+                0b01 => 1,
+                0b10 => 2,
+                _    => return Err(Error::WrongPhase),
+            }
+        } else if game.pending_tiles.is_empty() && game.pending_scan.is_none() {
+            return Err(Error::WrongPhase);
+        } else {
+            game.current_turn
+        };
+        if game.config.response_window == 0 { return Err(Error::NoTimeControl); }
+
+        let claimant = if claimant_num == 1 { game.player1.clone() } else { game.player2.clone() };
+        claimant.require_auth();
+
+        let deadline = game.last_action_ledger.saturating_add(game.config.response_window);
+        if env.ledger().sequence() <= deadline { return Err(Error::ClockNotExpired); }
+
+        game.forfeited     = 3 - claimant_num;
+        game.pending_tiles = vec![&env];
+        game.pending_scan  = None;
+        Self::finish_game(&env, session_id, &mut game, claimant_num == 1)?;
+        Self::save_game(&env, session_id, &game);
+        Ok(())
+    }
+}
+
+impl PoisonGameContract {
+    /// Bond `slot` posts at commit: `slash_bps` of their stake.
+    fn response_bond(game: &GameState, slot: u32) -> Result<i128, Error> {
+        let points = if slot == 1 { game.player1_points } else { game.player2_points };
+        points.checked_mul(game.config.slash_bps as i128)
+            .map(|p| p / MAX_BPS as i128)
+            .ok_or(Error::StakeOverflow)
+    }
+
+    pub(crate) fn lock_response_bond(env: &Env, game: &GameState, slot: u32) -> Result<(), Error> {
+        let bond = Self::response_bond(game, slot)?;
+        if bond > 0 { Self::escrow_in(env, game, slot, bond)?; }
+        Ok(())
+    }
+
+    /// Return each committed player's bond, or hand a forfeiting defender's
+    /// to their opponent. Runs once, from settlement.
+    pub(crate) fn release_response_bonds(env: &Env, game: &GameState) -> Result<(), Error> {
+        if game.config.slash_bps == 0 { return Ok(()); }
+
+        for (slot, committed) in [(1u32, game.player1_committed), (2u32, game.player2_committed)] {
+            let bond = Self::response_bond(game, slot)?;
+            if !committed || bond == 0 { continue; }
+            let to_slot = if game.forfeited == slot { 3 - slot } else { slot };
+            let to = if to_slot == 1 { &game.player1 } else { &game.player2 };
+            Self::escrow_out(env, game, slot, to, bond)?;
+        }
+        Ok(())
+    }
+}
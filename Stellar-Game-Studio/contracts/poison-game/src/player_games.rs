@@ -0,0 +1,71 @@
+//! # Sessions by player
+//!
+//! Every session a player takes part in is appended to a per-player index
+//! when it is stored, whichever path opened it (direct start, lobby, series
+//! leg, rematch). Like the results archive it is kept in fixed-size
+//! persistent pages. Statuses are not stored: `get_games_by_player` reads
+//! each session's `SessionStatus` at query time, so finishing or cancelling
+//! a game needs no index update and expired sessions still resolve from
+//! their receipt or archived result.
+
+use soroban_sdk::{contractimpl, contracttype, vec, Address, Env, Vec};
+
+use crate::{DataKey, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient, SessionStatus};
+use crate::{MAX_PAGE_SIZE, PLAYER_TTL_LEDGERS};
+
+/// Session ids per `PlayerGames` page.
+const GAMES_PAGE_SIZE: u32 = 20;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlayerSession {
+    pub session_id: u32,
+    pub status:     SessionStatus,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Up to `limit` of `player`'s sessions from position `cursor` on,
+    /// oldest first. The next cursor is `cursor` plus the number returned.
+    pub fn get_games_by_player(env: Env, player: Address, cursor: u32, limit: u32) -> Vec<PlayerSession> {
+        let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(Self::get_game_count(env.clone(), player.clone()));
+        let mut out = Vec::new(&env);
+        let mut page_no = u32::MAX;
+        let mut page: Vec<u32> = vec![&env];
+        for i in cursor..end {
+            if i / GAMES_PAGE_SIZE != page_no {
+                page_no = i / GAMES_PAGE_SIZE;
+                page = env.storage().persistent()
+                    .get(&DataKey::PlayerGames(player.clone(), page_no)).unwrap_or(vec![&env]);
+            }
+            if let Some(session_id) = page.get(i % GAMES_PAGE_SIZE) {
+                let status = Self::session_status(env.clone(), session_id);
+                out.push_back(PlayerSession { session_id, status });
+            }
+        }
+        out
+    }
+
+    pub fn get_game_count(env: Env, player: Address) -> u32 {
+        env.storage().persistent()
+            .get(&DataKey::PlayerGameCount(player)).unwrap_or(0)
+    }
+}
+
+impl PoisonGameContract {
+    /// Called from `store_new_game` for both players.
+    pub(crate) fn index_game(env: &Env, player: &Address, session_id: u32) {
+        let count = Self::get_game_count(env.clone(), player.clone());
+        let page_key = DataKey::PlayerGames(player.clone(), count / GAMES_PAGE_SIZE);
+        let mut ids: Vec<u32> = env.storage().persistent().get(&page_key).unwrap_or(vec![env]);
+        ids.push_back(session_id);
+        env.storage().persistent().set(&page_key, &ids);
+        env.storage().persistent().extend_ttl(&page_key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+
+        let count_key = DataKey::PlayerGameCount(player.clone());
+        env.storage().persistent().set(&count_key, &(count + 1));
+        env.storage().persistent().extend_ttl(&count_key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+    }
+}
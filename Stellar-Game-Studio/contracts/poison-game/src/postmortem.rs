@@ -0,0 +1,74 @@
+//! # Post-mortem
+//!
+//! `get_postmortem` compiles a finished game's move log into the summary a
+//! results screen needs: how the game ended, the reveal that decided it,
+//! the order in which specials surfaced and a few timing figures. It reads
+//! the same temporary entries as the live game, so it is available until
+//! the session's TTL lapses.
+
+use soroban_sdk::{contractimpl, contracttype, Env, Vec};
+
+use crate::{Error, MoveRecord, Outcome, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Postmortem {
+    pub session_id:       u32,
+    pub winner:           u32, // 1=player1 2=player2
+    pub outcome:          Outcome,
+    pub decisive_turn:    u32, // move number of the reveal that completed the set; 0 on flag fall or forfeit
+    pub specials:         Vec<MoveRecord>, // every Poison/Shield reveal, in order
+    pub total_reveals:    u32,
+    pub player1_reveals:  u32, // reveals made by player1 attacking
+    pub player2_reveals:  u32,
+    pub duration_ledgers: u32, // first reveal to last reveal
+    pub longest_gap:      u32, // most ledgers between two consecutive reveals
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    pub fn get_postmortem(env: Env, session_id: u32) -> Result<Postmortem, Error> {
+        let game = Self::get_game(env.clone(), session_id)?;
+        if game.phase != Phase::Finished { return Err(Error::WrongPhase); }
+
+        let outcome = Self::outcome_of(&game);
+
+        let log = Self::get_move_log(env.clone(), session_id);
+        let mut specials = Vec::new(&env);
+        let mut decisive_turn = 0;
+        let (mut player1_reveals, mut player2_reveals, mut longest_gap) = (0u32, 0u32, 0u32);
+        let mut prev_ledger: Option<u32> = None;
+
+        for record in log.iter() {
+            if record.attacker == 1 { player1_reveals += 1; } else { player2_reveals += 1; }
+            if let Some(prev) = prev_ledger {
+                longest_gap = longest_gap.max(record.ledger - prev);
+            }
+            prev_ledger = Some(record.ledger);
+            if record.tile_type != 1 && record.tile_type != 2 { continue; }
+            if outcome == Outcome::AllSpecialsFound && record.attacker == game.winner {
+                decisive_turn = record.turn;
+            }
+            specials.push_back(record);
+        }
+
+        let duration_ledgers = match (log.first(), log.last()) {
+            (Some(first), Some(last)) => last.ledger - first.ledger,
+            _                         => 0,
+        };
+
+        Ok(Postmortem {
+            session_id,
+            winner: game.winner,
+            outcome,
+            decisive_turn,
+            specials,
+            total_reveals: log.len(),
+            player1_reveals,
+            player2_reveals,
+            duration_ledgers,
+            longest_gap,
+        })
+    }
+}
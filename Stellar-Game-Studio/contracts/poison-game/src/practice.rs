@@ -0,0 +1,155 @@
+//! # Practice against the house
+//!
+//! A single-player game against a house board the operator commits to
+//! with `set_house_board`; each call opens a new house round. No wager is
+//! locked: the player attacks one tile at a time, the operator answers
+//! with a reveal proof against the house commitment exactly as a defender
+//! would, and the game is over once every special has been found.
+//!
+//! Unlike the tutorial's scripted board, the house board stays hidden
+//! while it is in play. Once a round has been replaced the operator
+//! publishes its tiles and salt with `reveal_house_board`, so players can
+//! check the commitment off-chain (see `board_reveal.rs`). The house
+//! commitment binds this contract's address and the house round in place
+//! of a seat and session id.
+
+use soroban_sdk::{contractimpl, contracttype, Address, Bytes, BytesN, Env, Vec};
+
+use crate::{commitment_inputs, u32_field, BoardMask, BoardReveal, DataKey, Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient, RevealCircuit};
+use crate::{DEFAULT_POISON, DEFAULT_SHIELD, GAME_TTL_LEDGERS, PLAYER_TTL_LEDGERS, PUB_INPUT_BYTES, TOTAL_TILES};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HouseBoard {
+    pub round:      u32,
+    pub commitment: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PracticeGame {
+    pub round:      u32,
+    pub commitment: BytesN<32>,
+    pub vk_version: u32,
+    pub revealed:   BoardMask,
+    pub pending:    Option<u32>, // attacked tile awaiting the house's answer
+    pub moves:      u32,
+    pub finished:   bool,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Commit to a new house board, opening the next house round.
+    pub fn set_house_board(env: Env, commitment: BytesN<32>) -> Result<u32, Error> {
+        Self::require_admin(&env)?;
+        let round = Self::get_house_board(env.clone()).map_or(1, |h| h.round + 1);
+        // Kept per round so the board can be revealed after it is replaced
+        let key = DataKey::HouseCommitment(round);
+        env.storage().persistent().set(&key, &commitment);
+        env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        env.storage().instance().set(&DataKey::HouseBoard, &HouseBoard { round, commitment });
+        Ok(round)
+    }
+
+    pub fn get_house_board(env: Env) -> Option<HouseBoard> {
+        env.storage().instance().get(&DataKey::HouseBoard)
+    }
+
+    /// Start (or restart) a practice game against the current house board.
+    pub fn start_practice(env: Env, player: Address) -> Result<(), Error> {
+        player.require_auth();
+        Self::check_accepting_games(&env)?;
+        let house = Self::get_house_board(env.clone()).ok_or(Error::GameNotFound)?;
+
+        let game = PracticeGame {
+            round:      house.round,
+            commitment: house.commitment,
+            vk_version: Self::get_vk_version(env.clone()),
+            revealed:   BoardMask::new(),
+            pending:    None,
+            moves:      0,
+            finished:   false,
+        };
+        Self::save_practice(&env, &player, &game);
+        Ok(())
+    }
+
+    pub fn practice_attack(env: Env, player: Address, tile_index: u32) -> Result<(), Error> {
+        player.require_auth();
+        let mut game = Self::get_practice(env.clone(), player.clone()).ok_or(Error::GameNotFound)?;
+        if game.finished                         { return Err(Error::GameAlreadyEnded);    }
+        if game.pending.is_some()                { return Err(Error::WrongPhase);          }
+        if tile_index >= TOTAL_TILES             { return Err(Error::InvalidTileIndex);    }
+        if game.revealed.is_revealed(tile_index) { return Err(Error::TileAlreadyRevealed); }
+
+        game.pending = Some(tile_index);
+        game.moves += 1;
+        Self::save_practice(&env, &player, &game);
+        Ok(())
+    }
+
+    /// The house answers `player`'s pending attack with a reveal proof.
+    pub fn answer_practice(env: Env, player: Address, tile_type: u32, proof_blob: Bytes) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        let mut game = Self::get_practice(env.clone(), player.clone()).ok_or(Error::GameNotFound)?;
+        let tile_index = game.pending.ok_or(Error::WrongPhase)?;
+        if tile_type > 2 { return Err(Error::InvalidProof); }
+        Self::check_verify_budget(&env, 1)?;
+
+        // The house board is bound to this contract and its house round
+        let mut pub_inputs = commitment_inputs(&env, &game.commitment, &env.current_contract_address(), game.round);
+        pub_inputs.append(&u32_field(&env, tile_index));
+        pub_inputs.append(&u32_field(&env, tile_type));
+        pub_inputs.append(&u32_field(&env, DEFAULT_POISON));
+        pub_inputs.append(&u32_field(&env, DEFAULT_SHIELD));
+        if pub_inputs.len() != PUB_INPUT_BYTES { return Err(Error::InternalInvariant); }
+        Self::verify_reveal(&env, game.vk_version, RevealCircuit::Main, &proof_blob, &pub_inputs)?;
+
+        game.revealed.reveal(tile_index, tile_type);
+        game.pending = None;
+        let (poison, shield) = game.revealed.specials();
+        game.finished = poison >= DEFAULT_POISON && shield >= DEFAULT_SHIELD;
+        Self::save_practice(&env, &player, &game);
+        Ok(())
+    }
+
+    pub fn get_practice(env: Env, player: Address) -> Option<PracticeGame> {
+        env.storage().temporary().get(&DataKey::Practice(player))
+    }
+
+    /// Publish a retired house round's board and salt.
+    pub fn reveal_house_board(env: Env, round: u32, tiles: Vec<u32>, salt: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        let current = Self::get_house_board(env.clone()).ok_or(Error::GameNotFound)?;
+        if round == 0 || round >= current.round { return Err(Error::WrongPhase); }
+        let key = DataKey::HouseReveal(round);
+        if env.storage().persistent().has(&key) { return Err(Error::AlreadyCommitted); }
+
+        if tiles.len() != TOTAL_TILES { return Err(Error::BoardMismatch); }
+        let mut counts = [0u32; 3];
+        for t in tiles.iter() {
+            if t > 2 { return Err(Error::BoardMismatch); }
+            counts[t as usize] += 1;
+        }
+        if counts[1] != DEFAULT_POISON || counts[2] != DEFAULT_SHIELD { return Err(Error::BoardMismatch); }
+
+        let commitment = env.storage().persistent().get(&DataKey::HouseCommitment(round))
+            .ok_or(Error::InternalInvariant)?;
+        env.storage().persistent().set(&key, &BoardReveal { tiles, salt, commitment });
+        env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        Ok(())
+    }
+
+    pub fn get_house_reveal(env: Env, round: u32) -> Option<BoardReveal> {
+        env.storage().persistent().get(&DataKey::HouseReveal(round))
+    }
+}
+
+impl PoisonGameContract {
+    fn save_practice(env: &Env, player: &Address, game: &PracticeGame) {
+        let key = DataKey::Practice(player.clone());
+        env.storage().temporary().set(&key, game);
+        env.storage().temporary().extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+    }
+}
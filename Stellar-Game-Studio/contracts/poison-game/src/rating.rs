@@ -0,0 +1,168 @@
+//! # Elo ratings
+//!
+//! A persistent rating per address, updated by `finish_game` for both
+//! players and by `finish_draw`, which scores half a point each. Expected
+//! scores come from a lookup table (per mille, 25-point steps, linearly
+//! interpolated) instead of floating point, which the contract cannot use.
+//! Friendly games, with nothing staked, leave ratings alone so free games
+//! cannot farm the rating that scales a player's stake cap (`limits.rs`).
+//!
+//! Optional inactivity decay (`set_rating_decay`, council-gated) lowers a
+//! rating by `points_per_period` for every full `period_ledgers` a player
+//! has gone unrated beyond `grace_ledgers`, never below `floor`. It is
+//! applied lazily: at the player's next rated game, or by anyone calling
+//! `apply_decay`.
+//!
+//! Ratings are those of the current season (`season.rs`); a player not yet
+//! rated this season starts from `DEFAULT_RATING` and owes no decay.
+
+use soroban_sdk::{contractimpl, contracttype, vec, Address, Env, IntoVal, Vec};
+
+use crate::{DataKey, Error, GameState, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::PLAYER_TTL_LEDGERS;
+
+pub(crate) const DEFAULT_RATING: u32 = 1200;
+const ELO_K: i64 = 32;
+
+/// 1000 / (1 + 10^(-d/400)) for d = 0, 25, …, 800.
+const EXPECTED_PER_MILLE: [i64; 33] = [
+    500, 536, 571, 606, 640, 673, 703, 733, 760, 785, 808,
+    830, 849, 867, 882, 896, 909, 920, 930, 939, 947, 954,
+    960, 965, 969, 973, 977, 980, 983, 985, 987, 989, 990,
+];
+
+/// Expected score (per mille) of a player rated `diff` points above the opponent.
+fn expected_per_mille(diff: i64) -> i64 {
+    let d = diff.abs().min(800);
+    let i = (d / 25) as usize;
+    let mut e = EXPECTED_PER_MILLE[i];
+    if i + 1 < EXPECTED_PER_MILLE.len() {
+        e += (EXPECTED_PER_MILLE[i + 1] - e) * (d % 25) / 25;
+    }
+    if diff < 0 { 1000 - e } else { e }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RatingDecay {
+    pub grace_ledgers:     u32,
+    pub period_ledgers:    u32,
+    pub points_per_period: u32,
+    pub floor:             u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Enable (Some) or disable (None) inactivity decay.
+    pub fn set_rating_decay(env: Env, decay: Option<RatingDecay>) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_rating_decay", vec![&env, decay.clone().into_val(&env)])?;
+        match decay {
+            Some(d) => {
+                if d.period_ledgers == 0 { return Err(Error::InvalidConfig); }
+                env.storage().instance().set(&DataKey::RatingDecay, &d);
+            }
+            None => env.storage().instance().remove(&DataKey::RatingDecay),
+        }
+        Ok(())
+    }
+
+    pub fn get_rating_decay(env: Env) -> Option<RatingDecay> {
+        env.storage().instance().get(&DataKey::RatingDecay)
+    }
+
+    /// Bring `player`'s rating up to date with any decay owed. Returns the
+    /// resulting rating.
+    pub fn apply_decay(env: Env, player: Address) -> u32 {
+        let rating = Self::get_rating(env.clone(), player.clone());
+        let season = Self::get_season(env.clone());
+        if !env.storage().persistent().has(&Self::rating_key(season, player.clone())) { return rating; }
+        let decay = match Self::get_rating_decay(env.clone()) {
+            Some(d) => d,
+            None    => return rating,
+        };
+        let key = DataKey::LastRated(player.clone());
+        let last: u32 = match env.storage().persistent().get(&key) {
+            Some(l) => l,
+            None    => return rating,
+        };
+
+        let idle = env.ledger().sequence().saturating_sub(last);
+        if idle <= decay.grace_ledgers { return rating; }
+        let periods = (idle - decay.grace_ledgers) / decay.period_ledgers;
+        if periods == 0 { return rating; }
+
+        let decayed = rating
+            .saturating_sub(periods.saturating_mul(decay.points_per_period))
+            .max(decay.floor.min(rating));
+        Self::set_rating(&env, &player, decayed);
+        // Carry the partial period forward so it is not charged twice
+        Self::set_last_rated(&env, &player, last + periods * decay.period_ledgers);
+        decayed
+    }
+
+    /// Live rating in the current season.
+    pub fn get_rating(env: Env, player: Address) -> u32 {
+        let season = Self::get_season(env.clone());
+        Self::get_season_rating(env, player, season)
+    }
+
+    pub fn get_ratings(env: Env, players: Vec<Address>) -> Vec<u32> {
+        let mut out = Vec::new(&env);
+        for p in players.iter() {
+            out.push_back(Self::get_rating(env.clone(), p));
+        }
+        out
+    }
+}
+
+impl PoisonGameContract {
+    /// Apply one decisive result to both players' ratings.
+    pub(crate) fn update_ratings(env: &Env, game: &GameState, winner: &Address, loser: &Address) {
+        if Self::is_friendly(game) { return; }
+        Self::apply_decay(env.clone(), winner.clone());
+        Self::apply_decay(env.clone(), loser.clone());
+        let rw = Self::get_rating(env.clone(), winner.clone()) as i64;
+        let rl = Self::get_rating(env.clone(), loser.clone()) as i64;
+
+        let delta = ELO_K * (1000 - expected_per_mille(rw - rl)) / 1000;
+
+        Self::set_rating(env, winner, (rw + delta) as u32);
+        Self::set_rating(env, loser, (rl - delta).max(0) as u32);
+
+        let now = env.ledger().sequence();
+        Self::set_last_rated(env, winner, now);
+        Self::set_last_rated(env, loser, now);
+    }
+
+    /// Apply one drawn result: each player scores half a point.
+    pub(crate) fn update_draw_ratings(env: &Env, game: &GameState) {
+        if Self::is_friendly(game) { return; }
+        let (player1, player2) = (&game.player1, &game.player2);
+        Self::apply_decay(env.clone(), player1.clone());
+        Self::apply_decay(env.clone(), player2.clone());
+        let r1 = Self::get_rating(env.clone(), player1.clone()) as i64;
+        let r2 = Self::get_rating(env.clone(), player2.clone()) as i64;
+
+        let delta = ELO_K * (500 - expected_per_mille(r1 - r2)) / 1000;
+
+        Self::set_rating(env, player1, (r1 + delta).max(0) as u32);
+        Self::set_rating(env, player2, (r2 - delta).max(0) as u32);
+
+        let now = env.ledger().sequence();
+        Self::set_last_rated(env, player1, now);
+        Self::set_last_rated(env, player2, now);
+    }
+
+    fn set_last_rated(env: &Env, player: &Address, ledger: u32) {
+        let key = DataKey::LastRated(player.clone());
+        env.storage().persistent().set(&key, &ledger);
+        env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+    }
+
+    fn set_rating(env: &Env, player: &Address, rating: u32) {
+        let key = Self::rating_key(Self::get_season(env.clone()), player.clone());
+        env.storage().persistent().set(&key, &rating);
+        env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+    }
+}
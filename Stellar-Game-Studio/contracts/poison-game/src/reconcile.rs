@@ -0,0 +1,90 @@
+//! # Escrow reconciliation
+//!
+//! The GameHub exposes no escrow getters, so drift between this contract
+//! and the hub shows up as inconsistencies between our own records: the
+//! live `GameState`, its `SettlementReceipt` and the archived `GameResult`.
+//! `reconcile` derives the escrow state each session should be in from
+//! those records and reports every session whose records disagree —
+//! typically after an incident, a failed hub call or an upgrade.
+
+use soroban_sdk::{contractimpl, contracttype, Env, Vec};
+
+use crate::{Error, GameState, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::{SettlementKind, MAX_PAGE_SIZE};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum EscrowState {
+    Unknown  = 0, // no live game, receipt or result (expired or never opened)
+    Locked   = 1, // stakes should still be held by the hub
+    PaidOut  = 2,
+    Refunded = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Drift {
+    FinishedUnsettled = 0, // game has a winner but was never paid out
+    MissingReceipt    = 1, // game is marked settled but no receipt exists
+    UnsettledReceipt  = 2, // receipt exists for a game still holding stakes
+    WinnerMismatch    = 3, // receipt winner differs from the game/result winner
+    ResultNotPaid     = 4, // archived result without a Win receipt
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Discrepancy {
+    pub session_id: u32,
+    pub expected:   EscrowState,
+    pub drift:      Drift,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Cross-check up to `MAX_PAGE_SIZE` sessions; returns only the ones
+    /// whose records disagree. Read-only.
+    pub fn reconcile(env: Env, session_ids: Vec<u32>) -> Result<Vec<Discrepancy>, Error> {
+        if session_ids.len() > MAX_PAGE_SIZE { return Err(Error::InsufficientResources); }
+
+        let mut out = Vec::new(&env);
+        for session_id in session_ids.iter() {
+            let game    = Self::get_game(env.clone(), session_id).ok();
+            let receipt = Self::get_settlement_receipt(env.clone(), session_id);
+            let result  = Self::get_result(env.clone(), session_id);
+
+            let expected = match (&receipt, &game) {
+                (Some(r), _) if r.kind == SettlementKind::Win => EscrowState::PaidOut,
+                (Some(_), _)                                  => EscrowState::Refunded,
+                (None, Some(g)) if !g.settled                 => EscrowState::Locked,
+                _                                             => EscrowState::Unknown,
+            };
+
+            let drift = match (&game, &receipt) {
+                (Some(g), None) if g.settled                  => Some(Drift::MissingReceipt),
+                (Some(g), None) if g.finalize_after > 0       => None, // held in its dispute window
+                (Some(g), None) if Self::is_decided(g)        => Some(Drift::FinishedUnsettled),
+                (Some(g), Some(_)) if !g.settled              => Some(Drift::UnsettledReceipt),
+                (Some(g), Some(r)) if Self::is_decided(g) && r.winner != g.winner => Some(Drift::WinnerMismatch),
+                _ => match (&result, &receipt) {
+                    (Some(_), None)                           => Some(Drift::ResultNotPaid),
+                    (Some(res), Some(r)) if r.winner != res.winner => Some(Drift::WinnerMismatch),
+                    _                                         => None,
+                },
+            };
+
+            if let Some(drift) = drift {
+                out.push_back(Discrepancy { session_id, expected, drift });
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl PoisonGameContract {
+    fn is_decided(game: &GameState) -> bool {
+        game.phase == Phase::Finished && game.winner != 0
+    }
+}
@@ -0,0 +1,98 @@
+//! # Referral rewards
+//!
+//! A player may name the address that referred them, once, with
+//! `register_referral`. From then on, every raked session they finish
+//! credits their referrer `referral_bps` of that session's protocol fee;
+//! the credit comes out of the fee before it accrues to the council's
+//! balance, so a session with two referred players pays both referrers. The
+//! rate is capped at half the fee for that reason.
+//!
+//! Credits are kept per referrer and paid out in GameHub points by
+//! `claim_referral_rewards`. Only sessions that are raked pay anything;
+//! see `treasury.rs`.
+
+use soroban_sdk::{contractimpl, vec, Address, Env, IntoVal};
+
+use crate::{DataKey, Error, GameState, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::{MAX_BPS, PLAYER_TTL_LEDGERS};
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Record `referrer` as the address that brought `new_player` in.
+    pub fn register_referral(env: Env, new_player: Address, referrer: Address) -> Result<(), Error> {
+        new_player.require_auth();
+        if new_player == referrer { return Err(Error::SelfPlay); }
+
+        let key = DataKey::Referrer(new_player);
+        if env.storage().persistent().has(&key) { return Err(Error::AlreadyReferred); }
+        env.storage().persistent().set(&key, &referrer);
+        env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        Ok(())
+    }
+
+    pub fn get_referrer(env: Env, player: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Referrer(player))
+    }
+
+    /// Referrers' share of each referred player's session fee.
+    pub fn set_referral_bps(env: Env, referral_bps: u32) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_referral_bps", vec![&env, referral_bps.into_val(&env)])?;
+        if referral_bps > MAX_BPS / 2 { return Err(Error::InvalidConfig); }
+        env.storage().instance().set(&DataKey::ReferralBps, &referral_bps);
+        Ok(())
+    }
+
+    pub fn get_referral_bps(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::ReferralBps).unwrap_or(0)
+    }
+
+    pub fn get_referral_rewards(env: Env, referrer: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::ReferralRewards(referrer)).unwrap_or(0)
+    }
+
+    /// Pay out everything credited to `referrer`; returns the amount paid.
+    pub fn claim_referral_rewards(env: Env, referrer: Address) -> Result<i128, Error> {
+        referrer.require_auth();
+        Self::check_operational(&env)?;
+
+        let points = Self::get_referral_rewards(env.clone(), referrer.clone());
+        if points == 0 { return Ok(0); }
+        let pool = Self::pool(&env)?;
+        env.storage().persistent().remove(&DataKey::ReferralRewards(referrer.clone()));
+        Self::add_referral_owed(&env, -points);
+        pool.release_points(&env.current_contract_address(), &referrer, &points);
+        Ok(points)
+    }
+}
+
+impl PoisonGameContract {
+    /// Credit the referrers of `game`'s players their share of `fee`;
+    /// returns the total credited.
+    pub(crate) fn credit_referrers(env: &Env, game: &GameState, fee: i128) -> Result<i128, Error> {
+        let share = fee.checked_mul(Self::get_referral_bps(env.clone()) as i128)
+            .ok_or(Error::StakeOverflow)? / MAX_BPS as i128;
+        if share == 0 { return Ok(0); }
+
+        let mut credited = 0;
+        for player in [&game.player1, &game.player2] {
+            let referrer = match Self::get_referrer(env.clone(), player.clone()) {
+                Some(r) => r,
+                None    => continue,
+            };
+            let key = DataKey::ReferralRewards(referrer.clone());
+            let rewards = Self::get_referral_rewards(env.clone(), referrer) + share;
+            env.storage().persistent().set(&key, &rewards);
+            env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+            credited += share;
+        }
+        Self::add_referral_owed(env, credited);
+        Ok(credited)
+    }
+
+    /// Running total of unclaimed referral credit, part of the pooled points.
+    fn add_referral_owed(env: &Env, delta: i128) {
+        let owed: i128 = env.storage().instance().get(&DataKey::ReferralOwed).unwrap_or(0);
+        env.storage().instance().set(&DataKey::ReferralOwed, &(owed + delta));
+    }
+}
@@ -0,0 +1,59 @@
+//! # Relayed moves
+//!
+//! Web clients without XLM can play through a sponsor: the player signs a
+//! move and any relayer submits it and pays the fee. The player's
+//! authorization covers exactly `(session_id, move_number, move)`, where
+//! `move_number` must equal the game's current `move_count`, so a signed
+//! move is only ever valid at the point in the game it was signed for and
+//! cannot be replayed, reordered or reused in another session. A relayer
+//! can delay or drop a move, but never alter one.
+//!
+//! Apart from the authorization the moves are exactly `attack_salvo` and
+//! `respond_batch`.
+
+use soroban_sdk::{contractimpl, vec, Address, Bytes, Env, IntoVal, Vec};
+
+use crate::{Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Relayed `attack_salvo`; `attacker` signs the session id, move number and tiles.
+    pub fn relay_attack(
+        env:         Env,
+        session_id:  u32,
+        attacker:    Address,
+        move_number: u32,
+        tiles:       Vec<u32>,
+    ) -> Result<(), Error> {
+        attacker.require_auth_for_args(vec![
+            &env, session_id.into_val(&env), move_number.into_val(&env), tiles.clone().into_val(&env),
+        ]);
+        Self::check_move_number(&env, session_id, move_number)?;
+        Self::place_attacks(&env, session_id, attacker, tiles)
+    }
+
+    /// Relayed `respond_batch`; `defender` signs the session id, move number
+    /// and every `(tile_type, proof_blob)` answer.
+    pub fn relay_respond(
+        env:         Env,
+        session_id:  u32,
+        defender:    Address,
+        move_number: u32,
+        responses:   Vec<(u32, Bytes)>,
+    ) -> Result<(), Error> {
+        defender.require_auth_for_args(vec![
+            &env, session_id.into_val(&env), move_number.into_val(&env), responses.clone().into_val(&env),
+        ]);
+        Self::check_move_number(&env, session_id, move_number)?;
+        Self::answer_attacks(&env, session_id, defender, responses)
+    }
+}
+
+impl PoisonGameContract {
+    fn check_move_number(env: &Env, session_id: u32, move_number: u32) -> Result<(), Error> {
+        let game = Self::get_game(env.clone(), session_id)?;
+        if game.move_count != move_number { return Err(Error::StaleMove); }
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+//! # Move log
+//!
+//! Every answered attack is appended to a per-session `MoveRecord` log kept
+//! next to the game entry, so replay and analysis clients can rebuild a
+//! match in order without scanning events. Each record carries the hash of
+//! the proof that justified the reveal.
+//!
+//! Polling clients catch up with `get_changes_since`: the live turn state
+//! plus only the log entries after the last move number they saw, a page
+//! at a time.
+
+use soroban_sdk::{contractimpl, contracttype, vec, BytesN, Env, Vec};
+
+use crate::{DataKey, Error, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::{GAME_TTL_LEDGERS, MAX_PAGE_SIZE};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MoveRecord {
+    pub turn:       u32, // the game's move number after this reveal
+    pub attacker:   u32, // 1=player1 2=player2
+    pub tile_index: u32,
+    pub tile_type:  u32,
+    pub ledger:     u32,
+    pub proof_hash: BytesN<32>,
+}
+
+/// State delta since a client's last seen move number.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameChanges {
+    pub move_count:        u32,
+    pub phase:             Phase,
+    pub current_turn:      u32,
+    pub winner:            u32,
+    pub pending_tiles:     Vec<u32>,
+    pub pending_scan:      Option<u32>,
+    pub player1_time_left: u32,
+    pub player2_time_left: u32,
+    // Log entries with `turn > since`, oldest first, at most MAX_PAGE_SIZE;
+    // when full, call again from the last entry's turn
+    pub moves:             Vec<MoveRecord>,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Ordered log of every answered attack in `session_id`.
+    pub fn get_move_log(env: Env, session_id: u32) -> Vec<MoveRecord> {
+        env.storage().temporary()
+            .get(&DataKey::MoveLog(session_id)).unwrap_or(vec![&env])
+    }
+
+    /// Everything that changed in `session_id` after move number `since`.
+    pub fn get_changes_since(env: Env, session_id: u32, since: u32) -> Result<GameChanges, Error> {
+        let game = Self::get_game(env.clone(), session_id)?;
+        let mut moves = vec![&env];
+        if since < game.move_count {
+            for record in Self::get_move_log(env.clone(), session_id).iter() {
+                if moves.len() == MAX_PAGE_SIZE { break; }
+                if record.turn > since { moves.push_back(record); }
+            }
+        }
+        let player1_time_left = Self::time_remaining(&env, &game, 1);
+        let player2_time_left = Self::time_remaining(&env, &game, 2);
+        Ok(GameChanges {
+            move_count:        game.move_count,
+            phase:             game.phase,
+            current_turn:      game.current_turn,
+            winner:            game.winner,
+            pending_tiles:     game.pending_tiles,
+            pending_scan:      game.pending_scan,
+            player1_time_left,
+            player2_time_left,
+            moves,
+        })
+    }
+}
+
+impl PoisonGameContract {
+    pub(crate) fn log_move(env: &Env, session_id: u32, record: MoveRecord) {
+        let mut log = Self::get_move_log(env.clone(), session_id);
+        log.push_back(record);
+        let key = DataKey::MoveLog(session_id);
+        env.storage().temporary().set(&key, &log);
+        env.storage().temporary().extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+    }
+}
@@ -0,0 +1,161 @@
+//! # GameHub sandbox mode
+//!
+//! Every hub call goes through `Hub`, a thin wrapper around
+//! `GameHubClient`. In sandbox mode the wrapper records each call as a
+//! `HubCall` intent instead of invoking the hub, so the contract can be
+//! demoed standalone while locking, payout and refund logic run exactly
+//! as they would against a real hub. Only the most recent
+//! `MAX_HUB_INTENTS` intents are kept.
+//!
+//! The mode can only be switched while the contract holds no points: no
+//! active session, open listing, unfinished series or staked free-for-all,
+//! no game held in its dispute window, and empty treasury, fee, jackpot and
+//! referral pools. Points are then never locked in one mode and released
+//! in the other.
+
+use soroban_sdk::{contractimpl, contracttype, vec, Address, Env, IntoVal, Vec};
+
+use crate::{DataKey, Error, GameHubClient, GameScore, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::{HUB_V2, PLAYER_TTL_LEDGERS};
+
+pub const MAX_HUB_INTENTS: u32 = 200;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HubIntent {
+    // session_id, player1, player2, player1_points, player2_points
+    StartGame(u32, Address, Address, i128, i128),
+    // session_id, player1_won
+    EndGame(u32, bool),
+    RefundGame(u32),
+    // player, points
+    LockPoints(Address, i128),
+    ReleasePoints(Address, i128),
+    // session_id, score (v2 hubs)
+    EndGameV2(u32, GameScore),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HubCall {
+    pub hub:    Address,
+    pub intent: HubIntent,
+    pub ledger: u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    pub fn set_sandbox_mode(env: Env, enabled: bool) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_sandbox_mode", vec![&env, enabled.into_val(&env)])?;
+        let escrows: u32 = env.storage().instance().get(&DataKey::OpenEscrows).unwrap_or(0);
+        if Self::get_active_game_count(env.clone()) > 0 || escrows > 0
+            || !Self::lobby(&env).is_empty() || Self::pooled_points(&env) > 0
+        {
+            return Err(Error::SessionsActive);
+        }
+        env.storage().instance().set(&DataKey::Sandbox, &enabled);
+        Ok(())
+    }
+
+    pub fn is_sandbox_mode(env: Env) -> bool {
+        env.storage().instance().get(&DataKey::Sandbox).unwrap_or(false)
+    }
+
+    /// Hub calls recorded in sandbox mode, oldest first.
+    pub fn get_hub_intents(env: Env) -> Vec<HubCall> {
+        env.storage().persistent().get(&DataKey::HubIntents).unwrap_or(vec![&env])
+    }
+}
+
+impl PoisonGameContract {
+    /// Count a series, free-for-all or held game whose points are escrowed.
+    pub(crate) fn open_escrow(env: &Env) {
+        let escrows: u32 = env.storage().instance().get(&DataKey::OpenEscrows).unwrap_or(0);
+        env.storage().instance().set(&DataKey::OpenEscrows, &(escrows + 1));
+    }
+
+    pub(crate) fn close_escrow(env: &Env) {
+        let escrows: u32 = env.storage().instance().get(&DataKey::OpenEscrows).unwrap_or(0);
+        env.storage().instance().set(&DataKey::OpenEscrows, &escrows.saturating_sub(1));
+    }
+}
+
+/// A GameHub as seen by the settlement code: live client or recorder.
+pub(crate) struct Hub<'a> {
+    env:     &'a Env,
+    client:  GameHubClient<'a>,
+    sandbox: bool,
+    version: u32,
+}
+
+impl<'a> Hub<'a> {
+    pub(crate) fn new(env: &'a Env, address: &Address) -> Self {
+        Hub {
+            env,
+            client:  GameHubClient::new(env, address),
+            sandbox: PoisonGameContract::is_sandbox_mode(env.clone()),
+            version: PoisonGameContract::get_hub_version(env.clone(), address.clone()),
+        }
+    }
+
+    pub(crate) fn start_game(
+        &self,
+        game_id:        &Address,
+        session_id:     &u32,
+        player1:        &Address,
+        player2:        &Address,
+        player1_points: &i128,
+        player2_points: &i128,
+    ) {
+        if self.sandbox {
+            self.record(HubIntent::StartGame(
+                *session_id, player1.clone(), player2.clone(), *player1_points, *player2_points,
+            ));
+        } else {
+            self.client.start_game(game_id, session_id, player1, player2, player1_points, player2_points);
+        }
+    }
+
+    /// Report a finished or drawn game in the form the hub's version takes.
+    pub(crate) fn end_game(&self, session_id: &u32, score: &GameScore) {
+        if self.version == HUB_V2 {
+            if self.sandbox { self.record(HubIntent::EndGameV2(*session_id, score.clone())); }
+            else            { self.client.end_game_v2(session_id, score); }
+        } else if score.draw {
+            self.refund_game(session_id);
+        } else {
+            let player1_won = score.winner == 1;
+            if self.sandbox { self.record(HubIntent::EndGame(*session_id, player1_won)); }
+            else            { self.client.end_game(session_id, &player1_won); }
+        }
+    }
+
+    pub(crate) fn refund_game(&self, session_id: &u32) {
+        if self.sandbox { self.record(HubIntent::RefundGame(*session_id)); }
+        else            { self.client.refund_game(session_id); }
+    }
+
+    pub(crate) fn lock_points(&self, game_id: &Address, player: &Address, points: &i128) {
+        if self.sandbox { self.record(HubIntent::LockPoints(player.clone(), *points)); }
+        else            { self.client.lock_points(game_id, player, points); }
+    }
+
+    pub(crate) fn release_points(&self, game_id: &Address, player: &Address, points: &i128) {
+        if self.sandbox { self.record(HubIntent::ReleasePoints(player.clone(), *points)); }
+        else            { self.client.release_points(game_id, player, points); }
+    }
+
+    fn record(&self, intent: HubIntent) {
+        let env = self.env;
+        let mut calls = PoisonGameContract::get_hub_intents(env.clone());
+        if calls.len() >= MAX_HUB_INTENTS { calls.pop_front(); }
+        calls.push_back(HubCall {
+            hub:    self.client.address.clone(),
+            intent,
+            ledger: env.ledger().sequence(),
+        });
+        env.storage().persistent().set(&DataKey::HubIntents, &calls);
+        env.storage().persistent().extend_ttl(&DataKey::HubIntents, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+    }
+}
@@ -0,0 +1,145 @@
+//! # Scan
+//!
+//! Once per game, instead of attacking, a player may `scan` a contiguous
+//! range of `SCAN_WIDTH` tiles on the opponent's board. The defender
+//! answers with `respond_scan`: how many special tiles (Poison or Shield)
+//! lie in the range, proven with the separate scan circuit
+//! (`circuits/poison_scan`, key installed with `set_scan_vk`) — the count
+//! is public, the positions are not. Answering passes the turn as a
+//! reveal would.
+//!
+//! Scan public inputs (224 bytes, built from on-chain state):
+//!   [0..32]    = defender's board commitment
+//!   [32..64]   = defender's board owner (address field, see `commit_board`)
+//!   [64..96]   = session_id
+//!   [96..128]  = range_start
+//!   [128..160] = specials in [range_start, range_start + SCAN_WIDTH)
+//!   [160..192] = poison_count
+//!   [192..224] = shield_count
+
+use soroban_sdk::{contractevent, contractimpl, vec, Address, Bytes, Env, IntoVal};
+use ultrahonk_soroban_verifier::PROOF_BYTES;
+
+use crate::{u32_field, CommitmentScheme, DataKey, Error, GameState, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::PUB_INPUT_BYTES;
+
+/// Tiles covered by one scan.
+pub const SCAN_WIDTH: u32 = 3;
+
+#[contractevent]
+pub struct ScanRequested {
+    #[topic]
+    pub session_id:  u32,
+    pub move_number: u32,
+    pub player_slot: u32, // scanner
+    pub range_start: u32,
+}
+
+#[contractevent]
+pub struct ScanAnswered {
+    #[topic]
+    pub session_id:  u32,
+    pub move_number: u32,
+    pub player_slot: u32, // defender
+    pub range_start: u32,
+    pub specials:    u32,
+    pub next_turn:   u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Install the scan circuit's VK.
+    pub fn set_scan_vk(env: Env, vk_bytes: Bytes) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_scan_vk", vec![&env, vk_bytes.clone().into_val(&env)])?;
+        Self::install_vk(&env, &DataKey::ScanVk, &vk_bytes)
+    }
+
+    /// Spend this game's scan on tiles `range_start .. range_start + SCAN_WIDTH`.
+    pub fn scan(env: Env, session_id: u32, attacker: Address, range_start: u32) -> Result<(), Error> {
+        attacker.require_auth();
+        Self::check_operational(&env)?;
+        if !env.storage().persistent().has(&DataKey::ScanVk) { return Err(Error::VkNotSet); }
+
+        let mut game = Self::get_game(env.clone(), session_id)?;
+        if game.phase != Phase::Playing || game.config.simultaneous      { return Err(Error::WrongPhase);       }
+        // The scan circuit only opens Pedersen commitments of decoy-free boards
+        if game.config.commitment_scheme != CommitmentScheme::Pedersen  { return Err(Error::WrongPhase);       }
+        if game.config.decoy_count > 0                                   { return Err(Error::WrongPhase);       }
+        if !game.pending_tiles.is_empty() || game.pending_scan.is_some() { return Err(Error::WrongPhase);       }
+        if range_start.saturating_add(SCAN_WIDTH) > game.board_size      { return Err(Error::InvalidTileIndex); }
+
+        let attacker_num = Self::player_slot(&game, &attacker)?;
+        if attacker_num != game.current_turn { return Err(Error::NotYourTurn); }
+        let used = if attacker_num == 1 { game.player1_scanned } else { game.player2_scanned };
+        if used { return Err(Error::ScanUsed); }
+        Self::charge_clock(&env, &mut game, attacker_num)?;
+
+        if attacker_num == 1 { game.player1_scanned = true; } else { game.player2_scanned = true; }
+        game.pending_scan = Some(range_start);
+        game.move_count += 1;
+        Self::save_game(&env, session_id, &game);
+
+        ScanRequested { session_id, move_number: game.move_count, player_slot: attacker_num, range_start }
+            .publish(&env);
+        Ok(())
+    }
+
+    /// Answer the pending scan with the number of specials in its range.
+    pub fn respond_scan(
+        env:        Env,
+        session_id: u32,
+        defender:   Address,
+        specials:   u32,
+        proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        defender.require_auth();
+        Self::check_operational(&env)?;
+
+        let mut game = Self::get_game(env.clone(), session_id)?;
+        if game.phase != Phase::Playing { return Err(Error::WrongPhase); }
+        let range_start = game.pending_scan.ok_or(Error::WrongPhase)?;
+        if specials > SCAN_WIDTH        { return Err(Error::InvalidProof); }
+
+        let defender_num = Self::player_slot(&game, &defender)?;
+        if defender_num == game.current_turn { return Err(Error::NotYourTurn); }
+        Self::charge_clock(&env, &mut game, defender_num)?;
+
+        if proof_blob.len() != PROOF_BYTES as u32 { return Err(Error::ProofWrongLength); }
+        Self::check_verify_budget(&env, 1)?;
+
+        let mut pub_inputs = Self::board_inputs(&env, &game, session_id, defender_num);
+        pub_inputs.append(&u32_field(&env, range_start));
+        pub_inputs.append(&u32_field(&env, specials));
+        pub_inputs.append(&u32_field(&env, game.config.poison_on(defender_num)));
+        pub_inputs.append(&u32_field(&env, game.config.shield_count));
+        if pub_inputs.len() != PUB_INPUT_BYTES { return Err(Error::InternalInvariant); }
+
+        Self::verify_proof(&env, &Self::load_verifier(&env, &DataKey::ScanVk)?, &proof_blob, &pub_inputs)?;
+
+        // The scan used the scanner's turn
+        game.pending_scan = None;
+        game.current_turn = defender_num;
+        game.move_count += 1;
+        Self::save_game(&env, session_id, &game);
+
+        ScanAnswered {
+            session_id,
+            move_number: game.move_count,
+            player_slot: defender_num,
+            range_start,
+            specials,
+            next_turn:   game.current_turn,
+        }
+        .publish(&env);
+        Ok(())
+    }
+}
+
+impl PoisonGameContract {
+    pub(crate) fn player_slot(game: &GameState, player: &Address) -> Result<u32, Error> {
+        if *player == game.player1      { Ok(1) }
+        else if *player == game.player2 { Ok(2) }
+        else                            { Err(Error::NotPlayer) }
+    }
+}
@@ -0,0 +1,78 @@
+//! # Seasons
+//!
+//! Ratings and win/loss records are kept per season. `start_season`
+//! (council-gated) advances the season counter; every live rating then
+//! reads as `DEFAULT_RATING` again and every record as empty, without
+//! touching per-player storage. Nothing ever writes to a past season, so
+//! its ratings and records stand as that season's final ladder, readable
+//! with `get_season_rating` and `get_season_record`.
+//!
+//! Season 0 is the ladder from before seasons existed: its ratings live
+//! under the original `DataKey::Rating` entries.
+
+use soroban_sdk::{contractevent, contractimpl, contracttype, vec, Address, Env, IntoVal};
+
+use crate::{DataKey, Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::rating::DEFAULT_RATING;
+use crate::PLAYER_TTL_LEDGERS;
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SeasonRecord {
+    pub wins:   u32,
+    pub losses: u32,
+}
+
+#[contractevent]
+pub struct SeasonStarted {
+    #[topic]
+    pub season: u32,
+    pub ledger: u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Close the current season and open the next one. Returns its number.
+    pub fn start_season(env: Env) -> Result<u32, Error> {
+        let season = Self::get_season(env.clone()) + 1;
+        Self::authorize_admin_action(&env, "start_season", vec![&env, season.into_val(&env)])?;
+
+        env.storage().instance().set(&DataKey::Season, &season);
+        SeasonStarted { season, ledger: env.ledger().sequence() }.publish(&env);
+        Ok(season)
+    }
+
+    pub fn get_season(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Season).unwrap_or(0)
+    }
+
+    /// `player`'s rating in `season`: final for past seasons, live for the current one.
+    pub fn get_season_rating(env: Env, player: Address, season: u32) -> u32 {
+        env.storage().persistent()
+            .get(&Self::rating_key(season, player)).unwrap_or(DEFAULT_RATING)
+    }
+
+    pub fn get_season_record(env: Env, player: Address, season: u32) -> SeasonRecord {
+        env.storage().persistent()
+            .get(&DataKey::SeasonRecord(season, player)).unwrap_or_default()
+    }
+}
+
+impl PoisonGameContract {
+    pub(crate) fn rating_key(season: u32, player: Address) -> DataKey {
+        if season == 0 { DataKey::Rating(player) } else { DataKey::SeasonRating(season, player) }
+    }
+
+    /// Count one decisive result in the current season's records.
+    pub(crate) fn record_season_result(env: &Env, winner: &Address, loser: &Address) {
+        let season = Self::get_season(env.clone());
+        for (player, won) in [(winner, true), (loser, false)] {
+            let mut record = Self::get_season_record(env.clone(), player.clone(), season);
+            if won { record.wins += 1; } else { record.losses += 1; }
+            let key = DataKey::SeasonRecord(season, player.clone());
+            env.storage().persistent().set(&key, &record);
+            env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        }
+    }
+}
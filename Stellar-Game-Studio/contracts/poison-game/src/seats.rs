@@ -0,0 +1,73 @@
+//! # Seat key rotation
+//!
+//! A player can move their seat in a live game to a new address by signing
+//! with the current one, so a compromised or migrated wallet does not force
+//! a forfeit. Rotations are rate-limited per seat and announced with a
+//! `SeatRotated` event so the opponent's client can follow along.
+//!
+//! Only the contract's view of the seat moves: hub escrow for a per-game
+//! stake stays credited to whichever address the hub locked it under.
+
+use soroban_sdk::{contractevent, contractimpl, vec, Address, Env, IntoVal};
+
+use crate::{DataKey, Error, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::GAME_TTL_LEDGERS;
+
+/// Ledgers a seat must wait between rotations (~1 hour).
+pub const SEAT_ROTATION_COOLDOWN: u32 = 720;
+
+#[contractevent]
+pub struct SeatRotated {
+    #[topic]
+    pub session_id:  u32,
+    pub player_slot: u32,
+    pub old_address: Address,
+    pub new_address: Address,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Hand `old_address`'s seat in `session_id` to `new_address`.
+    pub fn rotate_seat(
+        env:         Env,
+        session_id:  u32,
+        old_address: Address,
+        new_address: Address,
+    ) -> Result<(), Error> {
+        old_address.require_auth_for_args(vec![
+            &env, session_id.into_val(&env), new_address.clone().into_val(&env),
+        ]);
+
+        let mut game = Self::get_game(env.clone(), session_id)?;
+        if game.phase == Phase::Finished { return Err(Error::GameAlreadyEnded); }
+
+        let slot = if old_address == game.player1      { 1 }
+                   else if old_address == game.player2 { 2 }
+                   else                                { return Err(Error::NotPlayer); };
+        if new_address == game.player1 || new_address == game.player2 { return Err(Error::SelfPlay); }
+
+        let now = env.ledger().sequence();
+        let cooldown_key = DataKey::SeatRotation(session_id, slot);
+        let last: Option<u32> = env.storage().temporary().get(&cooldown_key);
+        if let Some(at) = last {
+            if now < at.saturating_add(SEAT_ROTATION_COOLDOWN) { return Err(Error::RotationCooldown); }
+        }
+
+        if slot == 1 { game.player1 = new_address.clone(); }
+        else         { game.player2 = new_address.clone(); }
+        Self::save_game(&env, session_id, &game);
+
+        env.storage().temporary().set(&cooldown_key, &now);
+        env.storage().temporary().extend_ttl(&cooldown_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+
+        SeatRotated {
+            session_id,
+            player_slot: slot,
+            old_address,
+            new_address,
+        }
+        .publish(&env);
+        Ok(())
+    }
+}
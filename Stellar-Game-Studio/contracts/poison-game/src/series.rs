@@ -0,0 +1,285 @@
+//! # Best-of-N series escrow
+//!
+//! The full series stake is locked once, up front, through the GameHub's
+//! extended escrow (`lock_points`). Individual games in the series carry no
+//! per-game stake; instead each finished game reports back here and points
+//! are released according to the series' `SeriesPayout` mode.
+//!
+//! A series holds escrow, so it lives in persistent storage. One nobody
+//! has touched for `SERIES_IDLE_LEDGERS` — and whose current game, if any,
+//! has itself expired — can be wound up by anyone with `abandon_series`,
+//! which returns each player's unreleased stake.
+
+use soroban_sdk::{contractimpl, contracttype, vec, Address, Env, IntoVal, Vec};
+
+use crate::{DataKey, Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::sandbox::Hub;
+use crate::{FEATURE_SERIES, GAME_TTL_LEDGERS, PLAYER_TTL_LEDGERS};
+
+/// Ledgers without activity after which a series may be abandoned; a
+/// series game expires from temporary storage after the same span.
+pub const SERIES_IDLE_LEDGERS: u32 = GAME_TTL_LEDGERS;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SeriesPayout {
+    PerGame        = 0, // each game releases its share of both stakes to that game's winner
+    WinnerTakesAll = 1, // everything is released to the series winner at the end
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Series {
+    pub player1:        Address,
+    pub player2:        Address,
+    pub best_of:        u32,
+    // Full series stake locked per player
+    pub player1_points: i128,
+    pub player2_points: i128,
+    pub payout:         SeriesPayout,
+    pub player1_wins:   u32,
+    pub player2_wins:   u32,
+    pub games:          Vec<u32>,
+    pub active_game:    Option<u32>,
+    pub finished:       bool,
+    // Broadcast production controlling pause points between games
+    pub organizer:      Option<Address>,
+    pub on_hold:        bool,
+    // Ledger of the last change to the series
+    pub last_activity:  u32,
+    // GameHub the stakes were locked on; every release goes back to it
+    pub hub:            Address,
+}
+
+impl Series {
+    fn wins_needed(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    fn games_played(&self) -> u32 {
+        self.player1_wins + self.player2_wins
+    }
+
+    /// Points still escrowed for each player once the played games are paid.
+    fn unreleased(&self) -> (i128, i128) {
+        if self.payout == SeriesPayout::WinnerTakesAll {
+            return (self.player1_points, self.player2_points);
+        }
+        let played = self.games_played() as i128;
+        (
+            self.player1_points - self.player1_points / self.best_of as i128 * played,
+            self.player2_points - self.player2_points / self.best_of as i128 * played,
+        )
+    }
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Lock both players' full series stake and open a best-of-N series.
+    /// `best_of` must be odd so the series always has a winner.
+    pub fn start_series(
+        env:            Env,
+        series_id:      u32,
+        player1:        Address,
+        player2:        Address,
+        best_of:        u32,
+        player1_points: i128,
+        player2_points: i128,
+        payout:         SeriesPayout,
+    ) -> Result<(), Error> {
+        Self::check_accepting_games(&env)?;
+        Self::check_feature(&env, FEATURE_SERIES)?;
+        if player1 == player2                        { return Err(Error::SelfPlay);      }
+        if best_of == 0 || best_of.is_multiple_of(2) { return Err(Error::InvalidBestOf); }
+        if env.storage().persistent().has(&DataKey::Series(series_id)) { return Err(Error::SeriesExists); }
+        Self::validate_stakes(player1_points, player2_points)?;
+        Self::check_stake_limits(&env, player1_points, player2_points)?;
+        Self::check_tutorial_gate(&env, &player1, &player2)?;
+        Self::check_stake_cap(&env, &player1, &player2, player1_points)?;
+        Self::check_stake_cap(&env, &player2, &player1, player2_points)?;
+
+        // Each player signs the series id, length and their own stake
+        player1.require_auth_for_args(vec![
+            &env, series_id.into_val(&env), best_of.into_val(&env), player1_points.into_val(&env),
+        ]);
+        player2.require_auth_for_args(vec![
+            &env, series_id.into_val(&env), best_of.into_val(&env), player2_points.into_val(&env),
+        ]);
+
+        let hub_addr = Self::get_hub(env.clone())?;
+        let hub      = Hub::new(&env, &hub_addr);
+        let this     = env.current_contract_address();
+        hub.lock_points(&this, &player1, &player1_points);
+        hub.lock_points(&this, &player2, &player2_points);
+
+        let mut series = Series {
+            player1,
+            player2,
+            best_of,
+            player1_points,
+            player2_points,
+            payout,
+            player1_wins: 0,
+            player2_wins: 0,
+            games:        vec![&env],
+            active_game:  None,
+            finished:     false,
+            organizer:    None,
+            on_hold:      false,
+            last_activity: 0,
+            hub:          hub_addr,
+        };
+        Self::open_escrow(&env);
+        Self::save_series(&env, series_id, &mut series);
+        Ok(())
+    }
+
+    /// Open the next game of a series. Either series player may call this;
+    /// the stakes are already escrowed so no further wager is signed.
+    pub fn start_series_game(
+        env:        Env,
+        series_id:  u32,
+        session_id: u32,
+        caller:     Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::check_operational(&env)?;
+
+        let mut series = Self::get_series(env.clone(), series_id)?;
+        if caller != series.player1 && caller != series.player2 { return Err(Error::NotPlayer);        }
+        if series.finished                                      { return Err(Error::SeriesFinished);   }
+        if series.active_game.is_some()                         { return Err(Error::SeriesGameActive); }
+        if series.on_hold                                       { return Err(Error::SeriesOnHold);     }
+        Self::check_session_unused(&env, session_id)?;
+
+        let mut game = Self::new_game(&env, series.player1.clone(), series.player2.clone(), 0, 0, series.hub.clone());
+        game.series_id = Some(series_id);
+        // Organized series are broadcast productions
+        game.config.broadcast = series.organizer.is_some();
+        Self::store_new_game(&env, session_id, &game);
+
+        series.games.push_back(session_id);
+        series.active_game = Some(session_id);
+        Self::save_series(&env, series_id, &mut series);
+        Ok(())
+    }
+
+    /// Both players hand pause-point control to a broadcast organizer.
+    pub fn set_series_organizer(env: Env, series_id: u32, organizer: Address) -> Result<(), Error> {
+        let mut series = Self::get_series(env.clone(), series_id)?;
+        series.player1.require_auth();
+        series.player2.require_auth();
+        series.organizer = Some(organizer);
+        Self::save_series(&env, series_id, &mut series);
+        Ok(())
+    }
+
+    /// Organizer holds (or releases) the series between games; no new
+    /// game can start while on hold. A game in progress is unaffected.
+    pub fn set_series_hold(env: Env, series_id: u32, hold: bool) -> Result<(), Error> {
+        let mut series = Self::get_series(env.clone(), series_id)?;
+        let organizer = series.organizer.clone().ok_or(Error::NotOrganizer)?;
+        organizer.require_auth();
+        series.on_hold = hold;
+        Self::save_series(&env, series_id, &mut series);
+        Ok(())
+    }
+
+    /// Wind up a series that has gone idle, returning each player's
+    /// unreleased stake. Anyone may call this once `SERIES_IDLE_LEDGERS`
+    /// have passed since the last activity and no series game is live.
+    pub fn abandon_series(env: Env, series_id: u32) -> Result<(), Error> {
+        let mut series = Self::get_series(env.clone(), series_id)?;
+        if series.finished { return Err(Error::SeriesFinished); }
+        if let Some(session_id) = series.active_game {
+            if Self::has_game(&env, session_id) { return Err(Error::SeriesGameActive); }
+        }
+        if env.ledger().sequence() <= series.last_activity + SERIES_IDLE_LEDGERS {
+            return Err(Error::ClockNotExpired);
+        }
+
+        let hub  = Hub::new(&env, &series.hub);
+        let this = env.current_contract_address();
+        let (p1_rest, p2_rest) = series.unreleased();
+        if p1_rest > 0 { hub.release_points(&this, &series.player1, &p1_rest); }
+        if p2_rest > 0 { hub.release_points(&this, &series.player2, &p2_rest); }
+
+        series.active_game = None;
+        series.finished    = true;
+        Self::close_escrow(&env);
+        Self::save_series(&env, series_id, &mut series);
+        Ok(())
+    }
+
+    pub fn get_series(env: Env, series_id: u32) -> Result<Series, Error> {
+        env.storage().persistent()
+            .get(&DataKey::Series(series_id)).ok_or(Error::SeriesNotFound)
+    }
+}
+
+impl PoisonGameContract {
+    /// Called from `finish_game` for series games: tally the result and
+    /// release escrowed points according to the payout mode.
+    pub(crate) fn record_series_result(
+        env:         &Env,
+        series_id:   u32,
+        player1_won: bool,
+    ) -> Result<(), Error> {
+        let mut series = Self::get_series(env.clone(), series_id)?;
+        if series.finished { return Err(Error::SeriesFinished); }
+
+        if player1_won { series.player1_wins += 1; } else { series.player2_wins += 1; }
+        series.active_game = None;
+
+        let hub    = Hub::new(env, &series.hub);
+        let this   = env.current_contract_address();
+        let winner = if player1_won { series.player1.clone() } else { series.player2.clone() };
+
+        // Per-game share of each stake; remainders are settled at series end
+        let p1_share = series.player1_points / series.best_of as i128;
+        let p2_share = series.player2_points / series.best_of as i128;
+
+        if series.payout == SeriesPayout::PerGame {
+            let pot = p1_share + p2_share;
+            if pot > 0 { hub.release_points(&this, &winner, &pot); }
+        }
+
+        let needed = series.wins_needed();
+        if series.player1_wins >= needed || series.player2_wins >= needed {
+            series.finished = true;
+            Self::close_escrow(env);
+            match series.payout {
+                SeriesPayout::WinnerTakesAll => {
+                    let pot = series.player1_points + series.player2_points;
+                    if pot > 0 { hub.release_points(&this, &winner, &pot); }
+                }
+                SeriesPayout::PerGame => {
+                    // Return each player's unplayed shares plus rounding dust
+                    let (p1_rest, p2_rest) = series.unreleased();
+                    if p1_rest > 0 { hub.release_points(&this, &series.player1, &p1_rest); }
+                    if p2_rest > 0 { hub.release_points(&this, &series.player2, &p2_rest); }
+                }
+            }
+        }
+
+        Self::save_series(env, series_id, &mut series);
+        Ok(())
+    }
+
+    /// A cancelled series game frees the slot for the next game.
+    pub(crate) fn clear_series_game(env: &Env, series_id: u32) {
+        if let Ok(mut series) = Self::get_series(env.clone(), series_id) {
+            series.active_game = None;
+            Self::save_series(env, series_id, &mut series);
+        }
+    }
+
+    fn save_series(env: &Env, series_id: u32, series: &mut Series) {
+        series.last_activity = env.ledger().sequence();
+        let key = DataKey::Series(series_id);
+        env.storage().persistent().set(&key, series);
+        env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+    }
+}
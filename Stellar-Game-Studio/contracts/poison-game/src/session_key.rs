@@ -0,0 +1,84 @@
+//! # Session keys
+//!
+//! A player can register a short-lived session key, typically a browser
+//! keypair, that signs their gameplay moves so every attack and answer
+//! does not need a wallet popup. The key is only accepted by
+//! `attack_with_session_key` and `respond_with_session_key`; resigning,
+//! staking, claims and every other entrypoint still need the player's own
+//! signature, so a leaked key can at worst play badly until it expires.
+//!
+//! One key per player. Registering again replaces it, and the player may
+//! revoke it at any time. Keys live at most `MAX_SESSION_KEY_LEDGERS`.
+
+use soroban_sdk::{contractevent, contractimpl, contracttype, vec, Address, Bytes, Env, Vec};
+
+use crate::{DataKey, Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::PLAYER_TTL_LEDGERS;
+
+pub const MAX_SESSION_KEY_LEDGERS: u32 = 120_960; // ~7 days
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionKey {
+    pub key:        Address,
+    pub expires_at: u32, // last ledger the key may sign on
+}
+
+#[contractevent]
+pub struct SessionKeySet {
+    #[topic]
+    pub player:     Address,
+    pub key:        Address,
+    pub expires_at: u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    pub fn register_session_key(env: Env, player: Address, key: Address, expires_at: u32) -> Result<(), Error> {
+        player.require_auth();
+        let now = env.ledger().sequence();
+        if expires_at <= now || expires_at - now > MAX_SESSION_KEY_LEDGERS { return Err(Error::InvalidConfig); }
+
+        let storage_key = DataKey::SessionKey(player.clone());
+        env.storage().persistent().set(&storage_key, &SessionKey { key: key.clone(), expires_at });
+        env.storage().persistent().extend_ttl(&storage_key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        SessionKeySet { player, key, expires_at }.publish(&env);
+        Ok(())
+    }
+
+    pub fn revoke_session_key(env: Env, player: Address) {
+        player.require_auth();
+        env.storage().persistent().remove(&DataKey::SessionKey(player));
+    }
+
+    pub fn get_session_key(env: Env, player: Address) -> Option<SessionKey> {
+        env.storage().persistent().get(&DataKey::SessionKey(player))
+    }
+
+    /// `attack` signed by `attacker`'s session key.
+    pub fn attack_with_session_key(env: Env, session_id: u32, attacker: Address, tile_index: u32) -> Result<(), Error> {
+        Self::require_session_key(&env, &attacker)?;
+        Self::place_attacks(&env, session_id, attacker, vec![&env, tile_index])
+    }
+
+    /// `respond_batch` signed by `defender`'s session key.
+    pub fn respond_with_session_key(
+        env:        Env,
+        session_id: u32,
+        defender:   Address,
+        responses:  Vec<(u32, Bytes)>,
+    ) -> Result<(), Error> {
+        Self::require_session_key(&env, &defender)?;
+        Self::answer_attacks(&env, session_id, defender, responses)
+    }
+}
+
+impl PoisonGameContract {
+    fn require_session_key(env: &Env, player: &Address) -> Result<(), Error> {
+        let session_key = Self::get_session_key(env.clone(), player.clone()).ok_or(Error::SessionKeyInvalid)?;
+        if env.ledger().sequence() > session_key.expires_at { return Err(Error::SessionKeyInvalid); }
+        session_key.key.require_auth();
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+//! # Stake settlement
+//!
+//! Every path that locks, pays out or refunds wagered points goes through
+//! these helpers, which pick the escrow source(s) a session was opened with:
+//!
+//! - series legs: stakes live in the series escrow (`series.rs`);
+//! - pooled (lobby and raked) sessions: stakes were locked individually
+//!   with `lock_points` and are released straight to the recipient, minus
+//!   the session's fee and jackpot slice on a win (`jackpot.rs`);
+//! - token sessions (`token_escrow.rs`): pooled, but the pool is this
+//!   contract's own balance of each `GameState.wagers` leg's token, moved
+//!   with transfers, and a win pays out both legs;
+//! - split-hub sessions: player1's stake is locked on the session's
+//!   GameHub and player2's on `config.player2_hub`, and both hubs are
+//!   settled in the same transaction so the outcome is always consistent;
+//! - friendly sessions (both stakes zero, outside a series): no escrow at
+//!   all, so the hub is never called;
+//! - everything else: one GameHub holds both stakes.
+//!
+//! The session's GameHub is `GameState.hub`, picked from the allowlist at
+//! open (`hub_allowlist.rs`), and stakes are released there even after a
+//! `set_hub`. The fee and jackpot slices go to the pool hub (`treasury.rs`),
+//! so a session on any other hub pays its full pot.
+//!
+//! Response bonds (`nonresponse.rs`) are released on both paths.
+//!
+//! A session settles at most once. `GameState.settled` is flipped before
+//! any hub call, and a persistent `SettlementReceipt` is written alongside;
+//! either one being present makes a second payout or refund fail with
+//! `AlreadySettled`, and a receipted session id cannot be opened again.
+
+use soroban_sdk::{contractimpl, contracttype, Env};
+
+use crate::{DataKey, Error, GameState, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::sandbox::Hub;
+use crate::PLAYER_TTL_LEDGERS;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SettlementKind {
+    Win    = 0,
+    Refund = 1,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementReceipt {
+    pub session_id: u32,
+    pub kind:       SettlementKind,
+    pub winner:     u32, // 1|2 for Win, 0 for Refund
+    pub ledger:     u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    pub fn get_settlement_receipt(env: Env, session_id: u32) -> Option<SettlementReceipt> {
+        env.storage().persistent().get(&DataKey::Receipt(session_id))
+    }
+}
+
+impl PoisonGameContract {
+    pub(crate) fn lock_stakes(env: &Env, session_id: u32, game: &GameState) -> Result<(), Error> {
+        if Self::is_friendly(game) { return Ok(()); }
+        let this = env.current_contract_address();
+        if game.pooled {
+            Self::escrow_in(env, game, 1, game.player1_points)?;
+            Self::escrow_in(env, game, 2, game.player2_points)?;
+            return Ok(());
+        }
+        match &game.config.player2_hub {
+            Some(hub2) => {
+                Self::game_hub(env, game).start_game(
+                    &this, &session_id, &game.player1, &game.player2, &game.player1_points, &0,
+                );
+                Hub::new(env, hub2).start_game(
+                    &this, &session_id, &game.player1, &game.player2, &0, &game.player2_points,
+                );
+            }
+            // Tell GameHub to lock both players' points into escrow
+            None => Self::game_hub(env, game).start_game(
+                &this, &session_id, &game.player1, &game.player2, &game.player1_points, &game.player2_points,
+            ),
+        }
+        Ok(())
+    }
+
+    pub(crate) fn settle_win(
+        env:         &Env,
+        session_id:  u32,
+        game:        &mut GameState,
+        player1_won: bool,
+    ) -> Result<(), Error> {
+        Self::claim_settlement(env, session_id, game, SettlementKind::Win, if player1_won { 1 } else { 2 })?;
+        Self::release_response_bonds(env, game)?;
+        Self::pay_out_win(env, session_id, game, player1_won)?;
+        Self::pay_bounty(env, session_id, game)
+    }
+
+    fn pay_out_win(env: &Env, session_id: u32, game: &GameState, player1_won: bool) -> Result<(), Error> {
+        // Series games settle against the series escrow instead
+        if let Some(series_id) = game.series_id {
+            return Self::record_series_result(env, series_id, player1_won);
+        }
+        if Self::is_friendly(game) { return Ok(()); }
+        if game.pooled {
+            let winner = if player1_won { &game.player1 } else { &game.player2 };
+            if !game.wagers.is_empty() {
+                // Legs may be in different tokens, so each is paid out on its own
+                Self::escrow_out(env, game, 1, winner, game.player1_points)?;
+                Self::escrow_out(env, game, 2, winner, game.player2_points)?;
+                return Ok(());
+            }
+            let pot    = game.player1_points + game.player2_points;
+            let fee    = Self::take_fee(env, game, pot)?;
+            let slice  = Self::take_jackpot_slice(env, game, pot)?;
+            Self::escrow_out(env, game, 1, winner, pot - fee - slice)?;
+            if game.perfect && game.jackpot_bps > 0 { Self::pay_jackpot(env, session_id, winner)?; }
+            return Ok(());
+        }
+        // Tell GameHub(s) to pay out the winner from escrow
+        let score = Self::game_score(game);
+        Self::game_hub(env, game).end_game(&session_id, &score);
+        if let Some(hub2) = &game.config.player2_hub {
+            Hub::new(env, hub2).end_game(&session_id, &score);
+        }
+        Ok(())
+    }
+
+    /// Refund a cancelled session, or a finished one that ended in a draw.
+    pub(crate) fn settle_refund(env: &Env, session_id: u32, game: &mut GameState) -> Result<(), Error> {
+        let draw = game.phase == Phase::Finished;
+        Self::refund_stakes(env, session_id, game, draw)
+    }
+
+    /// Refund a session whatever its phase, reporting it to the hub(s) as cancelled.
+    pub(crate) fn settle_cancel(env: &Env, session_id: u32, game: &mut GameState) -> Result<(), Error> {
+        Self::refund_stakes(env, session_id, game, false)
+    }
+
+    fn refund_stakes(env: &Env, session_id: u32, game: &mut GameState, draw: bool) -> Result<(), Error> {
+        Self::claim_settlement(env, session_id, game, SettlementKind::Refund, 0)?;
+        Self::release_response_bonds(env, game)?;
+        Self::deactivate(env, session_id);
+
+        // Series stakes stay in the series escrow until the series settles
+        if let Some(series_id) = game.series_id {
+            Self::clear_series_game(env, series_id);
+            return Ok(());
+        }
+        if Self::is_friendly(game) { return Ok(()); }
+        if game.pooled {
+            Self::escrow_out(env, game, 1, &game.player1, game.player1_points)?;
+            Self::escrow_out(env, game, 2, &game.player2, game.player2_points)?;
+            return Ok(());
+        }
+        // A drawn game is reported as a result, a cancelled one as a refund
+        let draw = draw.then(|| Self::game_score(game));
+        let hubs = [Some(game.hub.clone()), game.config.player2_hub.clone()];
+        for hub in hubs.iter().flatten() {
+            match &draw {
+                Some(score) => Hub::new(env, hub).end_game(&session_id, score),
+                None        => Hub::new(env, hub).refund_game(&session_id),
+            }
+        }
+        Ok(())
+    }
+
+    /// A casual session with nothing wagered and no series escrow behind it.
+    pub(crate) fn is_friendly(game: &GameState) -> bool {
+        game.series_id.is_none() && game.player1_points == 0 && game.player2_points == 0
+    }
+
+    /// Mark the session settled and write its receipt, or fail if either
+    /// already happened.
+    pub(crate) fn claim_settlement(
+        env:        &Env,
+        session_id: u32,
+        game:       &mut GameState,
+        kind:       SettlementKind,
+        winner:     u32,
+    ) -> Result<(), Error> {
+        let key = DataKey::Receipt(session_id);
+        if game.settled || env.storage().persistent().has(&key) { return Err(Error::AlreadySettled); }
+        game.settled = true;
+
+        let receipt = SettlementReceipt { session_id, kind, winner, ledger: env.ledger().sequence() };
+        env.storage().persistent().set(&key, &receipt);
+        env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        Ok(())
+    }
+}
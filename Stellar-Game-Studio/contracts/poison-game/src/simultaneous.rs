@@ -0,0 +1,227 @@
+//! # Simultaneous (double-blind) rounds
+//!
+//! With `config.simultaneous` the players do not alternate: the Playing
+//! phase runs in rounds in which both players attack at once. Each round
+//! steps through
+//!
+//!   1. `CommitAttacks` — each player commits `keccak256(tile_index ‖ salt)`
+//!      (`tile_index` as 4 big-endian bytes, a 32-byte salt), so neither
+//!      can pick their tile after seeing the other's;
+//!   2. `RevealAttacks` — each opens their commitment, naming the tile on
+//!      the opponent's board;
+//!   3. `AwaitProofs`  — each answers the tile attacked on their own board
+//!      with the usual reveal proof.
+//!
+//! The round resolves once both answers are in. If the win condition
+//! decides the game for one player they win; if it decides it for both
+//! in the same round the game is a draw and both stakes are refunded.
+//! Otherwise the next round begins.
+//!
+//! With `config.response_window > 0` every step has a deadline: once that
+//! many ledgers pass after one player's half of a step with the other's
+//! still missing, the player who acted can `claim_nonresponse` and the
+//! stalling player forfeits (`nonresponse.rs`).
+//!
+//! Turn-based rules have no meaning here, so simultaneous sessions cannot
+//! be lite, salvo, timed or penalised, and `attack`, `respond_to_attack`
+//! and `scan` are closed to them.
+
+use soroban_sdk::{contractevent, contractimpl, contracttype, vec, Address, Bytes, BytesN, Env, Vec};
+
+use crate::{u32_field, AttackAnswered, AttackMade, Error, GameConfig, GameState, MoveRecord, Outcome, Phase, RevealCircuit};
+use crate::{PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient, PUB_INPUT_BYTES};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RoundStep {
+    CommitAttacks = 0,
+    RevealAttacks = 1,
+    AwaitProofs   = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SimulRound {
+    pub step:    RoundStep,
+    pub commits: Vec<BytesN<32>>, // per slot, this round's attack commitment (empty until the first commit)
+    pub tiles:   Vec<u32>,        // per slot, the tile it attacks once revealed
+    pub done:    u32,             // bit (slot - 1) set once that slot acted in this step
+}
+
+impl SimulRound {
+    pub(crate) fn new(env: &Env) -> Self {
+        SimulRound { step: RoundStep::CommitAttacks, commits: vec![env], tiles: vec![env], done: 0 }
+    }
+}
+
+#[contractevent]
+pub struct RoundDrawn {
+    #[topic]
+    pub session_id:  u32,
+    pub move_number: u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Commit to this round's attack: `keccak256(tile_index ‖ salt)`.
+    pub fn commit_attack(env: Env, session_id: u32, player: Address, attack_hash: BytesN<32>) -> Result<(), Error> {
+        player.require_auth();
+        let (mut game, slot) = Self::load_round_step(&env, session_id, &player, RoundStep::CommitAttacks)?;
+
+        // A round's slots are only filled in once it is played
+        if game.round.commits.is_empty() {
+            let zero = BytesN::from_array(&env, &[0u8; 32]);
+            game.round.commits = vec![&env, zero.clone(), zero];
+            game.round.tiles   = vec![&env, 0, 0];
+        }
+        game.round.commits.set(slot - 1, attack_hash);
+        Self::finish_round_step(&env, session_id, &mut game, slot, RoundStep::RevealAttacks);
+        Ok(())
+    }
+
+    /// Open this round's attack commitment.
+    pub fn reveal_attack(
+        env:        Env,
+        session_id: u32,
+        player:     Address,
+        tile_index: u32,
+        salt:       BytesN<32>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        let (mut game, slot) = Self::load_round_step(&env, session_id, &player, RoundStep::RevealAttacks)?;
+
+        let mut preimage = Bytes::from_array(&env, &tile_index.to_be_bytes());
+        preimage.append(&Bytes::from(salt));
+        let digest: BytesN<32> = env.crypto().keccak256(&preimage).into();
+        if digest != game.round.commits.get_unchecked(slot - 1) { return Err(Error::AttackMismatch); }
+
+        if tile_index >= game.board_size { return Err(Error::InvalidTileIndex); }
+        let target = if slot == 1 { &game.p2_mask } else { &game.p1_mask };
+        if target.is_revealed(tile_index) { return Err(Error::TileAlreadyRevealed); }
+
+        game.round.tiles.set(slot - 1, tile_index);
+        AttackMade {
+            session_id,
+            move_number: game.move_count + 1,
+            player_slot: slot,
+            tile_index,
+            phase:       game.phase.clone(),
+        }
+        .publish(&env);
+        Self::finish_round_step(&env, session_id, &mut game, slot, RoundStep::AwaitProofs);
+        Ok(())
+    }
+
+    /// Prove the type of the tile the opponent attacked on `defender`'s board.
+    pub fn respond_round(
+        env:        Env,
+        session_id: u32,
+        defender:   Address,
+        tile_type:  u32,
+        proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        defender.require_auth();
+        let (mut game, slot) = Self::load_round_step(&env, session_id, &defender, RoundStep::AwaitProofs)?;
+        let attacker = 3 - slot;
+        let tile_index = game.round.tiles.get_unchecked(attacker - 1);
+
+        if tile_type > 3 || (tile_type == 3 && game.config.decoy_count == 0) { return Err(Error::InvalidProof); }
+        Self::check_verify_budget(&env, 1)?;
+        let mut pub_inputs = Self::board_inputs(&env, &game, session_id, slot);
+        pub_inputs.append(&u32_field(&env, tile_index));
+        pub_inputs.append(&u32_field(&env, tile_type));
+        pub_inputs.append(&u32_field(&env, game.config.poison_on(slot)));
+        pub_inputs.append(&u32_field(&env, game.config.shield_count));
+        let mut expected_len = PUB_INPUT_BYTES;
+        if game.config.decoy_count > 0 {
+            pub_inputs.append(&u32_field(&env, game.config.decoy_count));
+            expected_len += 32;
+        }
+        if pub_inputs.len() != expected_len { return Err(Error::InternalInvariant); }
+        Self::verify_reveal(&env, game.vk_version, RevealCircuit::of(&game.config), &proof_blob, &pub_inputs)?;
+        let proof_hash: BytesN<32> = env.crypto().keccak256(&proof_blob).into();
+
+        Self::record_reveal(&mut game, slot, tile_index, tile_type);
+        let both_answered = game.round.done | (1 << (slot - 1)) == 0b11;
+        let decided = if both_answered { [Self::evaluate_win(&game, 1), Self::evaluate_win(&game, 2)] } else { [0, 0] };
+        let (player1_won, player2_won) = (decided.contains(&1), decided.contains(&2));
+        if both_answered { game.rounds_played += 1; }
+        Self::finish_round_step(&env, session_id, &mut game, slot, RoundStep::CommitAttacks);
+        let winner = if player1_won != player2_won { if player1_won { 1 } else { 2 } } else { 0 };
+        AttackAnswered {
+            session_id,
+            move_number: game.move_count,
+            player_slot: slot,
+            tile_index,
+            tile_type,
+            next_turn:   game.current_turn,
+            phase:       if player1_won || player2_won { Phase::Finished } else { Phase::Playing },
+            winner,
+            proof_hash:  proof_hash.clone(),
+        }
+        .publish(&env);
+        Self::log_move(&env, session_id, MoveRecord {
+            turn:       game.move_count,
+            attacker,
+            tile_index,
+            tile_type,
+            ledger:     env.ledger().sequence(),
+            proof_hash,
+        });
+
+        if player1_won && player2_won {
+            Self::finish_draw(&env, session_id, &mut game, Outcome::DrawSameRound)?;
+            RoundDrawn { session_id, move_number: game.move_count }.publish(&env);
+        } else if winner != 0 {
+            Self::finish_game(&env, session_id, &mut game, player1_won)?;
+        } else if !both_answered
+            || !Self::resolve_dead_position(&env, session_id, &mut game)?
+            && !Self::resolve_turn_limit(&env, session_id, &mut game)?
+        {
+            return Ok(());
+        }
+        Self::save_game(&env, session_id, &game);
+        Ok(())
+    }
+}
+
+impl PoisonGameContract {
+    /// Turn-based options that cannot apply to simultaneous rounds.
+    pub(crate) fn validate_simultaneous(config: &GameConfig) -> Result<(), Error> {
+        if !config.simultaneous { return Ok(()); }
+        if config.lite || config.salvo_size > 1 || config.time_bank > 0 || config.poison_penalty
+            || config.reveal_delay > 0
+        {
+            return Err(Error::InvalidConfig);
+        }
+        Ok(())
+    }
+
+    fn load_round_step(env: &Env, session_id: u32, player: &Address, step: RoundStep) -> Result<(GameState, u32), Error> {
+        Self::check_operational(env)?;
+        let game = Self::get_game(env.clone(), session_id)?;
+        if !game.config.simultaneous     { return Err(Error::WrongPhase);       }
+        if game.phase != Phase::Playing  { return Err(Error::WrongPhase);       }
+        if game.winner != 0              { return Err(Error::GameAlreadyEnded); }
+        let slot = Self::player_slot(&game, player)?;
+        if game.round.step != step || game.round.done & (1 << (slot - 1)) != 0 { return Err(Error::NotYourTurn); }
+        Ok((game, slot))
+    }
+
+    /// Mark `slot` done with this step, moving on to `next` once both are.
+    fn finish_round_step(env: &Env, session_id: u32, game: &mut GameState, slot: u32, next: RoundStep) {
+        game.round.done |= 1 << (slot - 1);
+        game.move_count += 1;
+        // Anchors the step's response window
+        game.last_action_ledger = env.ledger().sequence();
+        if game.round.done == 0b11 {
+            game.round.done = 0;
+            game.round.step = next;
+            if next == RoundStep::CommitAttacks { game.round = SimulRound::new(env); }
+        }
+        Self::save_game(env, session_id, game);
+        Self::bump_game_ttl(env, session_id);
+    }
+}
@@ -0,0 +1,208 @@
+//! # Spectator support
+//!
+//! Delayed-reveal mode: when a session sets `config.reveal_delay`, tile
+//! types are withheld from the live `AttackAnswered` event (sent as
+//! `TILE_EMBARGOED`) and parked here instead. Anyone can call
+//! `release_reveals` once the delay has passed — or the game is over — to
+//! publish them as `RevealReleased` events. This keeps streamed high-stakes
+//! matches from being coached in real time off the event feed.
+//!
+//! Broadcast sessions (`config.broadcast`) are produced shows: their
+//! embargo is capped at a short stream delay and they accept watcher
+//! registrations so productions can track their audience on-chain.
+//!
+//! `get_board_view` derives a per-tile picture of either board from the
+//! revealed vectors, with embargoed tiles still shown as hidden.
+
+use soroban_sdk::{contractevent, contractimpl, contracttype, vec, Address, Env, Vec};
+
+use crate::{count_specials, DataKey, Error, GameConfig, Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::GAME_TTL_LEDGERS;
+
+/// `tile_type` placeholder in `AttackAnswered` while a reveal is embargoed.
+pub const TILE_EMBARGOED: u32 = u32::MAX;
+
+/// Longest embargo a broadcast session can impose (~1 minute stream delay).
+const BROADCAST_MAX_DELAY: u32 = 12;
+const MAX_WATCHERS:        u32 = 200;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmbargoedReveal {
+    pub board_slot:     u32, // whose board the tile is on (1|2)
+    pub tile_index:     u32,
+    pub tile_type:      u32,
+    pub release_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TileStatus {
+    Hidden = 0,
+    Normal = 1,
+    Poison = 2,
+    Shield = 3,
+    Decoy  = 4,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoardView {
+    pub board_owner:  Address,
+    pub tiles:        Vec<TileStatus>, // one entry per tile, `board_size` long
+    pub revealed:     u32,
+    pub poison_found: u32,
+    pub shield_found: u32,
+    pub poison_total: u32,
+    pub shield_total: u32,
+}
+
+#[contractevent]
+pub struct RevealReleased {
+    #[topic]
+    pub session_id: u32,
+    pub board_slot: u32,
+    pub tile_index: u32,
+    pub tile_type:  u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Publish every embargoed reveal whose delay has elapsed (all of them
+    /// once the game is finished). Returns how many were released.
+    pub fn release_reveals(env: Env, session_id: u32) -> Result<u32, Error> {
+        let game = Self::get_game(env.clone(), session_id)?;
+        let finished = game.phase == Phase::Finished;
+        let now = env.ledger().sequence();
+
+        let pending = Self::get_embargoed(&env, session_id);
+        let mut kept = Vec::new(&env);
+        let mut released = 0u32;
+        for r in pending.iter() {
+            if finished || r.release_ledger <= now {
+                RevealReleased {
+                    session_id,
+                    board_slot: r.board_slot,
+                    tile_index: r.tile_index,
+                    tile_type:  r.tile_type,
+                }
+                .publish(&env);
+                released += 1;
+            } else {
+                kept.push_back(r);
+            }
+        }
+
+        Self::save_embargoed(&env, session_id, &kept);
+        Ok(released)
+    }
+
+    /// Register `watcher` as a spectator of a broadcast session.
+    pub fn watch(env: Env, session_id: u32, watcher: Address) -> Result<(), Error> {
+        watcher.require_auth();
+
+        let game = Self::get_game(env.clone(), session_id)?;
+        if !game.config.broadcast { return Err(Error::NotBroadcast); }
+
+        let mut watchers = Self::get_watchers(env.clone(), session_id);
+        if watchers.contains(&watcher)        { return Ok(()); }
+        if watchers.len() >= MAX_WATCHERS     { return Err(Error::TooManyWatchers); }
+        watchers.push_back(watcher);
+
+        let key = DataKey::Watchers(session_id);
+        env.storage().temporary().set(&key, &watchers);
+        env.storage().temporary().extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+        Ok(())
+    }
+
+    pub fn get_watchers(env: Env, session_id: u32) -> Vec<Address> {
+        env.storage().temporary()
+            .get(&DataKey::Watchers(session_id)).unwrap_or(vec![&env])
+    }
+
+    /// Spectator picture of `board_owner`'s board. Reveals still under
+    /// embargo are reported as `Hidden` and left out of the counts.
+    pub fn get_board_view(env: Env, session_id: u32, board_owner: Address) -> Result<BoardView, Error> {
+        let game = Self::get_game(env.clone(), session_id)?;
+        let (slot, revealed) = if board_owner == game.player1      { (1, &game.p1_revealed) }
+                               else if board_owner == game.player2 { (2, &game.p2_revealed) }
+                               else                                { return Err(Error::NotPlayer); };
+
+        let now = env.ledger().sequence();
+        let withheld = Self::get_embargoed(&env, session_id);
+        let mut visible = Vec::new(&env);
+        for t in revealed.iter() {
+            let embargoed = game.phase != Phase::Finished && withheld.iter().any(|r| {
+                r.board_slot == slot && r.tile_index == t.tile_index && r.release_ledger > now
+            });
+            if !embargoed { visible.push_back(t); }
+        }
+
+        let mut tiles = Vec::new(&env);
+        for _ in 0..game.board_size { tiles.push_back(TileStatus::Hidden); }
+        for t in visible.iter() {
+            let status = match t.tile_type {
+                1 => TileStatus::Poison,
+                2 => TileStatus::Shield,
+                3 => TileStatus::Decoy,
+                _ => TileStatus::Normal,
+            };
+            tiles.set(t.tile_index, status);
+        }
+
+        let (poison_found, shield_found) = count_specials(&visible);
+        Ok(BoardView {
+            board_owner,
+            tiles,
+            revealed:     visible.len(),
+            poison_found,
+            shield_found,
+            poison_total: game.config.poison_on(slot),
+            shield_total: game.config.shield_count,
+        })
+    }
+}
+
+impl PoisonGameContract {
+    /// Embargo length actually applied to a session's reveals.
+    pub(crate) fn effective_reveal_delay(config: &GameConfig) -> u32 {
+        if config.broadcast { config.reveal_delay.min(BROADCAST_MAX_DELAY) }
+        else                { config.reveal_delay }
+    }
+
+    /// Park a reveal until `reveal_delay` ledgers from now.
+    pub(crate) fn embargo_reveal(
+        env:        &Env,
+        session_id: u32,
+        board_slot: u32,
+        tile_index: u32,
+        tile_type:  u32,
+        delay:      u32,
+    ) {
+        let mut pending = Self::get_embargoed(env, session_id);
+        pending.push_back(EmbargoedReveal {
+            board_slot,
+            tile_index,
+            tile_type,
+            release_ledger: env.ledger().sequence().saturating_add(delay),
+        });
+        Self::save_embargoed(env, session_id, &pending);
+    }
+
+    pub(crate) fn get_embargoed(env: &Env, session_id: u32) -> Vec<EmbargoedReveal> {
+        env.storage().temporary()
+            .get(&DataKey::Embargo(session_id)).unwrap_or(vec![env])
+    }
+
+    fn save_embargoed(env: &Env, session_id: u32, pending: &Vec<EmbargoedReveal>) {
+        let key = DataKey::Embargo(session_id);
+        if pending.is_empty() {
+            env.storage().temporary().remove(&key);
+        } else {
+            env.storage().temporary().set(&key, pending);
+            env.storage().temporary().extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! # Lifetime player statistics
+//!
+//! Per-address counters kept in persistent storage so profiles can be read
+//! straight from chain state. Results are recorded by `finish_game` and
+//! draws by `finish_draw`; poison finds are recorded as each reveal is
+//! verified in `respond_to_attack`. The daily win count and
+//! distinct-opponent tally feed the combo achievements.
+
+use soroban_sdk::{contractimpl, contracttype, Address, Env};
+
+use crate::{DataKey, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::{LEDGERS_PER_DAY, PLAYER_TTL_LEDGERS};
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PlayerStats {
+    pub wins:          u32,
+    pub losses:        u32,
+    pub draws:         u32,
+    pub poison_found:  u32, // Poison tiles this player uncovered as attacker
+    pub win_streak:    u32, // current run of consecutive wins
+    pub win_day:       u32, // day (ledger / LEDGERS_PER_DAY) of the last win
+    pub day_wins:      u32, // wins on `win_day`
+    pub rivals_beaten: u32, // distinct opponents beaten
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    pub fn get_player_stats(env: Env, player: Address) -> PlayerStats {
+        env.storage().persistent()
+            .get(&DataKey::Stats(player)).unwrap_or_default()
+    }
+}
+
+impl PoisonGameContract {
+    pub(crate) fn record_result_stats(env: &Env, winner: &Address, loser: &Address) {
+        let mut w = Self::get_player_stats(env.clone(), winner.clone());
+        w.wins       += 1;
+        w.win_streak += 1;
+
+        let day = env.ledger().sequence() / LEDGERS_PER_DAY;
+        if w.win_day != day { w.win_day = day; w.day_wins = 0; }
+        w.day_wins += 1;
+
+        let rival = DataKey::Beaten(winner.clone(), loser.clone());
+        if !env.storage().persistent().has(&rival) {
+            env.storage().persistent().set(&rival, &true);
+            env.storage().persistent().extend_ttl(&rival, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+            w.rivals_beaten += 1;
+        }
+        Self::save_stats(env, winner, &w);
+
+        let mut l = Self::get_player_stats(env.clone(), loser.clone());
+        l.losses     += 1;
+        l.win_streak  = 0;
+        Self::save_stats(env, loser, &l);
+        Self::record_season_result(env, winner, loser);
+    }
+
+    /// A draw ends both players' win streaks.
+    pub(crate) fn record_draw_stats(env: &Env, player1: &Address, player2: &Address) {
+        for player in [player1, player2] {
+            let mut st = Self::get_player_stats(env.clone(), player.clone());
+            st.draws      += 1;
+            st.win_streak  = 0;
+            Self::save_stats(env, player, &st);
+        }
+    }
+
+    pub(crate) fn record_poison_found(env: &Env, attacker: &Address) {
+        let mut st = Self::get_player_stats(env.clone(), attacker.clone());
+        st.poison_found += 1;
+        Self::save_stats(env, attacker, &st);
+    }
+
+    fn save_stats(env: &Env, player: &Address, stats: &PlayerStats) {
+        let key = DataKey::Stats(player.clone());
+        env.storage().persistent().set(&key, stats);
+        env.storage().persistent().extend_ttl(&key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+    }
+}
@@ -0,0 +1,76 @@
+//! # Burn-in stress mode
+//!
+//! Built only with the `stress` feature, for test networks. `stress_run`
+//! creates and resolves synthetic sessions in bulk so operators can
+//! load-test indexers, event consumers and storage growth before enabling
+//! a subsystem on mainnet. Each session plays `STRESS_SCRIPT` through the
+//! same commit, attack and answer helpers as a real game — only proof
+//! verification is skipped — so its events, game entry, move log, receipt
+//! and archived result are exactly a real game's. A `StressSession` event
+//! tags every synthetic session id.
+//!
+//! The admin plays player1 against this contract's own address with no
+//! stakes, so the hub is never called; like any finished game, a stress
+//! session counts towards both seats' ratings and stats.
+
+use soroban_sdk::{contractevent, contractimpl, vec, Bytes, BytesN, Env};
+use ultrahonk_soroban_verifier::PROOF_BYTES;
+
+use crate::{Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+/// Sessions per `stress_run`: each one touches some 35 ledger entries, and a
+/// transaction's footprint is capped at 100.
+pub const MAX_STRESS_SESSIONS: u32 = 2;
+
+/// (attacker, tile_index, tile_type): player1 finds two Poison and the
+/// Shield on player2's board while player2 hits Normal tiles.
+const STRESS_SCRIPT: [(u32, u32, u32); 5] = [(1, 0, 1), (2, 10, 0), (1, 1, 1), (2, 11, 0), (1, 2, 2)];
+
+#[contractevent]
+pub struct StressSession {
+    #[topic]
+    pub session_id: u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Create and resolve `count` synthetic sessions from `first_session` on.
+    pub fn stress_run(env: Env, first_session: u32, count: u32) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        Self::check_operational(&env)?;
+        if count > MAX_STRESS_SESSIONS { return Err(Error::InsufficientResources); }
+
+        for session_id in first_session..first_session.saturating_add(count) {
+            Self::stress_session(&env, session_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl PoisonGameContract {
+    fn stress_session(env: &Env, session_id: u32) -> Result<(), Error> {
+        Self::check_session_unused(env, session_id)?;
+
+        let admin = Self::get_admin(env.clone())?;
+        let this  = env.current_contract_address();
+        let game  = Self::new_game(env, admin.clone(), this.clone(), 0, 0, Self::get_hub(env.clone())?);
+        Self::store_new_game(env, session_id, &game);
+        StressSession { session_id }.publish(env);
+
+        let seats = [admin, this];
+        for (slot, player) in [1u8, 2u8].iter().zip(seats.iter()) {
+            Self::place_commitment(env, session_id, player.clone(), BytesN::from_array(env, &[*slot; 32]))?;
+        }
+
+        // The real move path, minus proof verification
+        let blank = Bytes::from_array(env, &[0u8; PROOF_BYTES]);
+        for (attacker, tile_index, tile_type) in STRESS_SCRIPT {
+            let defender = 3 - attacker;
+            Self::place_attacks(env, session_id, seats[attacker as usize - 1].clone(), vec![env, tile_index])?;
+            let game = Self::get_game(env.clone(), session_id)?;
+            Self::apply_answers(env, session_id, game, defender, vec![env, (tile_type, blank.clone())], |_, _| Ok(()))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+//! # Game templates
+//!
+//! An admin-managed registry of named presets — rules (special counts,
+//! time control, win condition, …), board size and VK version — so a
+//! client can open a session with `start_game_from_template` and a
+//! template id instead of spelling out a full `GameConfig`. A template
+//! with `board_size` 0 picks the board from the stake tier, and one with
+//! `vk_version` 0 pins the current key, as `start_game` does.
+//!
+//! Players sign the template id, not its contents: replacing a template
+//! only affects sessions opened afterwards.
+
+use soroban_sdk::{contractimpl, contracttype, vec, Address, Env, IntoVal, Symbol, Vec};
+
+use crate::{DataKey, Error, GameConfig, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::MAX_BOARD_TILES;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameTemplate {
+    pub name:       Symbol,
+    pub board_size: u32, // 0 = by stake tier
+    pub vk_version: u32, // 0 = current at start
+    pub config:     GameConfig,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Add or replace template `template_id`.
+    pub fn set_template(env: Env, template_id: u32, template: GameTemplate) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        if template.board_size > MAX_BOARD_TILES                        { return Err(Error::InvalidConfig); }
+        if template.vk_version > Self::get_vk_version(env.clone())      { return Err(Error::VkNotSet);      }
+        if template.board_size > 0 {
+            Self::validate_config(&env, &template.config, template.board_size)?;
+        }
+
+        let mut ids = Self::list_templates(env.clone());
+        if !ids.contains(template_id) {
+            ids.push_back(template_id);
+            env.storage().instance().set(&DataKey::TemplateIds, &ids);
+        }
+        env.storage().instance().set(&DataKey::Template(template_id), &template);
+        Ok(())
+    }
+
+    pub fn remove_template(env: Env, template_id: u32) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        let mut ids = Self::list_templates(env.clone());
+        let i = ids.first_index_of(template_id).ok_or(Error::GameNotFound)?;
+        ids.remove(i);
+        env.storage().instance().set(&DataKey::TemplateIds, &ids);
+        env.storage().instance().remove(&DataKey::Template(template_id));
+        Ok(())
+    }
+
+    /// Registered template ids, oldest first.
+    pub fn list_templates(env: Env) -> Vec<u32> {
+        env.storage().instance().get(&DataKey::TemplateIds).unwrap_or(vec![&env])
+    }
+
+    pub fn get_template(env: Env, template_id: u32) -> Option<GameTemplate> {
+        env.storage().instance().get(&DataKey::Template(template_id))
+    }
+
+    /// Like `start_game`, but on template `template_id`'s rules. Both
+    /// players also sign the template id.
+    pub fn start_game_from_template(
+        env:            Env,
+        session_id:     u32,
+        player1:        Address,
+        player2:        Address,
+        player1_points: i128,
+        player2_points: i128,
+        template_id:    u32,
+    ) -> Result<(), Error> {
+        player1.require_auth_for_args(vec![
+            &env, session_id.into_val(&env), player1_points.into_val(&env), template_id.into_val(&env),
+        ]);
+        player2.require_auth_for_args(vec![
+            &env, session_id.into_val(&env), player2_points.into_val(&env), template_id.into_val(&env),
+        ]);
+        let template = Self::get_template(env.clone(), template_id).ok_or(Error::GameNotFound)?;
+
+        Self::open_game_with(
+            &env, session_id, player1, player2, player1_points, player2_points,
+            template.config, vec![&env], template.board_size, template.vk_version,
+        )
+    }
+}
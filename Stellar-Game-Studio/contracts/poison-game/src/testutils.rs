@@ -0,0 +1,257 @@
+//! Helpers for exercising the contract without real UltraHonk proofs.
+//!
+//! Built only with the `testutils` feature, which also swaps the verifier
+//! for a mock that accepts `mock_proof` output. Never enable it for a
+//! deployed build.
+//!
+//! `generate_fixture` turns a seed into a complete, deterministic game —
+//! boards, commitments, every move with its mock proof, and the expected
+//! winner — so the contract tests and the frontend e2e suite replay the
+//! same games. `GameFixture::to_json` is the format the frontend reads.
+
+extern crate std;
+
+use std::format;
+use std::string::String;
+use std::vec::Vec;
+
+use soroban_sdk::{Address, Bytes, BytesN, Env};
+use ultrahonk_soroban_verifier::PROOF_BYTES;
+
+use crate::lite::digest_field;
+use crate::{address_field, commitment_inputs, u32_field, DEFAULT_POISON, DEFAULT_SHIELD, TOTAL_TILES};
+
+const BOARD_TILES: usize = TOTAL_TILES as usize;
+
+/// Public inputs `respond_to_attack` rebuilds on-chain for a reveal.
+pub fn reveal_inputs(
+    env:          &Env,
+    commitment:   &BytesN<32>,
+    owner:        &Address,
+    session_id:   u32,
+    tile_index:   u32,
+    tile_type:    u32,
+    poison_count: u32,
+    shield_count: u32,
+    decoy_count:  u32,
+) -> Bytes {
+    let mut inputs = commitment_inputs(env, commitment, owner, session_id);
+    inputs.append(&u32_field(env, tile_index));
+    inputs.append(&u32_field(env, tile_type));
+    inputs.append(&u32_field(env, poison_count));
+    inputs.append(&u32_field(env, shield_count));
+    if decoy_count > 0 { inputs.append(&u32_field(env, decoy_count)); }
+    inputs
+}
+
+/// Commitment `commit_attack` expects for a simultaneous-round attack.
+pub fn attack_commitment(env: &Env, tile_index: u32, salt: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(env, &tile_index.to_be_bytes());
+    preimage.append(&Bytes::from(salt.clone()));
+    env.crypto().keccak256(&preimage).into()
+}
+
+/// Public inputs `respond_scan` rebuilds on-chain for a scan answer.
+pub fn scan_inputs(
+    env:          &Env,
+    commitment:   &BytesN<32>,
+    owner:        &Address,
+    session_id:   u32,
+    range_start:  u32,
+    specials:     u32,
+    poison_count: u32,
+    shield_count: u32,
+) -> Bytes {
+    let mut inputs = commitment_inputs(env, commitment, owner, session_id);
+    inputs.append(&u32_field(env, range_start));
+    inputs.append(&u32_field(env, specials));
+    inputs.append(&u32_field(env, poison_count));
+    inputs.append(&u32_field(env, shield_count));
+    inputs
+}
+
+/// Running lite-mode digest over a board's `(tile_index, tile_type)` reveals.
+pub fn lite_digest(env: &Env, reveals: &[(u32, u32)]) -> BytesN<32> {
+    let mut digest = BytesN::from_array(env, &[0u8; 32]);
+    for &(tile_index, tile_type) in reveals {
+        let mut preimage = Bytes::from(digest);
+        preimage.append(&u32_field(env, tile_index));
+        preimage.append(&u32_field(env, tile_type));
+        digest = env.crypto().keccak256(&preimage).into();
+    }
+    digest
+}
+
+/// Public inputs `claim_lite_win` rebuilds on-chain for the claimer's board.
+pub fn lite_inputs(
+    env:          &Env,
+    commitment:   &BytesN<32>,
+    owner:        &Address,
+    session_id:   u32,
+    reveals:      &[(u32, u32)],
+    poison_count: u32,
+    shield_count: u32,
+) -> Bytes {
+    let mut inputs = commitment_inputs(env, commitment, owner, session_id);
+    inputs.append(&digest_field(env, &lite_digest(env, reveals)));
+    inputs.append(&u32_field(env, reveals.len() as u32));
+    inputs.append(&u32_field(env, poison_count));
+    inputs.append(&u32_field(env, shield_count));
+    inputs
+}
+
+/// A `PROOF_BYTES`-long blob the mock verifier accepts for `pub_inputs`.
+pub fn mock_proof(env: &Env, pub_inputs: &Bytes) -> Bytes {
+    let digest: BytesN<32> = env.crypto().keccak256(pub_inputs).into();
+    let mut proof = Bytes::from_slice(env, &[0u8; PROOF_BYTES]);
+    proof.copy_from_slice(0, &digest.to_array());
+    proof
+}
+
+/// One answered attack: `attacker` (1|2) hits `tile_index` and the
+/// defender reveals `tile_type` with `proof`.
+#[derive(Clone, Debug)]
+pub struct FixtureMove {
+    pub attacker:   u32,
+    pub tile_index: u32,
+    pub tile_type:  u32,
+    pub proof:      Bytes,
+}
+
+#[derive(Clone, Debug)]
+pub struct GameFixture {
+    pub seed:        u64,
+    pub owners:      [Address; 2],
+    pub session_id:  u32,
+    pub boards:      [[u32; BOARD_TILES]; 2],
+    pub salts:       [BytesN<32>; 2],
+    // keccak256(board || salt || owner || session_id): a stand-in for the
+    // circuit's Pedersen commitment, only meaningful to the mock verifier
+    pub commitments: [BytesN<32>; 2],
+    pub moves:       Vec<FixtureMove>,
+    pub winner:      u32,
+}
+
+/// SplitMix64 — tiny, seedable and identical on every platform.
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// Build the full game for `seed` under the default rules, with the boards
+/// bound to `owners` (player1, player2) in session `session_id`.
+pub fn generate_fixture(env: &Env, seed: u64, owners: &[Address; 2], session_id: u32) -> GameFixture {
+    let mut rng = SplitMix(seed);
+
+    let mut boards = [[0u32; BOARD_TILES]; 2];
+    let mut salts: [[u8; 32]; 2] = [[0; 32]; 2];
+    for slot in 0..2 {
+        let specials = (0..DEFAULT_POISON).map(|_| 1).chain((0..DEFAULT_SHIELD).map(|_| 2));
+        for special in specials {
+            loop {
+                let i = rng.below(BOARD_TILES);
+                if boards[slot][i] == 0 { boards[slot][i] = special; break; }
+            }
+        }
+        for chunk in salts[slot].chunks_mut(8) {
+            chunk.copy_from_slice(&rng.next().to_be_bytes());
+        }
+    }
+
+    let commitments = [0, 1].map(|slot| {
+        let mut preimage = Bytes::new(env);
+        for t in boards[slot] { preimage.push_back(t as u8); }
+        preimage.extend_from_array(&salts[slot]);
+        preimage.append(&address_field(env, &owners[slot]));
+        preimage.append(&u32_field(env, session_id));
+        env.crypto().keccak256(&preimage).into()
+    });
+
+    // Play it out: attackers pick random unrevealed tiles until one side
+    // has found every special
+    let mut revealed = [[false; BOARD_TILES]; 2];
+    let mut found = [(0u32, 0u32); 2]; // (poison, shield) found on each board
+    let mut turn = 1u32;
+    let mut moves = Vec::new();
+    let winner = loop {
+        let defender = (2 - turn) as usize;
+        let open: Vec<usize> = (0..BOARD_TILES).filter(|&i| !revealed[defender][i]).collect();
+        let tile = open[rng.below(open.len())];
+        let tile_type = boards[defender][tile];
+        revealed[defender][tile] = true;
+
+        let inputs = reveal_inputs(
+            env, &commitments[defender], &owners[defender], session_id,
+            tile as u32, tile_type, DEFAULT_POISON, DEFAULT_SHIELD, 0,
+        );
+        moves.push(FixtureMove {
+            attacker:   turn,
+            tile_index: tile as u32,
+            tile_type,
+            proof:      mock_proof(env, &inputs),
+        });
+
+        if tile_type == 1 { found[defender].0 += 1; }
+        if tile_type == 2 { found[defender].1 += 1; }
+        if found[defender].0 >= DEFAULT_POISON && found[defender].1 >= DEFAULT_SHIELD { break turn; }
+        if tile_type != 2 { turn = 3 - turn; }
+    };
+
+    GameFixture {
+        seed,
+        owners: owners.clone(),
+        session_id,
+        boards,
+        salts: salts.map(|s| BytesN::from_array(env, &s)),
+        commitments,
+        moves,
+        winner,
+    }
+}
+
+fn hex(bytes: &Bytes) -> String {
+    let mut buf = std::vec![0u8; bytes.len() as usize];
+    bytes.copy_into_slice(&mut buf);
+    buf.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn strkey(address: &Address) -> String {
+    let strkey = address.to_string();
+    let mut buf = std::vec![0u8; strkey.len() as usize];
+    strkey.copy_into_slice(&mut buf);
+    String::from_utf8(buf).unwrap()
+}
+
+impl GameFixture {
+    /// JSON for the frontend e2e suite; byte fields are lowercase hex.
+    pub fn to_json(&self) -> String {
+        let boards: Vec<String> = self.boards.iter().map(|b| format!("{:?}", b)).collect();
+        let salts: Vec<String> = self.salts.iter()
+            .map(|s| format!("\"{}\"", hex(&Bytes::from(s.clone())))).collect();
+        let commitments: Vec<String> = self.commitments.iter()
+            .map(|c| format!("\"{}\"", hex(&Bytes::from(c.clone())))).collect();
+        let owners: Vec<String> = self.owners.iter()
+            .map(|o| format!("\"{}\"", strkey(o))).collect();
+        let moves: Vec<String> = self.moves.iter().map(|m| format!(
+            "{{\"attacker\":{},\"tile_index\":{},\"tile_type\":{},\"proof\":\"{}\"}}",
+            m.attacker, m.tile_index, m.tile_type, hex(&m.proof),
+        )).collect();
+
+        format!(
+            "{{\"seed\":{},\"owners\":[{}],\"session_id\":{},\"boards\":[{}],\"salts\":[{}],\"commitments\":[{}],\"moves\":[{}],\"winner\":{}}}",
+            self.seed, owners.join(","), self.session_id, boards.join(","), salts.join(","), commitments.join(","),
+            moves.join(","), self.winner,
+        )
+    }
+}
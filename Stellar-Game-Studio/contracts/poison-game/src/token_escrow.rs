@@ -0,0 +1,95 @@
+//! # Token escrow
+//!
+//! Token sessions are wagered in Stellar Asset Contract tokens instead of
+//! GameHub points, so the game runs standalone on networks without a hub.
+//! Each player's stake is a `Wager` leg — a token and an amount — agreed by
+//! both players at open; the legs may be in different tokens (player1 in
+//! XLM against player2 in USDC). Both legs are transferred into this
+//! contract when the session opens and paid straight out of it on
+//! settlement: both legs to the winner, or each leg back to its owner on a
+//! refund. A player's response bond is posted in their own leg's token.
+//!
+//! Token sessions are pooled sessions whose pool is this contract's own
+//! token balances (see `settlement.rs`). They are never raked, since fee
+//! accounting is kept in hub points. Stake checks compare raw amounts, so
+//! limits and tiers mean little across legs in different tokens.
+
+use soroban_sdk::{contractimpl, contracttype, token::TokenClient, vec, Address, Env, IntoVal};
+
+use crate::{Error, GameState, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Wager {
+    pub token:  Address,
+    pub amount: i128,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Like `start_game`, but each player escrows `amount` of `token` in
+    /// this contract. Each player signs the session id, token and amount.
+    pub fn start_game_with_token(
+        env: Env,
+        session_id: u32,
+        player1:    Address,
+        player2:    Address,
+        token:      Address,
+        amount:     i128,
+    ) -> Result<(), Error> {
+        let args = vec![&env, session_id.into_val(&env), token.clone().into_val(&env), amount.into_val(&env)];
+        player1.require_auth_for_args(args.clone());
+        player2.require_auth_for_args(args);
+
+        let wager = Wager { token, amount };
+        let wagers = vec![&env, wager.clone(), wager];
+        Self::open_game(&env, session_id, player1, player2, amount, amount, Self::default_config(&env), wagers)
+    }
+
+    /// Token session where each player stakes their own leg. Both players
+    /// sign the session id and both legs.
+    pub fn start_game_with_wagers(
+        env: Env,
+        session_id: u32,
+        player1:    Address,
+        player2:    Address,
+        wager1:     Wager,
+        wager2:     Wager,
+    ) -> Result<(), Error> {
+        let args = vec![
+            &env, session_id.into_val(&env), wager1.clone().into_val(&env), wager2.clone().into_val(&env),
+        ];
+        player1.require_auth_for_args(args.clone());
+        player2.require_auth_for_args(args);
+
+        let (player1_points, player2_points) = (wager1.amount, wager2.amount);
+        let wagers = vec![&env, wager1, wager2];
+        Self::open_game(&env, session_id, player1, player2, player1_points, player2_points, Self::default_config(&env), wagers)
+    }
+}
+
+impl PoisonGameContract {
+    /// Take `amount` from the player in `slot` into this session's pool: a
+    /// transfer of their leg's token for token sessions, `lock_points` on
+    /// the hub otherwise.
+    pub(crate) fn escrow_in(env: &Env, game: &GameState, slot: u32, amount: i128) -> Result<(), Error> {
+        let this   = env.current_contract_address();
+        let player = if slot == 1 { &game.player1 } else { &game.player2 };
+        match game.wagers.get(slot - 1) {
+            Some(leg) => TokenClient::new(env, &leg.token).transfer(player, &this, &amount),
+            None      => Self::game_hub(env, game).lock_points(&this, player, &amount),
+        }
+        Ok(())
+    }
+
+    /// Pay `amount` of the `slot` player's leg out of this session's pool to `to`.
+    pub(crate) fn escrow_out(env: &Env, game: &GameState, slot: u32, to: &Address, amount: i128) -> Result<(), Error> {
+        let this = env.current_contract_address();
+        match game.wagers.get(slot - 1) {
+            Some(leg) => TokenClient::new(env, &leg.token).transfer(&this, to, &amount),
+            None      => Self::game_hub(env, game).release_points(&this, to, &amount),
+        }
+        Ok(())
+    }
+}
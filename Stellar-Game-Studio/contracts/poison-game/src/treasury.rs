@@ -0,0 +1,130 @@
+//! # Treasury
+//!
+//! Points the contract holds on its own account in the GameHub's escrow
+//! pool, separate from any session's stakes. Anyone can fund it through
+//! `lock_points`; the balance is tracked here and only ever paid out by
+//! contract rules (bounties), via `release_points`.
+//!
+//! Protocol fees are kept apart from that balance. With `fee_bps > 0` each
+//! new session snapshots the rate and, at settlement, that share of the
+//! winner's pot stays in the pool; referrers' shares are credited out of it
+//! (`referral.rs`) and the rest accrues as fees, which the council withdraws
+//! with `withdraw_fees`. Only pots this contract releases itself can be
+//! raked, so raked sessions escrow their stakes with `lock_points` (see
+//! `settlement.rs`); split-hub sessions and series are not raked.
+//!
+//! The treasury, fee, jackpot and referral pools all sit on one GameHub,
+//! the pool hub. It follows the deployment's GameHub while the pools are
+//! empty; `set_hub` pins it to the hub holding them otherwise, so pooled
+//! points are only ever released where they were locked. Only sessions on
+//! the pool hub are raked.
+
+use soroban_sdk::{contractimpl, vec, Address, Env, IntoVal};
+
+use crate::sandbox::Hub;
+use crate::{DataKey, Error, GameState, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::{MAX_BPS, MAX_RAKE_BPS};
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Move `points` from `funder` into the treasury.
+    pub fn fund_treasury(env: Env, funder: Address, points: i128) -> Result<(), Error> {
+        funder.require_auth();
+        if points <= 0 { return Err(Error::NegativeStake); }
+
+        Self::pool(&env)?.lock_points(&env.current_contract_address(), &funder, &points);
+        let balance = Self::get_treasury_balance(env.clone())
+            .checked_add(points).ok_or(Error::StakeOverflow)?;
+        env.storage().instance().set(&DataKey::Treasury, &balance);
+        Ok(())
+    }
+
+    pub fn get_treasury_balance(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::Treasury).unwrap_or(0)
+    }
+
+    /// Rake taken from winners' pots in sessions opened from now on.
+    pub fn set_fee_bps(env: Env, fee_bps: u32) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_fee_bps", vec![&env, fee_bps.into_val(&env)])?;
+        if fee_bps + Self::get_jackpot_bps(env.clone()) > MAX_RAKE_BPS { return Err(Error::InvalidConfig); }
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        Ok(())
+    }
+
+    pub fn get_fee_bps(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::FeeBps).unwrap_or(0)
+    }
+
+    pub fn get_fee_balance(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::FeeBalance).unwrap_or(0)
+    }
+
+    /// Pay `points` of accrued fees out to `to`.
+    pub fn withdraw_fees(env: Env, to: Address, points: i128) -> Result<(), Error> {
+        let args = vec![&env, to.clone().into_val(&env), points.into_val(&env)];
+        Self::authorize_admin_action(&env, "withdraw_fees", args)?;
+
+        let balance = Self::get_fee_balance(env.clone());
+        if points <= 0      { return Err(Error::NegativeStake);       }
+        if points > balance { return Err(Error::InsufficientBalance); }
+        Self::pool(&env)?.release_points(&env.current_contract_address(), &to, &points);
+        env.storage().instance().set(&DataKey::FeeBalance, &(balance - points));
+        Ok(())
+    }
+}
+
+impl PoisonGameContract {
+    /// Pay `points` out of the treasury to `to`. Returns false, paying
+    /// nothing, when the balance cannot cover it.
+    pub(crate) fn treasury_pay(env: &Env, to: &Address, points: i128) -> Result<bool, Error> {
+        let balance = Self::get_treasury_balance(env.clone());
+        if points <= 0 || points > balance { return Ok(false); }
+
+        Self::pool(env)?.release_points(&env.current_contract_address(), to, &points);
+        env.storage().instance().set(&DataKey::Treasury, &(balance - points));
+        Ok(true)
+    }
+
+    /// The GameHub the pools are escrowed on.
+    pub(crate) fn pool_hub(env: &Env) -> Result<Address, Error> {
+        match env.storage().instance().get(&DataKey::PoolHub) {
+            Some(hub) if Self::pooled_points(env) > 0 => Ok(hub),
+            _                                         => Self::get_hub(env.clone()),
+        }
+    }
+
+    pub(crate) fn pool(env: &Env) -> Result<Hub<'_>, Error> {
+        let hub = Self::pool_hub(env)?;
+        Ok(Hub::new(env, &hub))
+    }
+
+    /// Keep the pools on the hub that holds them across a `set_hub`.
+    pub(crate) fn pin_pool_hub(env: &Env) -> Result<(), Error> {
+        if Self::pooled_points(env) > 0 {
+            env.storage().instance().set(&DataKey::PoolHub, &Self::pool_hub(env)?);
+        } else {
+            env.storage().instance().remove(&DataKey::PoolHub);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn pooled_points(env: &Env) -> i128 {
+        Self::get_treasury_balance(env.clone())
+            + Self::get_fee_balance(env.clone())
+            + Self::get_jackpot(env.clone())
+            + env.storage().instance().get::<_, i128>(&DataKey::ReferralOwed).unwrap_or(0)
+    }
+
+    /// Accrue the session's rake on `pot`, less referrers' shares, and return it.
+    pub(crate) fn take_fee(env: &Env, game: &GameState, pot: i128) -> Result<i128, Error> {
+        if game.hub != Self::pool_hub(env)? { return Ok(0); }
+        let fee = pot.checked_mul(game.fee_bps as i128).ok_or(Error::StakeOverflow)? / MAX_BPS as i128;
+        if fee > 0 {
+            let referrals = Self::credit_referrers(env, game, fee)?;
+            let balance = Self::get_fee_balance(env.clone()) + fee - referrals;
+            env.storage().instance().set(&DataKey::FeeBalance, &balance);
+        }
+        Ok(fee)
+    }
+}
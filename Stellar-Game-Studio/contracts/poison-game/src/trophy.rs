@@ -0,0 +1,63 @@
+//! # Trophies
+//!
+//! When a trophy contract is configured, `finish_game` asks it to mint the
+//! winner a trophy recording the session, the beaten opponent, the winner's
+//! wager and how many moves the game took. Minting itself — token ids,
+//! metadata, transfers — is entirely the companion contract's business; it
+//! only has to implement `TrophyMinter`. Trophies are minted when the
+//! winner is recorded, so a result later overturned in a dispute keeps its
+//! original trophy. A mint that fails is dropped: the trophy contract can
+//! never keep a game from finishing.
+
+use soroban_sdk::{contractclient, contractimpl, contracttype, vec, Address, Env, IntoVal};
+
+use crate::{DataKey, Error, GameState, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Trophy {
+    pub session_id: u32,
+    pub opponent:   Address,
+    pub wager:      i128, // the winner's own stake
+    pub move_count: u32,
+}
+
+#[contractclient(name = "TrophyMinterClient")]
+pub trait TrophyMinter {
+    fn mint_trophy(env: Env, to: Address, trophy: Trophy);
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Mint winners' trophies on `trophy` (or stop minting with None).
+    pub fn set_trophy_contract(env: Env, trophy: Option<Address>) -> Result<(), Error> {
+        Self::authorize_admin_action(&env, "set_trophy_contract", vec![&env, trophy.clone().into_val(&env)])?;
+        match trophy {
+            Some(t) => env.storage().instance().set(&DataKey::TrophyContract, &t),
+            None    => env.storage().instance().remove(&DataKey::TrophyContract),
+        }
+        Ok(())
+    }
+
+    pub fn get_trophy_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::TrophyContract)
+    }
+}
+
+impl PoisonGameContract {
+    /// Called from `finish_game` once the winner is set.
+    pub(crate) fn mint_trophy(env: &Env, session_id: u32, game: &GameState) {
+        let minter = match Self::get_trophy_contract(env.clone()) {
+            Some(m) => m,
+            None    => return,
+        };
+        let (winner, opponent, wager) = if game.winner == 1 {
+            (&game.player1, &game.player2, game.player1_points)
+        } else {
+            (&game.player2, &game.player1, game.player2_points)
+        };
+        let trophy = Trophy { session_id, opponent: opponent.clone(), wager, move_count: game.move_count };
+        let _ = TrophyMinterClient::new(env, &minter).try_mint_trophy(winner, &trophy);
+    }
+}
@@ -0,0 +1,51 @@
+//! # Game TTL
+//!
+//! Live games sit in temporary storage, which is deleted once its TTL
+//! lapses. Every attack and every answer pushes the game's TTL — and that
+//! of its move log, watcher list and embargoed reveals — back out to
+//! `GAME_TTL_LEDGERS`, so an active game never expires. A slow
+//! correspondence-style game can also be kept alive between moves by
+//! either player calling `extend_game_ttl`. High-stakes games kept in
+//! persistent storage (see `game_store.rs`) are extended there instead.
+
+use soroban_sdk::{contractimpl, Address, Env};
+
+use crate::{DataKey, Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::{GAME_TTL_LEDGERS, PLAYER_TTL_LEDGERS};
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Push the session's TTL back out to the full game lifetime.
+    pub fn extend_game_ttl(env: Env, session_id: u32, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+        let game = Self::get_game(env.clone(), session_id)?;
+        if caller != game.player1 && caller != game.player2 { return Err(Error::NotPlayer); }
+
+        Self::bump_game_ttl(&env, session_id);
+        Ok(())
+    }
+}
+
+impl PoisonGameContract {
+    pub(crate) fn bump_game_ttl(env: &Env, session_id: u32) {
+        let keys = [
+            DataKey::Game(session_id),
+            DataKey::GameProgress(session_id),
+            DataKey::MoveLog(session_id),
+            DataKey::Watchers(session_id),
+            DataKey::Embargo(session_id),
+        ];
+        // A persistent game's entries are extended in persistent storage
+        for key in keys.iter().take(2) {
+            if env.storage().persistent().has(key) {
+                env.storage().persistent().extend_ttl(key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+            }
+        }
+        for key in keys.iter() {
+            if env.storage().temporary().has(key) {
+                env.storage().temporary().extend_ttl(key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! # Turn limit
+//!
+//! With `config.max_rounds` set, a game that reaches that many rounds
+//! without a winner goes to a tiebreak. A round ends with each of
+//! player2's turns (in simultaneous games, with each resolved round).
+//! Each player scores the specials they found on the opponent's board,
+//! a Poison counting double; the higher score wins the game outright and
+//! a true tie is a draw that refunds both stakes.
+
+use soroban_sdk::{contractevent, Env};
+
+use crate::{BoardMask, Error, GameEnded, GameState, Outcome, Phase, PoisonGameContract};
+
+#[contractevent]
+pub struct TurnLimitReached {
+    #[topic]
+    pub session_id:    u32,
+    pub move_number:   u32,
+    pub player1_score: u32,
+    pub player2_score: u32,
+    pub winner:        u32, // 0 = draw, stakes refunded
+}
+
+impl PoisonGameContract {
+    pub(crate) fn turn_limit_reached(game: &GameState) -> bool {
+        game.config.max_rounds > 0 && game.rounds_played >= game.config.max_rounds
+    }
+
+    /// Settle an undecided game on the tiebreak once the round limit is
+    /// reached. Returns whether the game is now over.
+    pub(crate) fn resolve_turn_limit(env: &Env, session_id: u32, game: &mut GameState) -> Result<bool, Error> {
+        if game.phase != Phase::Playing || game.winner != 0 || !Self::turn_limit_reached(game) {
+            return Ok(false);
+        }
+        Self::settle_tiebreak(env, session_id, game)?;
+        Ok(true)
+    }
+
+    /// Decide the game on the players' tiebreak scores; level scores draw.
+    pub(crate) fn settle_tiebreak(env: &Env, session_id: u32, game: &mut GameState) -> Result<(), Error> {
+        let player1_score = Self::tiebreak_score(&game.p2_mask);
+        let player2_score = Self::tiebreak_score(&game.p1_mask);
+        if player1_score == player2_score {
+            Self::finish_draw(env, session_id, game, Outcome::DrawLevelTiebreak)?;
+        } else {
+            Self::finish_game(env, session_id, game, player1_score > player2_score)?;
+        }
+        TurnLimitReached { session_id, move_number: game.move_count, player1_score, player2_score, winner: game.winner }
+            .publish(env);
+        Ok(())
+    }
+
+    /// End the game with no winner and refund both stakes. Stats, ratings
+    /// and the archive record the draw; achievements, trophies and bounties
+    /// only follow a win.
+    pub(crate) fn finish_draw(env: &Env, session_id: u32, game: &mut GameState, outcome: Outcome) -> Result<(), Error> {
+        game.phase = Phase::Finished;
+        Self::settle_refund(env, session_id, game)?;
+        Self::record_draw_stats(env, &game.player1, &game.player2);
+        Self::update_draw_ratings(env, game);
+        Self::archive_draw(env, session_id, game, outcome);
+        GameEnded { session_id, winner: 0, outcome }.publish(env);
+        Ok(())
+    }
+
+    fn tiebreak_score(found: &BoardMask) -> u32 {
+        let (poison, shield) = found.specials();
+        poison * 2 + shield
+    }
+}
@@ -0,0 +1,101 @@
+//! # On-chain tutorial
+//!
+//! A scripted single-player game against a fixed defender board: no stakes,
+//! no commitments and no proofs — the contract answers each attack from
+//! `TUTORIAL_BOARD` directly. Finishing it (2 Poison + 1 Shield found) marks
+//! the player as onboarded in persistent storage, which ranked entry can be
+//! configured to require.
+
+use soroban_sdk::{contractimpl, contracttype, vec, Address, Env, Vec};
+
+use crate::{attacker_won, DataKey, Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient, RevealedTile};
+use crate::{GAME_TTL_LEDGERS, PLAYER_TTL_LEDGERS};
+
+/// The scripted defender board (0=Normal 1=Poison 2=Shield).
+const TUTORIAL_BOARD: [u32; 15] = [
+    0, 0, 1, 0, 0,
+    0, 2, 0, 0, 0,
+    0, 0, 0, 1, 0,
+];
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TutorialState {
+    pub revealed: Vec<RevealedTile>,
+    pub moves:    u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Start (or restart) the tutorial for `player`.
+    pub fn start_tutorial(env: Env, player: Address) {
+        player.require_auth();
+        let state = TutorialState { revealed: vec![&env], moves: 0 };
+        let key = DataKey::Tutorial(player);
+        env.storage().temporary().set(&key, &state);
+        env.storage().temporary().extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+    }
+
+    /// Attack a tile on the scripted board and get its type back immediately.
+    /// Finding all specials completes the tutorial.
+    pub fn tutorial_attack(env: Env, player: Address, tile_index: u32) -> Result<u32, Error> {
+        player.require_auth();
+
+        let key = DataKey::Tutorial(player.clone());
+        let mut state: TutorialState = env.storage().temporary()
+            .get(&key).ok_or(Error::TutorialNotStarted)?;
+
+        if tile_index as usize >= TUTORIAL_BOARD.len() { return Err(Error::InvalidTileIndex); }
+        for t in state.revealed.iter() {
+            if t.tile_index == tile_index { return Err(Error::TileAlreadyRevealed); }
+        }
+
+        let tile_type = TUTORIAL_BOARD[tile_index as usize];
+        state.revealed.push_back(RevealedTile { tile_index, tile_type });
+        state.moves += 1;
+
+        if attacker_won(&state.revealed, &Self::default_config(&env), 2) {
+            let done = DataKey::TutorialDone(player);
+            env.storage().persistent().set(&done, &true);
+            env.storage().persistent().extend_ttl(&done, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+            env.storage().temporary().remove(&key);
+        } else {
+            env.storage().temporary().set(&key, &state);
+        }
+        Ok(tile_type)
+    }
+
+    pub fn get_tutorial(env: Env, player: Address) -> Option<TutorialState> {
+        env.storage().temporary().get(&DataKey::Tutorial(player))
+    }
+
+    pub fn has_completed_tutorial(env: Env, player: Address) -> bool {
+        env.storage().persistent().get(&DataKey::TutorialDone(player)).unwrap_or(false)
+    }
+
+    /// When enabled, staked games and series only start if both players
+    /// have completed the tutorial.
+    pub fn set_tutorial_required(env: Env, required: bool) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::TutorialRequired, &required);
+        Ok(())
+    }
+
+    pub fn is_tutorial_required(env: Env) -> bool {
+        env.storage().instance().get(&DataKey::TutorialRequired).unwrap_or(false)
+    }
+}
+
+impl PoisonGameContract {
+    /// Ranked-entry gate used by `start_game` / `start_series`.
+    pub(crate) fn check_tutorial_gate(env: &Env, player1: &Address, player2: &Address) -> Result<(), Error> {
+        if !Self::is_tutorial_required(env.clone()) { return Ok(()); }
+        if !Self::has_completed_tutorial(env.clone(), player1.clone())
+            || !Self::has_completed_tutorial(env.clone(), player2.clone())
+        {
+            return Err(Error::TutorialRequired);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+//! # Timelocked upgrades
+//!
+//! Upgrades are announced before they can run: `schedule_upgrade` records
+//! the new wasm hash with an activation ledger `UPGRADE_DELAY_LEDGERS`
+//! ahead and emits `UpgradeScheduled`, so players can read the pending
+//! code with `get_pending_upgrade` and leave before it lands. Anyone may
+//! `execute_upgrade` once the delay has passed; the admin (or council)
+//! can `cancel_upgrade` until then.
+
+use soroban_sdk::{contractevent, contractimpl, contracttype, vec, BytesN, Env, IntoVal};
+
+use crate::{DataKey, Error, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+
+/// Minimum notice before an upgrade can execute (~1 day).
+pub const UPGRADE_DELAY_LEDGERS: u32 = 17_280;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingUpgrade {
+    pub wasm_hash:         BytesN<32>,
+    pub activation_ledger: u32,
+}
+
+#[contractevent]
+pub struct UpgradeScheduled {
+    pub wasm_hash:         BytesN<32>,
+    pub activation_ledger: u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Announce `new_wasm_hash`, replacing any upgrade already pending.
+    pub fn schedule_upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<PendingUpgrade, Error> {
+        Self::authorize_admin_action(&env, "upgrade", vec![&env, new_wasm_hash.clone().into_val(&env)])?;
+
+        let pending = PendingUpgrade {
+            wasm_hash:         new_wasm_hash,
+            activation_ledger: env.ledger().sequence().saturating_add(UPGRADE_DELAY_LEDGERS),
+        };
+        env.storage().instance().set(&DataKey::PendingUpgrade, &pending);
+
+        UpgradeScheduled {
+            wasm_hash:         pending.wasm_hash.clone(),
+            activation_ledger: pending.activation_ledger,
+        }
+        .publish(&env);
+        Ok(pending)
+    }
+
+    pub fn get_pending_upgrade(env: Env) -> Option<PendingUpgrade> {
+        env.storage().instance().get(&DataKey::PendingUpgrade)
+    }
+
+    /// Install the pending upgrade once its activation ledger is reached.
+    pub fn execute_upgrade(env: Env) -> Result<(), Error> {
+        let pending = Self::get_pending_upgrade(env.clone()).ok_or(Error::NoPendingUpgrade)?;
+        if env.ledger().sequence() < pending.activation_ledger { return Err(Error::UpgradeNotReady); }
+
+        env.storage().instance().remove(&DataKey::PendingUpgrade);
+        env.deployer().update_current_contract_wasm(pending.wasm_hash);
+        Ok(())
+    }
+
+    pub fn cancel_upgrade(env: Env) -> Result<(), Error> {
+        let pending = Self::get_pending_upgrade(env.clone()).ok_or(Error::NoPendingUpgrade)?;
+        Self::authorize_admin_action(&env, "cancel_upgrade", vec![&env, pending.wasm_hash.into_val(&env)])?;
+        env.storage().instance().remove(&DataKey::PendingUpgrade);
+        Ok(())
+    }
+}
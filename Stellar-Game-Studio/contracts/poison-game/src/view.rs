@@ -0,0 +1,61 @@
+//! # PoisonGameView interface
+//!
+//! A read-only surface for other contracts — tournaments, betting markets,
+//! quest systems — that only need to know how a session stands: who won,
+//! which phase it is in, and whether an address plays in it. Callers use
+//! `PoisonGameViewClient` and never depend on the `GameState` layout.
+//!
+//! Answers come from the live game while it is stored and from the
+//! archived result or settlement receipt afterwards. A session that was
+//! settled, refunded or cancelled reads as `Finished`.
+
+use soroban_sdk::{contractclient, contractimpl, Address, Env};
+
+use crate::{Phase, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient, SessionStatus};
+
+#[contractclient(name = "PoisonGameViewClient")]
+pub trait PoisonGameView {
+    /// 1 or 2 once the session is won; 0 while undecided, drawn or unknown.
+    fn get_winner(env: Env, session_id: u32) -> u32;
+    /// None if the session was never opened or has left storage untraced.
+    fn get_phase(env: Env, session_id: u32) -> Option<Phase>;
+    fn is_player(env: Env, session_id: u32, addr: Address) -> bool;
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    pub fn get_winner(env: Env, session_id: u32) -> u32 {
+        Self::session_result(env, session_id).map_or(0, |r| r.winner)
+    }
+
+    pub fn get_phase(env: Env, session_id: u32) -> Option<Phase> {
+        match Self::session_status(env, session_id) {
+            SessionStatus::Unknown                            => None,
+            SessionStatus::Pending                            => Some(Phase::WaitingForCommits),
+            SessionStatus::Active                             => Some(Phase::Playing),
+            SessionStatus::Finished | SessionStatus::Cancelled => Some(Phase::Finished),
+        }
+    }
+
+    pub fn is_player(env: Env, session_id: u32, addr: Address) -> bool {
+        if let Ok(game) = Self::get_game(env.clone(), session_id) {
+            return addr == game.player1 || addr == game.player2;
+        }
+        Self::get_result(env, session_id).is_some_and(|r| addr == r.player1 || addr == r.player2)
+    }
+}
+
+impl PoisonGameView for PoisonGameContract {
+    fn get_winner(env: Env, session_id: u32) -> u32 {
+        Self::get_winner(env, session_id)
+    }
+
+    fn get_phase(env: Env, session_id: u32) -> Option<Phase> {
+        Self::get_phase(env, session_id)
+    }
+
+    fn is_player(env: Env, session_id: u32, addr: Address) -> bool {
+        Self::is_player(env, session_id, addr)
+    }
+}
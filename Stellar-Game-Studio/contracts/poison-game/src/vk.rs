@@ -0,0 +1,238 @@
+//! # Verification-key rotation
+//!
+//! `init_vk` adds a VK version that only new games use. `rotate_vk`
+//! migrates instead: it installs a new version and, for `grace_ledgers`,
+//! reveals in games on either the outgoing or the incoming version verify
+//! against both keys. Once the window closes the outgoing key is retired
+//! and its games verify against the new one only.
+//!
+//! Keys are parsed when installed, so a malformed one is refused there
+//! instead of failing every move, and stored in parsed form (`StoredVk`)
+//! so a reveal never parses key bytes again. A call answering several
+//! attacks loads its game's key once (`reveal_keys`) and reuses it for
+//! each proof. Poseidon-committed and decoy games verify against their own
+//! slots instead, which are never rotated (see `commitment.rs` and
+//! `decoy.rs`); the scan and lite circuits have a slot each as well.
+
+use soroban_sdk::{contractevent, contractimpl, contracttype, vec, Bytes, BytesN, Env, IntoVal, Vec};
+#[cfg(not(feature = "testutils"))]
+use ultrahonk_soroban_verifier::types::{G1Point, VerificationKey};
+
+use crate::{CommitmentScheme, DataKey, Error, GameConfig, ParsedVk, PoisonGameContract, PoisonGameContractArgs, PoisonGameContractClient};
+use crate::PLAYER_TTL_LEDGERS;
+
+/// Which reveal circuit a game's proofs come from.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum RevealCircuit {
+    Main,
+    Poseidon,
+    Decoy,
+}
+
+impl RevealCircuit {
+    pub(crate) fn of(config: &GameConfig) -> Self {
+        if config.decoy_count > 0 { return RevealCircuit::Decoy; }
+        match config.commitment_scheme {
+            CommitmentScheme::Poseidon => RevealCircuit::Poseidon,
+            CommitmentScheme::Pedersen => RevealCircuit::Main,
+        }
+    }
+}
+
+/// Commitments in a stored key.
+#[cfg(not(feature = "testutils"))]
+pub(crate) const VK_POINTS: u32 = 27;
+
+/// A VK as parsed at install time: the header sizes and its commitments
+/// (`qm` … `lagrange_last`) in the order `bb` writes them.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StoredVk {
+    pub circuit_size:       u64,
+    pub log_circuit_size:   u64,
+    pub public_inputs_size: u64,
+    pub points:             Vec<BytesN<64>>,
+}
+
+#[cfg(not(feature = "testutils"))]
+impl StoredVk {
+    pub(crate) fn from_key(env: &Env, vk: &VerificationKey) -> Self {
+        let mut points = Vec::new(env);
+        for point in [
+            &vk.qm, &vk.qc, &vk.ql, &vk.qr, &vk.qo, &vk.q4, &vk.q_lookup, &vk.q_arith, &vk.q_delta_range,
+            &vk.q_elliptic, &vk.q_aux, &vk.q_poseidon2_external, &vk.q_poseidon2_internal,
+            &vk.s1, &vk.s2, &vk.s3, &vk.s4, &vk.id1, &vk.id2, &vk.id3, &vk.id4,
+            &vk.t1, &vk.t2, &vk.t3, &vk.t4, &vk.lagrange_first, &vk.lagrange_last,
+        ] {
+            points.push_back(BytesN::from_array(env, &point.to_bytes()));
+        }
+        StoredVk {
+            circuit_size:       vk.circuit_size,
+            log_circuit_size:   vk.log_circuit_size,
+            public_inputs_size: vk.public_inputs_size,
+            points,
+        }
+    }
+
+    pub(crate) fn to_key(&self) -> VerificationKey {
+        let p = |i: u32| G1Point::from_bytes(self.points.get_unchecked(i).to_array());
+        VerificationKey {
+            circuit_size:         self.circuit_size,
+            log_circuit_size:     self.log_circuit_size,
+            public_inputs_size:   self.public_inputs_size,
+            qm:                   p(0),
+            qc:                   p(1),
+            ql:                   p(2),
+            qr:                   p(3),
+            qo:                   p(4),
+            q4:                   p(5),
+            q_lookup:             p(6),
+            q_arith:              p(7),
+            q_delta_range:        p(8),
+            q_elliptic:           p(9),
+            q_aux:                p(10),
+            q_poseidon2_external: p(11),
+            q_poseidon2_internal: p(12),
+            s1:                   p(13),
+            s2:                   p(14),
+            s3:                   p(15),
+            s4:                   p(16),
+            id1:                  p(17),
+            id2:                  p(18),
+            id3:                  p(19),
+            id4:                  p(20),
+            t1:                   p(21),
+            t2:                   p(22),
+            t3:                   p(23),
+            t4:                   p(24),
+            lagrange_first:       p(25),
+            lagrange_last:        p(26),
+        }
+    }
+}
+
+/// A game's reveal key, parsed, and the rotation fallback version if any.
+pub(crate) struct RevealKeys {
+    primary:  ParsedVk,
+    fallback: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VkRotation {
+    pub previous:     u32,
+    pub current:      u32,
+    pub until_ledger: u32, // last ledger `previous` is still accepted
+}
+
+#[contractevent]
+pub struct VkRotated {
+    pub previous:     u32,
+    pub current:      u32,
+    pub until_ledger: u32,
+}
+
+#[contractimpl]
+impl PoisonGameContract {
+
+    /// Install `new_vk` as the current version, keeping the outgoing one
+    /// accepted for `grace_ledgers`. Returns the new version.
+    pub fn rotate_vk(env: Env, new_vk: Bytes, grace_ledgers: u32) -> Result<u32, Error> {
+        Self::authorize_admin_action(
+            &env, "rotate_vk", vec![&env, new_vk.clone().into_val(&env), grace_ledgers.into_val(&env)],
+        )?;
+        let previous = Self::get_vk_version(env.clone());
+        if previous == 0 { return Err(Error::VkNotSet); }
+
+        let current = Self::store_vk(&env, &new_vk)?;
+        let rotation = VkRotation {
+            previous,
+            current,
+            until_ledger: env.ledger().sequence().saturating_add(grace_ledgers),
+        };
+        env.storage().instance().set(&DataKey::VkRotation, &rotation);
+
+        VkRotated { previous, current, until_ledger: rotation.until_ledger }.publish(&env);
+        Ok(current)
+    }
+
+    pub fn get_vk_rotation(env: Env) -> Option<VkRotation> {
+        env.storage().instance().get(&DataKey::VkRotation)
+    }
+}
+
+impl PoisonGameContract {
+    /// Store `vk_bytes` as the next version and make it current.
+    pub(crate) fn store_vk(env: &Env, vk_bytes: &Bytes) -> Result<u32, Error> {
+        let version = Self::get_vk_version(env.clone()) + 1;
+        Self::install_vk(env, &DataKey::Vk(version), vk_bytes)?;
+        env.storage().instance().set(&DataKey::VkVersion, &version);
+        Ok(version)
+    }
+
+    /// Parse `vk_bytes` and store the parsed key under `key`.
+    pub(crate) fn install_vk(env: &Env, key: &DataKey, vk_bytes: &Bytes) -> Result<(), Error> {
+        let vk = Self::parse_vk(env, vk_bytes)?;
+        env.storage().persistent().set(key, &vk);
+        env.storage().persistent().extend_ttl(key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        Ok(())
+    }
+
+    /// Verifier for the key stored under `key`.
+    pub(crate) fn load_verifier(env: &Env, key: &DataKey) -> Result<ParsedVk, Error> {
+        let vk: StoredVk = env.storage().persistent().get(key).ok_or(Error::VkNotSet)?;
+        env.storage().persistent().extend_ttl(key, PLAYER_TTL_LEDGERS, PLAYER_TTL_LEDGERS);
+        Self::verifier_of(env, &vk)
+    }
+
+    /// Verify a reveal for a game pinned to `pinned`, falling back to the
+    /// other key of an open rotation window. The fallback costs a second
+    /// verification and is charged against the per-call proof budget.
+    pub(crate) fn verify_reveal(
+        env: &Env, pinned: u32, circuit: RevealCircuit, proof: &Bytes, pub_inputs: &Bytes,
+    ) -> Result<(), Error> {
+        Self::verify_with_keys(env, &Self::reveal_keys(env, pinned, circuit)?, proof, pub_inputs)
+    }
+
+    /// Load the key a game pinned to `pinned` verifies against, for reuse
+    /// across every proof in the call.
+    pub(crate) fn reveal_keys(env: &Env, pinned: u32, circuit: RevealCircuit) -> Result<RevealKeys, Error> {
+        let single = match circuit {
+            RevealCircuit::Poseidon => Some(DataKey::PoseidonVk),
+            RevealCircuit::Decoy    => Some(DataKey::DecoyVk),
+            RevealCircuit::Main     => None,
+        };
+        if let Some(key) = single {
+            return Ok(RevealKeys { primary: Self::load_verifier(env, &key)?, fallback: None });
+        }
+        let (primary, fallback) = Self::accepted_vk_versions(env, pinned);
+        Ok(RevealKeys { primary: Self::load_verifier(env, &DataKey::Vk(primary))?, fallback })
+    }
+
+    /// `verify_reveal` with the keys already loaded. The rotation fallback
+    /// is only loaded if the primary key rejects the proof.
+    pub(crate) fn verify_with_keys(env: &Env, keys: &RevealKeys, proof: &Bytes, pub_inputs: &Bytes) -> Result<(), Error> {
+        match (Self::verify_proof(env, &keys.primary, proof, pub_inputs), keys.fallback) {
+            (Err(_), Some(version)) => {
+                Self::check_verify_budget(env, 2)?;
+                Self::verify_proof(env, &Self::load_verifier(env, &DataKey::Vk(version))?, proof, pub_inputs)
+            }
+            (result, _) => result,
+        }
+    }
+
+    fn accepted_vk_versions(env: &Env, pinned: u32) -> (u32, Option<u32>) {
+        let rotation = match Self::get_vk_rotation(env.clone()) {
+            Some(r) => r,
+            None    => return (pinned, None),
+        };
+        let open = env.ledger().sequence() <= rotation.until_ledger;
+        if pinned == rotation.previous {
+            if open { (rotation.previous, Some(rotation.current)) } else { (rotation.current, None) }
+        } else if pinned == rotation.current && open {
+            (rotation.current, Some(rotation.previous))
+        } else {
+            (pinned, None)
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! # Win conditions
+//!
+//! `config.win_condition` picks the variant a session is played under:
+//!
+//!   * `AllSpecials` — the classic game: the first attacker to reveal
+//!     every Poison and Shield on the opponent's board wins;
+//!   * `FirstPoisonLoses` — the attacker who uncovers a Poison loses on
+//!     the spot; Shields still grant bonus attacks;
+//!   * `MostSpecialsAfterNRounds` — nothing ends the game early; after
+//!     `config.max_rounds` rounds it is decided on the tiebreak score
+//!     (see `turn_limit.rs`).
+//!
+//! Every answered attack runs the session's evaluator, which names the
+//! slot the reveal decides the game for, if any.
+
+use soroban_sdk::contracttype;
+
+use crate::{attacker_won_mask, Error, GameConfig, GameState, PoisonGameContract};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum WinCondition {
+    AllSpecials              = 0,
+    FirstPoisonLoses         = 1,
+    MostSpecialsAfterNRounds = 2,
+}
+
+impl PoisonGameContract {
+    /// Winner (1|2) decided by `attacker`'s reveals so far, 0 if none.
+    pub(crate) fn evaluate_win(game: &GameState, attacker: u32) -> u32 {
+        let found = if attacker == 1 { &game.p2_mask } else { &game.p1_mask };
+        match game.config.win_condition {
+            WinCondition::AllSpecials if attacker_won_mask(found, &game.config, 3 - attacker) => attacker,
+            WinCondition::FirstPoisonLoses if found.poison != 0                                => 3 - attacker,
+            _ => 0,
+        }
+    }
+
+    pub(crate) fn validate_win_condition(config: &GameConfig) -> Result<(), Error> {
+        match config.win_condition {
+            WinCondition::AllSpecials => Ok(()),
+            // Lite games only prove the classic condition at the win claim
+            _ if config.lite => Err(Error::InvalidConfig),
+            WinCondition::MostSpecialsAfterNRounds if config.max_rounds == 0 => Err(Error::InvalidConfig),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+// Achievements unlock once per player and are forwarded to the badge contract.
+
+mod common;
+
+use common::setup;
+use poison_game::{Achievement, Trophy, LEDGERS_PER_DAY};
+use soroban_sdk::{contract, contractimpl, contracttype, vec, Address, Env};
+
+#[contracttype]
+#[derive(Clone)]
+enum BadgeKey {
+    Awards(Address, u32),
+}
+
+#[contract]
+pub struct MockBadge;
+
+#[contractimpl]
+impl MockBadge {
+    pub fn award(env: Env, player: Address, achievement_id: u32) {
+        let key = BadgeKey::Awards(player, achievement_id);
+        let n: u32 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(n + 1));
+    }
+
+    pub fn awards(env: Env, player: Address, achievement_id: u32) -> u32 {
+        env.storage().instance().get(&BadgeKey::Awards(player, achievement_id)).unwrap_or(0)
+    }
+}
+
+/// Player1 wins without player2 finding any special on player1's board.
+fn win(s: &common::Setup, session_id: u32) {
+    s.start_and_commit(session_id);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(session_id, attacker, tile);
+    }
+}
+
+#[test]
+fn test_combo_achievements_unlock_once() {
+    let s = setup();
+    let badge = MockBadgeClient::new(&s.env, &s.env.register(MockBadge, ()));
+    s.client.set_badge_contract(&Some(badge.address.clone()));
+
+    win(&s, 1);
+    assert_eq!(badge.awards(&s.player1, &(Achievement::Flawless as u32)), 1);
+    assert_eq!(badge.awards(&s.player1, &(Achievement::TripleDay as u32)), 0);
+
+    // A win on the next day starts a new daily count
+    s.advance(LEDGERS_PER_DAY);
+    win(&s, 2);
+    win(&s, 3);
+    assert_eq!(s.client.get_player_stats(&s.player1).day_wins, 2);
+    win(&s, 4);
+    assert_eq!(badge.awards(&s.player1, &(Achievement::TripleDay as u32)), 1);
+    assert_eq!(badge.awards(&s.player1, &(Achievement::Flawless as u32)), 1);
+
+    // Repeat wins against the same opponent are one rival
+    assert_eq!(s.client.get_player_stats(&s.player1).rivals_beaten, 1);
+    assert_eq!(badge.awards(&s.player1, &(Achievement::FiveRivals as u32)), 0);
+}
+
+#[test]
+fn test_first_quick_and_streak_wins_unlock() {
+    let s = setup();
+    assert_eq!(s.client.get_achievements(&s.player1), vec![&s.env]);
+
+    win(&s, 1);
+    let unlocked = vec![&s.env, Achievement::Flawless, Achievement::FirstWin, Achievement::QuickWin];
+    assert_eq!(s.client.get_achievements(&s.player1), unlocked);
+    assert_eq!(s.client.get_achievements(&s.player2), vec![&s.env]);
+
+    for session_id in 2..=10 {
+        s.advance(LEDGERS_PER_DAY);
+        win(&s, session_id);
+    }
+    assert_eq!(s.client.get_player_stats(&s.player1).win_streak, 10);
+    assert!(s.client.get_achievements(&s.player1).contains(Achievement::TenStreak));
+    assert!(!s.client.get_achievements(&s.player1).contains(Achievement::TripleDay));
+}
+
+#[contract]
+pub struct MockTrophies;
+
+#[contractimpl]
+impl MockTrophies {
+    pub fn mint_trophy(env: Env, to: Address, trophy: Trophy) {
+        env.storage().instance().set(&to, &trophy);
+    }
+
+    pub fn trophy(env: Env, owner: Address) -> Option<Trophy> {
+        env.storage().instance().get(&owner)
+    }
+}
+
+#[test]
+fn test_winner_is_minted_a_trophy() {
+    let s = setup();
+    let trophies = MockTrophiesClient::new(&s.env, &s.env.register(MockTrophies, ()));
+    win(&s, 1);
+    assert_eq!(trophies.trophy(&s.player1), None);
+
+    s.client.set_trophy_contract(&Some(trophies.address.clone()));
+    win(&s, 2);
+    let trophy = trophies.trophy(&s.player1).unwrap();
+    assert_eq!(trophy, Trophy { session_id: 2, opponent: s.player2.clone(), wager: 100, move_count: 12 });
+    assert_eq!(trophies.trophy(&s.player2), None);
+}
+
+#[contract]
+pub struct MockReverting;
+
+#[contractimpl]
+impl MockReverting {
+    pub fn mint_trophy(_env: Env, _to: Address, _trophy: Trophy) {
+        panic!("minting disabled");
+    }
+
+    pub fn award(_env: Env, _player: Address, _achievement_id: u32) {
+        panic!("awards disabled");
+    }
+}
+
+#[test]
+fn test_reverting_trophy_contract_does_not_block_the_win() {
+    let s = setup();
+    s.client.set_trophy_contract(&Some(s.env.register(MockReverting, ())));
+    win(&s, 1);
+    assert_eq!(s.client.get_game(&1).winner, 1);
+    assert_eq!(s.hub.ended(&1), Some(true));
+}
+
+#[test]
+fn test_reverting_badge_contract_does_not_block_the_unlock() {
+    let s = setup();
+    s.client.set_badge_contract(&Some(s.env.register(MockReverting, ())));
+    win(&s, 1);
+    assert!(s.client.get_achievements(&s.player1).contains(Achievement::FirstWin));
+    assert_eq!(s.hub.ended(&1), Some(true));
+}
@@ -0,0 +1,261 @@
+// Admin controls: emergency pause, emergency cancellation, feature flags,
+// the threshold council, upgrades and sandbox mode.
+
+mod common;
+
+use common::{assert_error, setup};
+use poison_game::{
+    Error, HubIntent, Outcome, PoisonGameContract, PoisonGameContractClient, RatingStakeScale, SeriesPayout,
+    SettlementKind, StakeLimits, FEATURE_LITE, FEATURE_SERIES, UPGRADE_DELAY_LEDGERS,
+};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{vec, xdr::ToXdr, Address, BytesN, Symbol};
+
+#[test]
+fn test_pause_blocks_play_but_not_cancel() {
+    let s = setup();
+    s.client.start_game(&2, &s.player1, &s.player2, &100, &100);
+    s.start_and_commit(1);
+
+    s.client.pause();
+    assert!(s.client.is_paused());
+    assert_error(&s.client.try_start_game(&3, &s.player1, &s.player2, &100, &100), Error::ContractPaused);
+    assert_error(&s.client.try_attack(&1, &s.player1, &0), Error::ContractPaused);
+
+    // Refund paths stay open
+    s.client.cancel_game(&2, &s.player1);
+    assert!(s.hub.refunded(&2));
+
+    s.client.unpause();
+    s.play(1, 1, 0);
+    assert_eq!(s.client.get_game(&1).move_count, 4);
+}
+
+#[test]
+fn test_admin_cancel_refunds_and_archives_a_stuck_game() {
+    let s = setup();
+    s.start_and_commit(1);
+    let mut config = s.client.get_game(&1).config;
+    config.dispute_window = 20;
+    s.start_with_config(2, &config);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(2, attacker, tile);
+    }
+    s.play(1, 1, 0);
+
+    s.client.admin_cancel(&1, &7);
+    assert!(s.hub.refunded(&1));
+    assert_error(&s.client.try_get_game(&1), Error::GameNotFound);
+    assert_eq!(s.client.get_settlement_receipt(&1).unwrap().kind, SettlementKind::Refund);
+    let result = s.client.get_result(&1).unwrap();
+    assert_eq!((result.winner, result.outcome), (0, Outcome::Cancelled));
+    assert_error(&s.client.try_admin_cancel(&1, &7), Error::GameNotFound);
+
+    // A finished game held open to challenges is refunded, not paid out
+    s.client.admin_cancel(&2, &8);
+    assert!(s.hub.refunded(&2));
+    assert_eq!(s.hub.ended(&2), None);
+    let result = s.client.get_result(&2).unwrap();
+    assert_eq!((result.winner, result.outcome), (0, Outcome::Cancelled));
+    assert_eq!(s.client.get_result_count(&s.player1), 2);
+}
+
+#[test]
+fn test_feature_flags_gate_new_activity() {
+    let s = setup();
+    s.client.start_series(&1, &s.player1, &s.player2, &3, &90, &90, &SeriesPayout::PerGame);
+
+    s.client.set_disabled_features(&FEATURE_SERIES);
+    assert!(!s.client.is_feature_enabled(&FEATURE_SERIES));
+    assert!(s.client.is_feature_enabled(&FEATURE_LITE));
+    let result = s.client.try_start_series(&2, &s.player1, &s.player2, &3, &90, &90, &SeriesPayout::PerGame);
+    assert_error(&result, Error::FeatureDisabled);
+
+    // The running series can still play on
+    s.client.start_series_game(&1, &10, &s.player1);
+    s.client.set_disabled_features(&0);
+    s.client.start_series(&2, &s.player1, &s.player2, &3, &90, &90, &SeriesPayout::PerGame);
+}
+
+#[test]
+fn test_stake_limits_bound_new_sessions() {
+    let s = setup();
+    let bad = StakeLimits { min_stake: 50, max_stake: 10, max_ratio: 0 };
+    assert_error(&s.client.try_set_stake_limits(&bad), Error::InvalidConfig);
+    s.client.set_stake_limits(&StakeLimits { min_stake: 10, max_stake: 500, max_ratio: 2 });
+
+    assert_error(&s.client.try_start_game(&1, &s.player1, &s.player2, &5, &10), Error::StakeTooLow);
+    assert_error(&s.client.try_start_game(&1, &s.player1, &s.player2, &100, &501), Error::StakeTooHigh);
+    assert_error(&s.client.try_start_game(&1, &s.player1, &s.player2, &100, &201), Error::StakeTooHigh);
+    s.client.start_game(&1, &s.player1, &s.player2, &100, &200);
+}
+
+#[test]
+fn test_rating_scales_the_stake_a_player_may_wager() {
+    let s = setup();
+    assert_eq!(s.client.get_rating_stake_cap(&s.player1), None);
+    s.client.set_rating_stake_scale(&RatingStakeScale { base_stake: 100, stake_per_point: 10 });
+
+    // Both players are unrated, so each may stake at most the base
+    assert_error(&s.client.try_start_game(&1, &s.player1, &s.player2, &101, &100), Error::StakeTooHigh);
+    s.start_and_commit(1);
+    s.play(1, 1, 0);
+    s.play(1, 2, 10);
+    s.play(1, 1, 1);
+    s.play(1, 2, 11);
+    s.play(1, 1, 2);
+
+    // The winner's rating gain lifts their cap; the loser's stays at the base
+    let above = s.client.get_rating(&s.player1) - 1200;
+    let cap = 100 + 10 * above as i128;
+    assert_eq!(s.client.get_rating_stake_cap(&s.player1), Some(cap));
+    assert_eq!(s.client.get_rating_stake_cap(&s.player2), Some(100));
+    assert_error(&s.client.try_start_game(&2, &s.player1, &s.player2, &(cap + 1), &100), Error::StakeTooHigh);
+    s.client.start_game(&2, &s.player1, &s.player2, &cap, &100);
+
+    // Winning a friendly game, with nothing staked, earns no rating
+    s.client.start_game(&3, &s.player1, &s.player2, &0, &0);
+    s.commit_both(3);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(3, attacker, tile);
+    }
+    assert_eq!(s.client.get_game(&3).winner, 1);
+    assert_eq!(s.client.get_rating_stake_cap(&s.player1), Some(cap));
+}
+
+#[test]
+fn test_council_gates_sensitive_actions() {
+    let s = setup();
+    let (a, b, c) = (Address::generate(&s.env), Address::generate(&s.env), Address::generate(&s.env));
+    let council = vec![&s.env, a.clone(), b.clone(), c.clone()];
+    s.client.set_admin_council(&council, &2);
+
+    let new_hub = Address::generate(&s.env);
+    assert_error(&s.client.try_set_hub(&new_hub), Error::NotEnoughApprovals);
+
+    let action = (Symbol::new(&s.env, "set_hub"), new_hub.clone()).to_xdr(&s.env);
+    let hash: BytesN<32> = s.env.crypto().keccak256(&action).into();
+    assert_error(&s.client.try_approve_action(&s.admin, &hash), Error::NotSigner);
+    s.client.approve_action(&a, &hash);
+    assert_error(&s.client.try_set_hub(&new_hub), Error::NotEnoughApprovals);
+    assert_eq!(s.client.approve_action(&b, &hash), 2);
+
+    s.client.set_hub(&new_hub);
+    assert_eq!(s.client.get_hub(), new_hub);
+    // Approvals are spent
+    assert_eq!(s.client.get_approvals(&hash).len(), 0);
+    assert_error(&s.client.try_set_hub(&new_hub), Error::NotEnoughApprovals);
+}
+
+#[test]
+fn test_council_gates_pause_and_admin_handover() {
+    let s = setup();
+    let (a, b) = (Address::generate(&s.env), Address::generate(&s.env));
+    s.client.set_admin_council(&vec![&s.env, a.clone(), b.clone()], &2);
+
+    assert_error(&s.client.try_pause(), Error::NotEnoughApprovals);
+    assert_error(&s.client.try_set_sandbox_mode(&true), Error::NotEnoughApprovals);
+    assert_error(&s.client.try_set_stake_tiers(&vec![&s.env]), Error::NotEnoughApprovals);
+    assert_error(&s.client.try_set_fee_bps(&500), Error::NotEnoughApprovals);
+    assert_error(&s.client.try_set_champion(&s.player1, &Some(10), &0), Error::NotEnoughApprovals);
+    assert_error(&s.client.try_set_disabled_features(&FEATURE_SERIES), Error::NotEnoughApprovals);
+    let pause: BytesN<32> = s.env.crypto().keccak256(&(Symbol::new(&s.env, "pause"),).to_xdr(&s.env)).into();
+    s.client.approve_action(&a, &pause);
+    s.client.approve_action(&b, &pause);
+    s.client.pause();
+    assert!(s.client.is_paused());
+    assert_error(&s.client.try_unpause(), Error::NotEnoughApprovals);
+
+    let new_admin = Address::generate(&s.env);
+    assert_error(&s.client.try_set_admin(&new_admin), Error::NotEnoughApprovals);
+    let handover = (Symbol::new(&s.env, "set_admin"), new_admin.clone()).to_xdr(&s.env);
+    let handover: BytesN<32> = s.env.crypto().keccak256(&handover).into();
+    s.client.approve_action(&a, &handover);
+    s.client.approve_action(&b, &handover);
+    s.client.set_admin(&new_admin);
+    assert_eq!(s.client.get_admin(), new_admin);
+}
+
+#[test]
+fn test_upgrade_is_timelocked() {
+    let s = setup();
+    let hash = BytesN::from_array(&s.env, &[7u8; 32]);
+    assert_error(&s.client.try_execute_upgrade(), Error::NoPendingUpgrade);
+
+    let pending = s.client.schedule_upgrade(&hash);
+    assert_eq!(s.client.get_pending_upgrade(), Some(pending));
+
+    s.advance(UPGRADE_DELAY_LEDGERS - 1);
+    assert_error(&s.client.try_execute_upgrade(), Error::UpgradeNotReady);
+
+    s.client.cancel_upgrade();
+    assert_eq!(s.client.get_pending_upgrade(), None);
+    s.advance(1);
+    assert_error(&s.client.try_execute_upgrade(), Error::NoPendingUpgrade);
+}
+
+#[test]
+fn test_games_wait_for_a_vk() {
+    let s = setup();
+    let hub = s.client.get_hub();
+    let id = s.env.register(PoisonGameContract, (&s.admin, &hub));
+    let fresh = PoisonGameContractClient::new(&s.env, &id);
+
+    assert!(fresh.is_initialized());
+    assert!(!fresh.has_vk());
+    assert_error(&fresh.try_start_game(&1, &s.player1, &s.player2, &100, &100), Error::VkNotSet);
+}
+
+#[test]
+fn test_sandbox_records_hub_calls() {
+    let s = setup();
+    s.client.set_sandbox_mode(&true);
+    s.client.start_game(&1, &s.player1, &s.player2, &100, &50);
+    s.client.cancel_game(&1, &s.player1);
+
+    let calls = s.client.get_hub_intents();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(
+        calls.get(0).unwrap().intent,
+        HubIntent::StartGame(1, s.player1.clone(), s.player2.clone(), 100, 50),
+    );
+    assert_eq!(calls.get(1).unwrap().intent, HubIntent::RefundGame(1));
+    // Nothing reached the real hub
+    assert!(!s.hub.refunded(&1));
+}
+
+#[test]
+fn test_sandbox_mode_cannot_switch_under_a_live_session() {
+    let s = setup();
+    s.client.start_game(&1, &s.player1, &s.player2, &100, &100);
+    assert_error(&s.client.try_set_sandbox_mode(&true), Error::SessionsActive);
+
+    s.client.cancel_game(&1, &s.player1);
+    assert!(s.hub.refunded(&1));
+    s.client.set_sandbox_mode(&true);
+    assert!(s.client.is_sandbox_mode());
+}
+
+#[test]
+fn test_sandbox_mode_cannot_switch_while_points_are_held() {
+    let s = setup();
+    s.client.create_open_game(&1, &s.player1, &100);
+    assert_error(&s.client.try_set_sandbox_mode(&true), Error::SessionsActive);
+    s.client.cancel_open_game(&1);
+
+    s.client.start_series(&1, &s.player1, &s.player2, &1, &10, &10, &SeriesPayout::WinnerTakesAll);
+    assert_error(&s.client.try_set_sandbox_mode(&true), Error::SessionsActive);
+    s.client.start_series_game(&1, &10, &s.player1);
+    s.commit_both(10);
+    s.play(10, 1, 0);
+    s.play(10, 2, 10);
+    s.play(10, 1, 1);
+    s.play(10, 2, 11);
+    s.play(10, 1, 2);
+    assert!(s.client.get_series(&1).finished);
+    s.client.set_sandbox_mode(&false);
+
+    // Pooled points hold the switch until they are paid out
+    s.client.fund_treasury(&s.player1, &50);
+    assert_error(&s.client.try_set_sandbox_mode(&true), Error::SessionsActive);
+}
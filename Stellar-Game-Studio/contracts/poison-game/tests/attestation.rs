@@ -0,0 +1,59 @@
+// Attested results and the result digest of finished sessions.
+
+mod common;
+
+use common::{assert_error, setup, Setup};
+use poison_game::{Error, ResultAttested};
+use soroban_sdk::testutils::{Address as _, Events as _};
+use soroban_sdk::{Address, Event as _};
+
+/// Player 1 uncovers both Poisons and the Shield on player 2's board.
+fn player1_wins(s: &Setup, session_id: u32) {
+    s.start_and_commit(session_id);
+    s.play(session_id, 1, 0);
+    s.play(session_id, 2, 10);
+    s.play(session_id, 1, 1);
+    s.play(session_id, 2, 11);
+    s.play(session_id, 1, 2);
+}
+
+#[test]
+fn test_attestor_cosigns_a_finished_result() {
+    let s = setup();
+    player1_wins(&s, 1);
+    assert_error(&s.client.try_attest_result(&1), Error::NoAttestor);
+
+    let attestor = Address::generate(&s.env);
+    s.client.set_attestor(&Some(attestor.clone()));
+    let attestation = s.client.attest_result(&1);
+    assert_eq!(attestation.session_id, 1);
+    assert_eq!(attestation.attestor, attestor);
+    assert_eq!(s.env.events().all(), std::vec![ResultAttested {
+        session_id:  1,
+        result_hash: attestation.result_hash.clone(),
+        attestor:    attestor.clone(),
+    }
+    .to_xdr(&s.env, &s.client.address)]);
+    assert_eq!(s.client.get_attestation(&1), Some(attestation));
+
+    s.client.set_attestor(&None);
+    assert_error(&s.client.try_attest_result(&1), Error::NoAttestor);
+}
+
+#[test]
+fn test_only_finished_sessions_are_attested_or_digested() {
+    let s = setup();
+    s.client.set_attestor(&Some(Address::generate(&s.env)));
+    assert_error(&s.client.try_attest_result(&1), Error::GameNotFound);
+
+    s.start_and_commit(1);
+    assert_error(&s.client.try_attest_result(&1), Error::WrongPhase);
+    assert_error(&s.client.try_get_result_digest(&1), Error::WrongPhase);
+    assert_eq!(s.client.get_attestation(&1), None);
+
+    player1_wins(&s, 2);
+    player1_wins(&s, 3);
+    let digest = s.client.get_result_digest(&2);
+    assert_eq!(s.client.get_result_digest(&2), digest);
+    assert_ne!(s.client.get_result_digest(&3), digest);
+}
@@ -0,0 +1,61 @@
+// Practice games against a registered bot contract playing as player2.
+
+mod common;
+
+use common::{assert_error, commitment, setup, BOARD};
+use poison_game::testutils::{mock_proof, reveal_inputs};
+use poison_game::{BoardMask, Error, Phase};
+use soroban_sdk::{contract, contractimpl, Bytes, BytesN, Env};
+
+/// Plays `BOARD` under commitment 2 and attacks the highest unrevealed tile.
+#[contract]
+pub struct MockBot;
+
+#[contractimpl]
+impl MockBot {
+    pub fn board_commitment(env: Env, _session_id: u32) -> BytesN<32> {
+        commitment(&env, 2)
+    }
+
+    pub fn choose_attack(_env: Env, _session_id: u32, revealed: BoardMask) -> u32 {
+        (0..15).rev().find(|&t| !revealed.is_revealed(t)).unwrap()
+    }
+
+    pub fn respond(env: Env, session_id: u32, tile_index: u32) -> (u32, Bytes) {
+        let tile_type = BOARD[tile_index as usize];
+        let owner = env.current_contract_address();
+        let inputs = reveal_inputs(&env, &commitment(&env, 2), &owner, session_id, tile_index, tile_type, 2, 1, 0);
+        (tile_type, mock_proof(&env, &inputs))
+    }
+}
+
+#[test]
+fn test_bot_answers_and_attacks_through_play_bot_turn() {
+    let s = setup();
+    let bot = s.env.register(MockBot, ());
+    assert_error(&s.client.try_start_bot_game(&1, &s.player1, &bot), Error::NotPlayer);
+    s.client.register_bot(&bot);
+
+    s.client.start_bot_game(&1, &s.player1, &bot);
+    let game = s.client.get_game(&1);
+    assert_eq!((game.player2.clone(), game.player2_committed, game.player1_points), (bot.clone(), true, 0));
+    s.client.commit_board(&1, &s.player1, &commitment(&s.env, 1));
+    assert_error(&s.client.try_play_bot_turn(&1), Error::NotYourTurn);
+
+    // The bot answers each of player1's attacks, then attacks back
+    for tile in [5, 0, 1, 2] {
+        s.client.attack(&1, &s.player1, &tile);
+        s.client.play_bot_turn(&1);
+        if s.client.get_game(&1).winner != 0 { break; }
+
+        assert_eq!(s.client.get_game(&1).current_turn, 2);
+        s.client.play_bot_turn(&1);
+        let pending = s.client.get_game(&1).pending_tiles.get(0).unwrap();
+        let tile_type = BOARD[pending as usize];
+        s.client.respond_to_attack(&1, &s.player1, &tile_type, &s.proof(1, 1, pending, tile_type));
+    }
+    let game = s.client.get_game(&1);
+    assert_eq!((game.phase, game.winner), (Phase::Finished, 1));
+    assert_eq!(game.p1_revealed.len(), 3);
+    assert!(game.p1_mask.is_revealed(14));
+}
@@ -0,0 +1,205 @@
+// Shared harness for the integration suite: a recording mock GameHub, a
+// deployed contract wired to it, and helpers that play moves with proofs
+// the `testutils` mock verifier accepts.
+
+#![allow(dead_code)]
+
+use poison_game::testutils::{mock_proof, reveal_inputs};
+use poison_game::{Error, GameConfig, GameScore, PoisonGameContract, PoisonGameContractClient};
+use soroban_sdk::testutils::{Address as _, Ledger as _, LedgerInfo};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Bytes, BytesN, Env};
+
+// ============================================================================
+// Mock GameHub — records what it was asked to do
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone)]
+enum HubKey {
+    Ended(u32),
+    EndCalls(u32),
+    Score(u32),
+    Refunded(u32),
+    Locked(Address),
+    Released(Address),
+}
+
+#[contract]
+pub struct MockGameHub;
+
+#[contractimpl]
+impl MockGameHub {
+    pub fn start_game(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _player1: Address,
+        _player2: Address,
+        _player1_points: i128,
+        _player2_points: i128,
+    ) {
+    }
+
+    pub fn end_game(env: Env, session_id: u32, player1_won: bool) {
+        env.storage().instance().set(&HubKey::Ended(session_id), &player1_won);
+        let calls = Self::end_calls(env.clone(), session_id);
+        env.storage().instance().set(&HubKey::EndCalls(session_id), &(calls + 1));
+    }
+
+    pub fn end_game_v2(env: Env, session_id: u32, score: GameScore) {
+        env.storage().instance().set(&HubKey::Score(session_id), &score);
+    }
+
+    pub fn refund_game(env: Env, session_id: u32) {
+        env.storage().instance().set(&HubKey::Refunded(session_id), &true);
+    }
+
+    pub fn lock_points(env: Env, _game_id: Address, player: Address, points: i128) {
+        let key = HubKey::Locked(player);
+        let total: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(total + points));
+    }
+
+    pub fn release_points(env: Env, _game_id: Address, player: Address, points: i128) {
+        let key = HubKey::Released(player);
+        let total: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(total + points));
+    }
+
+    pub fn ended(env: Env, session_id: u32) -> Option<bool> {
+        env.storage().instance().get(&HubKey::Ended(session_id))
+    }
+
+    pub fn score(env: Env, session_id: u32) -> Option<GameScore> {
+        env.storage().instance().get(&HubKey::Score(session_id))
+    }
+
+    pub fn end_calls(env: Env, session_id: u32) -> u32 {
+        env.storage().instance().get(&HubKey::EndCalls(session_id)).unwrap_or(0)
+    }
+
+    pub fn refunded(env: Env, session_id: u32) -> bool {
+        env.storage().instance().get(&HubKey::Refunded(session_id)).unwrap_or(false)
+    }
+
+    pub fn locked(env: Env, player: Address) -> i128 {
+        env.storage().instance().get(&HubKey::Locked(player)).unwrap_or(0)
+    }
+
+    pub fn released(env: Env, player: Address) -> i128 {
+        env.storage().instance().get(&HubKey::Released(player)).unwrap_or(0)
+    }
+}
+
+// ============================================================================
+// Setup
+// ============================================================================
+
+pub struct Setup {
+    pub env:     Env,
+    pub client:  PoisonGameContractClient<'static>,
+    pub hub:     MockGameHubClient<'static>,
+    pub admin:   Address,
+    pub player1: Address,
+    pub player2: Address,
+}
+
+/// Default boards: Poison at 0 and 1, Shield at 2, everything else Normal.
+pub const BOARD: [u32; 15] = [1, 1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+pub fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    env.ledger().set(LedgerInfo {
+        timestamp: 1441065600,
+        protocol_version: 25,
+        sequence_number: 100,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: u32::MAX / 2,
+        min_persistent_entry_ttl: u32::MAX / 2,
+        max_entry_ttl: u32::MAX / 2,
+    });
+
+    let hub_addr = env.register(MockGameHub, ());
+    let hub = MockGameHubClient::new(&env, &hub_addr);
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PoisonGameContract, (&admin, &hub_addr));
+    let client = PoisonGameContractClient::new(&env, &contract_id);
+    client.init_vk(&admin, &Bytes::from_array(&env, &[0u8; 4]));
+
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+    Setup { env, client, hub, admin, player1, player2 }
+}
+
+pub fn commitment(env: &Env, player_slot: u32) -> BytesN<32> {
+    BytesN::from_array(env, &[player_slot as u8; 32])
+}
+
+impl Setup {
+    pub fn advance(&self, ledgers: u32) {
+        self.env.ledger().with_mut(|l| l.sequence_number += ledgers);
+    }
+
+    /// Start a default 100/100 game and commit both boards.
+    pub fn start_and_commit(&self, session_id: u32) {
+        self.client.start_game(&session_id, &self.player1, &self.player2, &100, &100);
+        self.commit_both(session_id);
+    }
+
+    pub fn start_with_config(&self, session_id: u32, config: &GameConfig) {
+        self.client.start_game_with_config(&session_id, &self.player1, &self.player2, &100, &100, config);
+        self.commit_both(session_id);
+    }
+
+    pub fn commit_both(&self, session_id: u32) {
+        self.client.commit_board(&session_id, &self.player1, &commitment(&self.env, 1));
+        self.client.commit_board(&session_id, &self.player2, &commitment(&self.env, 2));
+    }
+
+    pub fn player(&self, slot: u32) -> &Address {
+        if slot == 1 { &self.player1 } else { &self.player2 }
+    }
+
+    /// Proof for the defender's answer about `tile_index` on their board.
+    pub fn proof(&self, session_id: u32, defender_slot: u32, tile_index: u32, tile_type: u32) -> Bytes {
+        let game = self.client.get_game(&session_id);
+        let inputs = reveal_inputs(
+            &self.env,
+            &commitment(&self.env, defender_slot),
+            &game.board_owners.get(defender_slot - 1).unwrap(),
+            session_id,
+            tile_index,
+            tile_type,
+            game.config.poison_on(defender_slot),
+            game.config.shield_count,
+            game.config.decoy_count,
+        );
+        mock_proof(&self.env, &inputs)
+    }
+
+    /// `attacker_slot` attacks `tile_index` and the defender answers
+    /// truthfully from `BOARD`.
+    pub fn play(&self, session_id: u32, attacker_slot: u32, tile_index: u32) {
+        let defender_slot = 3 - attacker_slot;
+        let tile_type = BOARD[tile_index as usize];
+        self.client.attack(&session_id, self.player(attacker_slot), &tile_index);
+        let proof = self.proof(session_id, defender_slot, tile_index, tile_type);
+        self.client.respond_to_attack(&session_id, self.player(defender_slot), &tile_type, &proof);
+    }
+}
+
+/// Assert that a `try_` call failed with a specific contract error.
+pub fn assert_error<T, E>(
+    result: &Result<Result<T, E>, Result<Error, soroban_sdk::InvokeError>>,
+    expected: Error,
+) {
+    match result {
+        Err(Ok(actual)) => assert_eq!(*actual, expected),
+        Err(Err(_))     => panic!("expected {:?}, got an invocation error", expected),
+        Ok(_)           => panic!("expected {:?}, but the call succeeded", expected),
+    }
+}
@@ -0,0 +1,79 @@
+// Free-for-all: turns rotate over the players still standing and the last
+// board standing takes the pot.
+
+mod common;
+
+use common::{assert_error, commitment, setup, Setup, BOARD};
+use poison_game::testutils::{mock_proof, reveal_inputs};
+use poison_game::{Error, Phase, FEATURE_FFA};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{vec, Address, Vec};
+
+fn start(s: &Setup, players: &Vec<Address>) {
+    s.client.start_ffa(&1, players, &100);
+    for (i, player) in players.iter().enumerate() {
+        s.client.commit_ffa_board(&1, &player, &commitment(&s.env, i as u32 + 1));
+    }
+}
+
+/// `attacker` attacks `tile_index` on `target`'s board and `target` answers from `BOARD`.
+fn play(s: &Setup, players: &Vec<Address>, attacker: u32, target: u32, tile_index: u32) {
+    let tile_type = BOARD[tile_index as usize];
+    let defender = players.get(target - 1).unwrap();
+    s.client.ffa_attack(&1, &players.get(attacker - 1).unwrap(), &defender, &tile_index);
+    let inputs = reveal_inputs(&s.env, &commitment(&s.env, target), &defender, 1, tile_index, tile_type, 2, 1, 0);
+    s.client.ffa_respond(&1, &defender, &tile_type, &mock_proof(&s.env, &inputs));
+}
+
+#[test]
+fn test_last_board_standing_takes_the_pot() {
+    let s = setup();
+    let player3 = Address::generate(&s.env);
+    let players = vec![&s.env, s.player1.clone(), s.player2.clone(), player3.clone()];
+    start(&s, &players);
+    assert_eq!(s.hub.locked(&player3), 100);
+    assert_eq!(s.client.get_ffa(&1).phase, Phase::Playing);
+
+    play(&s, &players, 1, 3, 0);
+    assert_error(&s.client.try_ffa_attack(&1, &s.player1, &player3, &1), Error::NotYourTurn);
+    play(&s, &players, 2, 3, 1);
+    play(&s, &players, 3, 2, 3);
+    // Finding player3's Shield eliminates them and keeps the turn with player1
+    play(&s, &players, 1, 3, 2);
+    let game = s.client.get_ffa(&1);
+    assert_eq!((game.eliminated, game.current_turn), (0b100, 1));
+    assert_error(&s.client.try_ffa_attack(&1, &s.player1, &player3, &4), Error::PlayerEliminated);
+
+    // player3 no longer takes a turn
+    play(&s, &players, 1, 2, 0);
+    play(&s, &players, 2, 1, 3);
+    play(&s, &players, 1, 2, 1);
+    play(&s, &players, 2, 1, 4);
+    play(&s, &players, 1, 2, 2);
+
+    let game = s.client.get_ffa(&1);
+    assert_eq!((game.phase, game.winner), (Phase::Finished, 1));
+    assert_eq!(s.hub.released(&s.player1), 300);
+}
+
+#[test]
+fn test_ffa_start_rules_and_cancel() {
+    let s = setup();
+    let player3 = Address::generate(&s.env);
+    let two = vec![&s.env, s.player1.clone(), s.player2.clone()];
+    assert_error(&s.client.try_start_ffa(&1, &two, &100), Error::InvalidConfig);
+    let repeated = vec![&s.env, s.player1.clone(), s.player2.clone(), s.player1.clone()];
+    assert_error(&s.client.try_start_ffa(&1, &repeated, &100), Error::SelfPlay);
+
+    let players = vec![&s.env, s.player1.clone(), s.player2.clone(), player3.clone()];
+    s.client.set_disabled_features(&FEATURE_FFA);
+    assert_error(&s.client.try_start_ffa(&1, &players, &100), Error::FeatureDisabled);
+    s.client.set_disabled_features(&0);
+
+    s.client.start_ffa(&1, &players, &100);
+    assert_error(&s.client.try_start_ffa(&1, &players, &100), Error::SessionExists);
+    s.client.commit_ffa_board(&1, &s.player1, &commitment(&s.env, 1));
+    s.client.cancel_ffa(&1, &player3);
+    assert_eq!(s.hub.released(&s.player2), 100);
+    assert_error(&s.client.try_get_ffa(&1), Error::GameNotFound);
+}
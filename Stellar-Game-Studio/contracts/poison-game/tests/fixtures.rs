@@ -0,0 +1,42 @@
+// Seeded fixtures replay to their recorded result.
+
+mod common;
+
+use common::setup;
+use poison_game::testutils::generate_fixture;
+use poison_game::Phase;
+
+#[test]
+fn test_fixtures_replay_to_expected_winner() {
+    for seed in [1u64, 7, 42, 1337] {
+        let s = setup();
+        let owners = [s.player1.clone(), s.player2.clone()];
+        let fixture = generate_fixture(&s.env, seed, &owners, 1);
+
+        s.client.start_game(&1, &s.player1, &s.player2, &100, &100);
+        s.client.commit_board(&1, &s.player1, &fixture.commitments[0]);
+        s.client.commit_board(&1, &s.player2, &fixture.commitments[1]);
+
+        for m in &fixture.moves {
+            let defender = 3 - m.attacker;
+            s.client.attack(&1, s.player(m.attacker), &m.tile_index);
+            s.client.respond_to_attack(&1, s.player(defender), &m.tile_type, &m.proof);
+        }
+
+        let game = s.client.get_game(&1);
+        assert_eq!(game.phase, Phase::Finished, "seed {seed}");
+        assert_eq!(game.winner, fixture.winner, "seed {seed}");
+        assert_eq!(s.client.get_move_log(&1).len() as usize, fixture.moves.len());
+    }
+}
+
+#[test]
+fn test_fixture_is_deterministic() {
+    let s = setup();
+    s.env.cost_estimate().budget().reset_unlimited();
+    let owners = [s.player1.clone(), s.player2.clone()];
+    let a = generate_fixture(&s.env, 99, &owners, 1);
+    let b = generate_fixture(&s.env, 99, &owners, 1);
+    assert_eq!(a.to_json(), b.to_json());
+    assert_ne!(a.to_json(), generate_fixture(&s.env, 100, &owners, 1).to_json());
+}
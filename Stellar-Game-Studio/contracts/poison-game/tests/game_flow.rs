@@ -0,0 +1,962 @@
+// End-to-end game flows against the mock verifier and mock GameHub.
+
+mod common;
+
+use common::{assert_error, commitment, setup, BOARD};
+use poison_game::testutils::{lite_inputs, mock_proof, reveal_inputs, scan_inputs};
+use poison_game::{
+    CommitmentScheme, DataKey, Error, ErrorCategory, GameConfig, GameScore, GameSummary, GameTemplate, Outcome, Phase, PlayerSession, RatingDecay, SeasonRecord,
+    SessionStatus, StakeTier, TileStatus, WinCondition, CODES_VERSION, HUB_V1, HUB_V2,
+};
+use soroban_sdk::testutils::storage::Temporary as _;
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{vec, Address, BytesN, Env, Symbol};
+
+/// Ledger ceiling for a single contract call.
+const CPU_LIMIT: u64 = 100_000_000;
+
+fn timed_config(env: &Env, time_bank: u32) -> GameConfig {
+    GameConfig {
+        poison_count:      2,
+        shield_count:      1,
+        player2_hub:       None,
+        reveal_delay:      0,
+        broadcast:         false,
+        rules_hash:        None,
+        time_bank,
+        salvo_size:        0,
+        poison_penalty:    false,
+        max_bonus_attacks: 0,
+        decoy_count:       0,
+        lite:              false,
+        response_window:   0,
+        slash_bps:         0,
+        dispute_window:    0,
+        simultaneous:      false,
+        max_rounds:        0,
+        win_condition:     WinCondition::AllSpecials,
+        board_poison:      vec![env],
+        commitment_scheme: CommitmentScheme::Pedersen,
+        commit_window:     0,
+        hub:               None,
+    }
+}
+
+#[test]
+fn test_full_game_player1_wins() {
+    let s = setup();
+    s.start_and_commit(1);
+    assert_eq!(s.client.get_game(&1).phase, Phase::Playing);
+
+    s.play(1, 1, 0);  // Poison, turn passes
+    s.play(1, 2, 10); // Normal
+    s.play(1, 1, 1);  // Poison
+    s.advance(4);
+    s.play(1, 2, 11); // Normal
+    s.play(1, 1, 2);  // Shield — last special
+
+    let game = s.client.get_game(&1);
+    assert_eq!(game.phase, Phase::Finished);
+    assert_eq!(game.winner, 1);
+    assert_eq!(game.p2_revealed.len(), 3);
+    assert_eq!(s.hub.ended(&1), Some(true));
+
+    let result = s.client.get_result(&1).unwrap();
+    assert_eq!(result.winner, 1);
+    assert_eq!(result.tiles_found, 3);
+    assert_eq!(result.outcome, Outcome::AllSpecialsFound);
+    assert_eq!(s.client.get_move_log(&1).len(), 5);
+    assert_eq!(s.client.get_player_stats(&s.player1).wins, 1);
+    assert_eq!(s.client.get_player_stats(&s.player2).losses, 1);
+
+    let pm = s.client.get_postmortem(&1);
+    assert_eq!(pm.outcome, Outcome::AllSpecialsFound);
+    assert_eq!(pm.specials.len(), 3);
+    assert_eq!(pm.decisive_turn, pm.specials.last().unwrap().turn);
+    assert_eq!((pm.player1_reveals, pm.player2_reveals), (3, 2));
+    assert_eq!((pm.duration_ledgers, pm.longest_gap), (4, 4));
+}
+
+#[test]
+fn test_game_summary_tracks_the_live_game() {
+    let s = setup();
+    assert_error(&s.client.try_get_game_summary(&1), Error::GameNotFound);
+    s.start_and_commit(1);
+    s.play(1, 1, 0);  // Poison, turn passes
+    s.play(1, 2, 10); // Normal
+
+    assert_eq!(s.client.get_game_summary(&1), GameSummary {
+        session_id:       1,
+        player1:          s.player1.clone(),
+        player2:          s.player2.clone(),
+        phase:            Phase::Playing,
+        current_turn:     1,
+        player1_specials: 1,
+        player2_specials: 0,
+        winner:           0,
+    });
+}
+
+#[test]
+fn test_shield_keeps_the_turn() {
+    let s = setup();
+    s.start_and_commit(1);
+
+    s.play(1, 1, 2);
+    assert_eq!(s.client.get_game(&1).current_turn, 1);
+    s.play(1, 1, 5);
+    assert_eq!(s.client.get_game(&1).current_turn, 2);
+}
+
+#[test]
+fn test_shield_chain_is_capped() {
+    let s = setup();
+    let mut config = timed_config(&s.env, 0);
+    config.shield_count = 3;
+    config.max_bonus_attacks = 1;
+    s.start_with_config(1, &config);
+
+    s.play(1, 1, 2);
+    let game = s.client.get_game(&1);
+    assert_eq!((game.current_turn, game.consecutive_bonus), (1, 1));
+
+    // A second Shield in a row no longer earns a bonus attack
+    s.client.attack(&1, &s.player1, &3);
+    let proof = s.proof(1, 2, 3, 2);
+    s.client.respond_to_attack(&1, &s.player2, &2, &proof);
+    let game = s.client.get_game(&1);
+    assert_eq!((game.current_turn, game.consecutive_bonus), (2, 0));
+}
+
+#[test]
+fn test_turn_and_tile_rules() {
+    let s = setup();
+    s.start_and_commit(1);
+
+    assert_error(&s.client.try_attack(&1, &s.player2, &0), Error::NotYourTurn);
+    assert_error(&s.client.try_attack(&1, &s.player1, &15), Error::InvalidTileIndex);
+
+    s.play(1, 1, 3);
+    s.play(1, 2, 3);
+    assert_error(&s.client.try_attack(&1, &s.player1, &3), Error::TileAlreadyRevealed);
+}
+
+#[test]
+fn test_wrong_proof_rejected() {
+    let s = setup();
+    s.start_and_commit(1);
+    s.client.attack(&1, &s.player1, &0);
+
+    // Proof for the real tile type cannot be replayed for a lie
+    let proof = s.proof(1, 2, 0, BOARD[0]);
+    assert_error(&s.client.try_respond_to_attack(&1, &s.player2, &0, &proof), Error::InvalidProof);
+
+    let short = soroban_sdk::Bytes::from_array(&s.env, &[0u8; 32]);
+    assert_error(&s.client.try_respond_to_attack(&1, &s.player2, &1, &short), Error::ProofWrongLength);
+}
+
+#[test]
+fn test_result_digest_covers_the_archived_result() {
+    let s = setup();
+    s.start_and_commit(1);
+    assert_error(&s.client.try_get_result_digest(&1), Error::WrongPhase);
+    assert_error(&s.client.try_get_result_digest(&2), Error::GameNotFound);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+
+    let result = s.client.get_result(&1).unwrap();
+    assert_eq!(result.move_count, s.client.get_game(&1).move_count);
+    let preimage = (
+        1u32, s.player1.clone(), s.player2.clone(), 100i128, 100i128, 1u32, result.move_count, result.finished_ledger,
+    )
+        .to_xdr(&s.env);
+    let expected: BytesN<32> = s.env.crypto().keccak256(&preimage).into();
+    assert_eq!(s.client.get_result_digest(&1), expected);
+}
+
+#[test]
+fn test_cancel_before_commits_refunds() {
+    let s = setup();
+    s.client.start_game(&1, &s.player1, &s.player2, &100, &100);
+    s.client.commit_board(&1, &s.player1, &commitment(&s.env, 1));
+
+    s.client.cancel_game(&1, &s.player2);
+    assert!(s.hub.refunded(&1));
+    assert_error(&s.client.try_get_game(&1), Error::GameNotFound);
+}
+
+#[test]
+fn test_uncommitted_game_expires_after_commit_window() {
+    let s = setup();
+    let mut config = timed_config(&s.env, 0);
+    s.client.start_game(&1, &s.player1, &s.player2, &100, &100);
+    assert_error(&s.client.try_expire_uncommitted(&1), Error::NoTimeControl);
+
+    config.commit_window = 10;
+    s.client.start_game_with_config(&2, &s.player1, &s.player2, &100, &100, &config);
+    s.client.commit_board(&2, &s.player1, &commitment(&s.env, 1));
+    s.advance(10);
+    assert_error(&s.client.try_expire_uncommitted(&2), Error::ClockNotExpired);
+    s.advance(1);
+    s.client.expire_uncommitted(&2);
+    assert!(s.hub.refunded(&2));
+    assert_error(&s.client.try_get_game(&2), Error::GameNotFound);
+
+    // Committed in time: the window no longer applies
+    s.start_with_config(3, &config);
+    s.advance(20);
+    assert_error(&s.client.try_expire_uncommitted(&3), Error::WrongPhase);
+}
+
+#[test]
+fn test_cancel_after_play_begins_rejected() {
+    let s = setup();
+    s.start_and_commit(1);
+    assert_error(&s.client.try_cancel_game(&1, &s.player1), Error::WrongPhase);
+}
+
+#[test]
+fn test_timeout_by_flag_fall() {
+    let s = setup();
+    s.start_with_config(1, &timed_config(&s.env, 50));
+    s.play(1, 1, 5);
+
+    // Player 2 is on move and lets the clock run out
+    s.advance(30);
+    assert_error(&s.client.try_claim_flag_fall(&1, &s.player1), Error::ClockNotExpired);
+    s.advance(20);
+    assert_error(&s.client.try_attack(&1, &s.player2, &5), Error::OutOfTime);
+
+    s.client.claim_flag_fall(&1, &s.player1);
+    let game = s.client.get_game(&1);
+    assert_eq!(game.winner, 1);
+    assert_eq!(s.hub.ended(&1), Some(true));
+    let pm = s.client.get_postmortem(&1);
+    assert_eq!(pm.outcome, Outcome::FlagFall);
+    assert_eq!(pm.decisive_turn, 0);
+}
+
+#[test]
+fn test_stake_tiers_impose_their_time_control() {
+    let s = setup();
+    let tier = |min_stake, board_size, time_bank| StakeTier { min_stake, board_size, time_bank };
+    // Only the 15-tile board has a circuit
+    assert_error(&s.client.try_set_stake_tiers(&vec![&s.env, tier(100, 20, 0)]), Error::InvalidConfig);
+    assert_error(
+        &s.client.try_set_stake_tiers(&vec![&s.env, tier(100, 15, 60), tier(100, 15, 30)]),
+        Error::InvalidConfig,
+    );
+    s.client.set_stake_tiers(&vec![&s.env, tier(100, 15, 60), tier(500, 15, 30)]);
+
+    // Below every tier the game stays untimed
+    s.client.start_game(&1, &s.player1, &s.player2, &50, &50);
+    assert_eq!(s.client.get_game(&1).config.time_bank, 0);
+
+    // An untimed session at a tier runs on the tier clock
+    s.client.start_game(&2, &s.player1, &s.player2, &100, &100);
+    let game = s.client.get_game(&2);
+    assert_eq!((game.config.time_bank, game.player1_time_left), (60, 60));
+
+    // A shorter clock is kept; a longer one is refused
+    s.client.start_game_with_config(&3, &s.player1, &s.player2, &500, &100, &timed_config(&s.env, 20));
+    assert_eq!(s.client.get_game(&3).config.time_bank, 20);
+    let result = s.client.try_start_game_with_config(&4, &s.player1, &s.player2, &500, &100, &timed_config(&s.env, 60));
+    assert_error(&result, Error::InvalidConfig);
+}
+
+#[test]
+fn test_turn_limit_goes_to_the_tiebreak() {
+    let s = setup();
+    let mut config = timed_config(&s.env, 0);
+    config.max_rounds = 2;
+    s.start_with_config(1, &config);
+    s.play(1, 1, 0);  // Poison: 2 points
+    s.play(1, 2, 10); // round 1
+    s.play(1, 1, 3);
+    s.play(1, 2, 11); // round 2: the limit
+
+    let game = s.client.get_game(&1);
+    assert_eq!((game.phase, game.winner, game.rounds_played), (Phase::Finished, 1, 2));
+    assert_eq!(s.hub.ended(&1), Some(true));
+    assert_eq!(s.client.get_result(&1).unwrap().outcome, Outcome::TurnLimit);
+
+    // Level scores are a draw and refund both stakes
+    config.max_rounds = 1;
+    s.start_with_config(2, &config);
+    s.play(2, 1, 2);  // Shield: 1 point, bonus attack
+    s.play(2, 1, 3);
+    s.play(2, 2, 2);
+    s.play(2, 2, 10);
+    let game = s.client.get_game(&2);
+    assert_eq!((game.phase, game.winner), (Phase::Finished, 0));
+    assert!(s.hub.refunded(&2));
+
+    config.lite = true;
+    assert_error(&s.client.try_start_game_with_config(&3, &s.player1, &s.player2, &100, &100, &config), Error::InvalidConfig);
+}
+
+#[test]
+fn test_turn_limit_draw_is_recorded() {
+    let s = setup();
+    s.start_and_commit(1);
+    s.play(1, 1, 0);
+    s.play(1, 2, 10);
+    s.play(1, 1, 1);
+    s.play(1, 2, 11);
+    s.play(1, 1, 2);
+    assert_eq!(s.client.get_player_stats(&s.player1).win_streak, 1);
+
+    // One Shield each after a single round is a level tiebreak
+    let mut config = timed_config(&s.env, 0);
+    config.max_rounds = 1;
+    s.start_with_config(2, &config);
+    s.play(2, 1, 2);
+    s.play(2, 1, 3);
+    s.play(2, 2, 2);
+    s.play(2, 2, 10);
+    assert_eq!(s.client.get_game(&2).winner, 0);
+
+    let p1 = s.client.get_player_stats(&s.player1);
+    let p2 = s.client.get_player_stats(&s.player2);
+    assert_eq!((p1.wins, p1.draws, p1.win_streak), (1, 1, 0));
+    assert_eq!((p2.losses, p2.draws), (1, 1));
+
+    // The draw is archived and scores half a point each: 1216 vs 1184 before
+    let result = s.client.get_result(&2).unwrap();
+    assert_eq!((result.winner, result.outcome), (0, Outcome::DrawLevelTiebreak));
+    assert_eq!((s.client.get_rating(&s.player1), s.client.get_rating(&s.player2)), (1215, 1185));
+}
+
+#[test]
+fn test_dead_position_is_drawn() {
+    let s = setup();
+    s.start_and_commit(1);
+    // Every answer claims Normal, so neither board can hold its specials for long
+    for tile in 0..13 {
+        for attacker in [1, 2] {
+            assert_eq!(s.client.get_game(&1).phase, Phase::Playing);
+            s.client.attack(&1, s.player(attacker), &tile);
+            let proof = s.proof(1, 3 - attacker, tile, 0);
+            s.client.respond_to_attack(&1, s.player(3 - attacker), &0, &proof);
+        }
+    }
+    let game = s.client.get_game(&1);
+    assert_eq!((game.phase, game.winner), (Phase::Finished, 0));
+    assert!(s.hub.refunded(&1));
+    assert_eq!(s.hub.ended(&1), None);
+    assert_eq!(s.client.get_result(&1).unwrap().outcome, Outcome::DrawBoardExhausted);
+}
+
+#[test]
+fn test_v2_hub_is_sent_the_full_score() {
+    let s = setup();
+    assert_eq!(s.client.get_hub_version(&s.hub.address), HUB_V1);
+    assert_error(&s.client.try_set_hub_version(&s.hub.address, &3), Error::InvalidConfig);
+    s.client.set_hub_version(&s.hub.address, &HUB_V2);
+
+    s.start_and_commit(1);
+    // Player2's Shield keeps the turn for one more attack
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 2), (2, 11), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+    let score = s.hub.score(&1).unwrap();
+    assert_eq!(score, GameScore {
+        winner:           1,
+        loser:            2,
+        player1_specials: 3,
+        player2_specials: 1,
+        moves:            s.client.get_game(&1).move_count,
+        draw:             false,
+    });
+    assert_eq!(s.hub.ended(&1), None);
+
+    // A level tiebreak is reported as a draw, not refunded
+    let mut config = timed_config(&s.env, 0);
+    config.max_rounds = 1;
+    s.start_with_config(2, &config);
+    s.play(2, 1, 10);
+    s.play(2, 2, 10);
+    assert_eq!(s.hub.score(&2).map(|score| (score.winner, score.draw)), Some((0, true)));
+    assert!(!s.hub.refunded(&2));
+
+    // Cancelled sessions are still refunded
+    s.client.start_game(&3, &s.player1, &s.player2, &100, &100);
+    s.client.cancel_game(&3, &s.player1);
+    assert!(s.hub.refunded(&3));
+}
+
+#[test]
+fn test_win_condition_presets() {
+    let s = setup();
+    let mut config = timed_config(&s.env, 0);
+    config.win_condition = WinCondition::FirstPoisonLoses;
+    s.start_with_config(1, &config);
+    s.play(1, 1, 5);
+    s.play(1, 2, 0);  // player2 uncovers a Poison and loses
+    let game = s.client.get_game(&1);
+    assert_eq!((game.phase, game.winner), (Phase::Finished, 1));
+    assert_eq!(s.client.get_result(&1).unwrap().outcome, Outcome::PoisonFound);
+
+    // Finding every special ends nothing; the round limit decides
+    config.win_condition = WinCondition::MostSpecialsAfterNRounds;
+    assert_error(&s.client.try_start_game_with_config(&2, &s.player1, &s.player2, &100, &100, &config), Error::InvalidConfig);
+    config.max_rounds = 3;
+    s.start_with_config(2, &config);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2), (1, 3)] {
+        s.play(2, attacker, tile);
+    }
+    assert_eq!(s.client.get_game(&2).phase, Phase::Playing);
+    s.play(2, 2, 12);
+    let game = s.client.get_game(&2);
+    assert_eq!((game.phase, game.winner), (Phase::Finished, 1));
+    assert_eq!(s.client.get_result(&2).unwrap().outcome, Outcome::TurnLimit);
+}
+
+#[test]
+fn test_handicap_sets_each_boards_poison() {
+    let s = setup();
+    let mut config = timed_config(&s.env, 0);
+    config.board_poison = vec![&s.env, 1];
+    assert_error(&s.client.try_start_game_with_config(&1, &s.player1, &s.player2, &100, &100, &config), Error::InvalidConfig);
+    config.board_poison = vec![&s.env, 0, 3];
+    assert_error(&s.client.try_start_game_with_config(&1, &s.player1, &s.player2, &100, &100, &config), Error::InvalidConfig);
+
+    // Player1 hides a single Poison, player2 three
+    config.board_poison = vec![&s.env, 1, 3];
+    s.start_with_config(1, &config);
+    s.play(1, 1, 0);
+    s.client.attack(&1, &s.player2, &0);
+    let inputs = reveal_inputs(&s.env, &commitment(&s.env, 1), &s.player1, 1, 0, 1, 2, 1, 0);
+    assert_error(&s.client.try_respond_to_attack(&1, &s.player1, &1, &mock_proof(&s.env, &inputs)), Error::InvalidProof);
+    s.client.respond_to_attack(&1, &s.player1, &1, &s.proof(1, 1, 0, 1));
+
+    s.play(1, 1, 1);  // two of player2's three Poison: not yet a win
+    assert_eq!(s.client.get_game(&1).phase, Phase::Playing);
+    s.play(1, 2, 2);  // player1's only Poison and the Shield
+    let game = s.client.get_game(&1);
+    assert_eq!((game.phase, game.winner), (Phase::Finished, 2));
+}
+
+#[test]
+fn test_sessions_start_from_registered_templates() {
+    let s = setup();
+    let mut config = timed_config(&s.env, 40);
+    config.win_condition = WinCondition::FirstPoisonLoses;
+    let mut template = GameTemplate { name: Symbol::new(&s.env, "sudden_death"), board_size: 40, vk_version: 0, config };
+    assert_error(&s.client.try_set_template(&7, &template), Error::InvalidConfig);
+    template.board_size = 20;
+    template.vk_version = 9;
+    assert_error(&s.client.try_set_template(&7, &template), Error::VkNotSet);
+    template.vk_version = 1;
+    s.client.set_template(&7, &template);
+    s.client.set_template(&7, &template);
+    assert_eq!(s.client.list_templates(), vec![&s.env, 7]);
+    assert_eq!(s.client.get_template(&7), Some(template));
+
+    assert_error(&s.client.try_start_game_from_template(&1, &s.player1, &s.player2, &100, &100, &8), Error::GameNotFound);
+    s.client.start_game_from_template(&1, &s.player1, &s.player2, &100, &100, &7);
+    let game = s.client.get_game(&1);
+    assert_eq!((game.board_size, game.vk_version, game.player1_time_left), (20, 1, 40));
+    assert_eq!(game.config.win_condition, WinCondition::FirstPoisonLoses);
+
+    s.client.remove_template(&7);
+    assert_eq!(s.client.list_templates().len(), 0);
+}
+
+#[test]
+fn test_respond_within_budget() {
+    let s = setup();
+    s.start_and_commit(1);
+    s.client.attack(&1, &s.player1, &0);
+    let proof = s.proof(1, 2, 0, BOARD[0]);
+
+    s.env.cost_estimate().budget().reset_default();
+    s.client.respond_to_attack(&1, &s.player2, &BOARD[0], &proof);
+    assert!(s.env.cost_estimate().budget().cpu_instruction_cost() < CPU_LIMIT);
+}
+
+#[test]
+fn test_games_keep_their_vk_version() {
+    let s = setup();
+    s.start_and_commit(1);
+    assert_eq!(s.client.get_game(&1).vk_version, 1);
+
+    let v2 = s.client.init_vk(&s.admin, &soroban_sdk::Bytes::from_array(&s.env, &[1u8; 4]));
+    assert_eq!(v2, 2);
+    s.start_and_commit(2);
+    assert_eq!(s.client.get_game(&2).vk_version, 2);
+
+    // The in-flight game still verifies against its original VK
+    s.play(1, 1, 5);
+    assert_eq!(s.client.get_move_log(&1).len(), 1);
+}
+
+#[test]
+fn test_changes_since_returns_only_new_moves() {
+    let s = setup();
+    s.start_and_commit(1);
+    s.play(1, 1, 0);
+    let seen = s.client.get_game(&1).move_count;
+    s.play(1, 2, 10);
+    s.client.attack(&1, &s.player1, &1);
+
+    let changes = s.client.get_changes_since(&1, &seen);
+    assert_eq!(changes.moves.len(), 1);
+    assert_eq!(changes.moves.get(0).unwrap().tile_index, 10);
+    assert_eq!(changes.pending_tiles, soroban_sdk::vec![&s.env, 1]);
+    assert_eq!((changes.move_count, changes.current_turn), (seen + 3, 1));
+
+    // Nothing new since the latest move number
+    assert!(s.client.get_changes_since(&1, &changes.move_count).moves.is_empty());
+    assert_error(&s.client.try_get_changes_since(&9, &0), Error::GameNotFound);
+}
+
+#[test]
+fn test_rotated_vk_retires_the_previous_key() {
+    let s = setup();
+    s.start_and_commit(1);
+
+    let v2 = s.client.rotate_vk(&soroban_sdk::Bytes::from_array(&s.env, &[2u8; 4]), &10);
+    assert_eq!(v2, 2);
+    let rotation = s.client.get_vk_rotation().unwrap();
+    assert_eq!((rotation.previous, rotation.current), (1, 2));
+
+    // Inside the grace window and after it, the v1 game keeps playing
+    s.play(1, 1, 5);
+    s.advance(11);
+    s.play(1, 2, 5);
+    assert_eq!(s.client.get_move_log(&1).len(), 2);
+}
+
+#[test]
+fn test_respond_batch_answers_pending_attacks() {
+    let s = setup();
+    s.start_and_commit(1);
+    s.client.attack(&1, &s.player1, &5);
+
+    // Answers must line up one-to-one with the pending tiles
+    let proof = s.proof(1, 2, 5, BOARD[5]);
+    let two = soroban_sdk::vec![&s.env, (BOARD[5], proof.clone()), (BOARD[5], proof.clone())];
+    assert_error(&s.client.try_respond_batch(&1, &s.player2, &two), Error::BatchLengthMismatch);
+
+    let one = soroban_sdk::vec![&s.env, (BOARD[5], proof)];
+    s.client.respond_batch(&1, &s.player2, &one);
+    let game = s.client.get_game(&1);
+    assert!(game.pending_tiles.is_empty());
+    assert_eq!(game.current_turn, 2);
+    assert_eq!(game.p2_revealed.len(), 1);
+}
+
+#[test]
+fn test_codes_are_versioned() {
+    let s = setup();
+    assert_eq!(s.client.codes_version(), CODES_VERSION);
+    assert_eq!(Error::NotYourTurn.category(), ErrorCategory::Rules);
+    assert_eq!(Error::InvalidProof.category(), ErrorCategory::Proof);
+    assert_eq!(Error::ProofWrongLength.category(), ErrorCategory::Proof);
+}
+
+#[test]
+fn test_salvo_is_answered_before_the_turn_flips() {
+    let s = setup();
+    s.client.set_max_proofs_per_call(&3);
+    let mut config = timed_config(&s.env, 0);
+    config.salvo_size = 3;
+    s.start_with_config(1, &config);
+
+    let too_many = soroban_sdk::vec![&s.env, 5, 6, 7, 8];
+    assert_error(&s.client.try_attack_salvo(&1, &s.player1, &too_many), Error::InvalidSalvo);
+    let repeated = soroban_sdk::vec![&s.env, 5, 5];
+    assert_error(&s.client.try_attack_salvo(&1, &s.player1, &repeated), Error::TileAlreadyRevealed);
+
+    s.client.attack_salvo(&1, &s.player1, &soroban_sdk::vec![&s.env, 5, 6, 7]);
+    assert_eq!(s.client.get_game(&1).pending_tiles.len(), 3);
+
+    // A single answer does not cover the salvo
+    let proofs: std::vec::Vec<_> = [5u32, 6, 7].iter()
+        .map(|&t| (BOARD[t as usize], s.proof(1, 2, t, BOARD[t as usize])))
+        .collect();
+    assert_error(
+        &s.client.try_respond_to_attack(&1, &s.player2, &proofs[0].0, &proofs[0].1),
+        Error::BatchLengthMismatch,
+    );
+
+    let mut all = soroban_sdk::Vec::new(&s.env);
+    for p in proofs { all.push_back(p); }
+    s.client.respond_batch(&1, &s.player2, &all);
+    let game = s.client.get_game(&1);
+    assert!(game.pending_tiles.is_empty());
+    assert_eq!(game.p2_revealed.len(), 3);
+    assert_eq!(game.current_turn, 2);
+}
+
+#[test]
+fn test_queued_attacks_fire_when_the_turn_comes_back() {
+    let s = setup();
+    s.start_and_commit(1);
+    assert_error(&s.client.try_queue_attacks(&1, &s.player1, &vec![&s.env, 0, 0]), Error::InvalidSalvo);
+    assert_error(&s.client.try_queue_attacks(&1, &s.player1, &vec![&s.env, 15]), Error::InvalidTileIndex);
+
+    // It is player1's turn, so the first queued attack goes out at once
+    s.client.queue_attacks(&1, &s.player1, &vec![&s.env, 0, 5]);
+    assert_eq!(s.client.get_game(&1).pending_tiles, vec![&s.env, 0]);
+    s.client.respond_to_attack(&1, &s.player2, &1, &s.proof(1, 2, 0, 1));
+    s.play(1, 2, 10);
+    assert_eq!(s.client.get_game(&1).pending_tiles, vec![&s.env, 5]);
+    assert!(s.client.get_attack_queue(&1, &1).is_empty());
+    s.client.respond_to_attack(&1, &s.player2, &0, &s.proof(1, 2, 5, 0));
+
+    // Tiles revealed by the time the queue is consumed are skipped
+    s.client.queue_attacks(&1, &s.player1, &vec![&s.env, 0, 5, 2, 3]);
+    s.play(1, 2, 11);
+    let game = s.client.get_game(&1);
+    assert_eq!((game.current_turn, game.pending_tiles), (1, vec![&s.env, 2]));
+    assert_eq!(s.client.get_attack_queue(&1, &1), vec![&s.env, 3]);
+
+    s.client.cancel_attack_queue(&1, &s.player1);
+    assert!(s.client.get_attack_queue(&1, &1).is_empty());
+}
+
+#[test]
+fn test_poison_penalty_gives_the_defender_two_turns() {
+    let s = setup();
+    let mut config = timed_config(&s.env, 0);
+    config.poison_penalty = true;
+    s.start_with_config(1, &config);
+
+    s.play(1, 1, 0); // player1 finds Poison and loses their next turn
+    assert_eq!(s.client.get_game(&1).skip_next_turn, 1);
+    s.play(1, 2, 10);
+    s.play(1, 2, 11); // player2 attacks twice in a row
+    let game = s.client.get_game(&1);
+    assert_eq!(game.current_turn, 1);
+    assert_eq!(game.skip_next_turn, 0);
+}
+
+#[test]
+fn test_new_season_resets_live_ratings_and_keeps_the_archive() {
+    let s = setup();
+    s.start_and_commit(1);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+    let final_rating = s.client.get_rating(&s.player1);
+    assert!(final_rating > 1200);
+
+    assert_eq!(s.client.start_season(), 1);
+    assert_eq!(s.client.get_season(), 1);
+    assert_eq!(s.client.get_rating(&s.player1), 1200);
+    assert_eq!(s.client.get_season_record(&s.player1, &1), SeasonRecord::default());
+
+    s.start_and_commit(2);
+    for (attacker, tile) in [(1, 10), (2, 0), (1, 11), (2, 1), (1, 12), (2, 2)] {
+        s.play(2, attacker, tile);
+    }
+    assert!(s.client.get_rating(&s.player1) < 1200);
+    assert_eq!(s.client.get_season_rating(&s.player1, &0), final_rating);
+    assert_eq!(s.client.get_season_record(&s.player1, &0), SeasonRecord { wins: 1, losses: 0 });
+    assert_eq!(s.client.get_season_record(&s.player1, &1), SeasonRecord { wins: 0, losses: 1 });
+}
+
+#[test]
+fn test_rating_decays_after_inactivity() {
+    let s = setup();
+    s.client.set_rating_decay(&Some(RatingDecay {
+        grace_ledgers:     100,
+        period_ledgers:    50,
+        points_per_period: 10,
+        floor:             1150,
+    }));
+    s.start_and_commit(1);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+    let rating = s.client.get_rating(&s.player1);
+    assert!(rating > 1200);
+
+    // Within the grace period nothing changes; afterwards whole periods count
+    s.advance(100);
+    assert_eq!(s.client.apply_decay(&s.player1), rating);
+    s.advance(120);
+    assert_eq!(s.client.apply_decay(&s.player1), rating - 20);
+    assert_eq!(s.client.apply_decay(&s.player1), rating - 20);
+
+    // Never below the floor
+    s.advance(10_000);
+    assert_eq!(s.client.apply_decay(&s.player1), 1150);
+    assert_eq!(s.client.apply_decay(&s.player2), 1150);
+}
+
+#[test]
+fn test_decoy_reveals_count_toward_nothing() {
+    let s = setup();
+    s.start_and_commit(1);
+    s.client.attack(&1, &s.player1, &5);
+    let proof = s.proof(1, 2, 5, 3);
+    assert_error(&s.client.try_respond_to_attack(&1, &s.player2, &3, &proof), Error::InvalidProof);
+
+    let mut config = timed_config(&s.env, 0);
+    config.decoy_count = 2;
+    // Decoy boards are proven by their own circuit
+    let result = s.client.try_start_game_with_config(&2, &s.player1, &s.player2, &100, &100, &config);
+    assert_error(&result, Error::VkNotSet);
+    s.client.set_decoy_vk(&soroban_sdk::Bytes::from_array(&s.env, &[9u8; 4]));
+    config.lite = true;
+    let result = s.client.try_start_game_with_config(&2, &s.player1, &s.player2, &100, &100, &config);
+    assert_error(&result, Error::InvalidConfig);
+    config.lite = false;
+    s.start_with_config(2, &config);
+    s.client.set_scan_vk(&soroban_sdk::Bytes::from_array(&s.env, &[7u8; 4]));
+    assert_error(&s.client.try_scan(&2, &s.player1, &0), Error::WrongPhase);
+    s.client.attack(&2, &s.player1, &5);
+    let proof = s.proof(2, 2, 5, 3);
+    s.client.respond_to_attack(&2, &s.player2, &3, &proof);
+
+    let game = s.client.get_game(&2);
+    assert_eq!(game.current_turn, 2);
+    assert_eq!(s.client.get_board_view(&2, &s.player2).tiles.get(5), Some(TileStatus::Decoy));
+}
+
+#[test]
+fn test_scan_once_per_game() {
+    let s = setup();
+    s.start_and_commit(1);
+    assert_error(&s.client.try_scan(&1, &s.player1, &0), Error::VkNotSet);
+    s.client.set_scan_vk(&soroban_sdk::Bytes::from_array(&s.env, &[7u8; 4]));
+    assert_error(&s.client.try_scan(&1, &s.player1, &13), Error::InvalidTileIndex);
+
+    s.client.scan(&1, &s.player1, &0);
+    assert_error(&s.client.try_attack(&1, &s.player1, &5), Error::WrongPhase);
+
+    // Tiles 0..3 on player2's board hold two Poison and the Shield
+    let inputs = scan_inputs(&s.env, &commitment(&s.env, 2), &s.player2, 1, 0, 3, 2, 1);
+    let proof = mock_proof(&s.env, &inputs);
+    assert_error(&s.client.try_respond_scan(&1, &s.player2, &2, &proof), Error::InvalidProof);
+    s.client.respond_scan(&1, &s.player2, &3, &proof);
+
+    let game = s.client.get_game(&1);
+    assert_eq!((game.pending_scan, game.current_turn), (None, 2));
+    s.play(1, 2, 5);
+    assert_error(&s.client.try_scan(&1, &s.player1, &3), Error::ScanUsed);
+}
+
+#[test]
+fn test_reveal_board_after_finish() {
+    let s = setup();
+    s.start_and_commit(1);
+    let salt = soroban_sdk::BytesN::from_array(&s.env, &[9u8; 32]);
+    let board = soroban_sdk::Vec::from_slice(&s.env, &BOARD);
+    assert_error(&s.client.try_reveal_board(&1, &s.player2, &board, &salt), Error::WrongPhase);
+
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+
+    // Contradicts the Poison proven at tile 0
+    let mut lie = BOARD;
+    lie.swap(0, 3);
+    let lie = soroban_sdk::Vec::from_slice(&s.env, &lie);
+    assert_error(&s.client.try_reveal_board(&1, &s.player2, &lie, &salt), Error::BoardMismatch);
+
+    s.client.reveal_board(&1, &s.player2, &board, &salt);
+    let result = s.client.get_result(&1).unwrap();
+    assert!(result.player2_board_verified);
+    assert!(!result.player1_board_verified);
+    assert_eq!(s.client.get_board_reveal(&1, &2).unwrap().salt, salt);
+}
+
+#[test]
+fn test_lite_game_settles_on_aggregated_proof() {
+    let s = setup();
+    let mut config = timed_config(&s.env, 0);
+    config.lite = true;
+    s.start_with_config(1, &config);
+    let empty = soroban_sdk::Bytes::new(&s.env);
+
+    // Answers are folded into digests, not stored or verified
+    for (attacker, tile, tile_type) in [(1, 0, 1), (2, 10, 0), (1, 1, 1), (2, 11, 0), (1, 2, 2)] {
+        let defender = if attacker == 1 { &s.player2 } else { &s.player1 };
+        s.client.attack(&1, s.player(attacker), &tile);
+        s.client.respond_to_attack(&1, defender, &tile_type, &empty);
+    }
+    let game = s.client.get_game(&1);
+    assert!(game.p2_revealed.is_empty());
+    assert_eq!(game.lite_boards.get(1).unwrap().mask, 0b111);
+
+    // Player2's board is settled: no more attacks, player2 cannot claim
+    assert_error(&s.client.try_attack(&1, &s.player1, &5), Error::WrongPhase);
+    let own = [(10, 0), (11, 0)];
+    let inputs = lite_inputs(&s.env, &commitment(&s.env, 2), &s.player2, 1, &own, 2, 1);
+    assert_error(&s.client.try_claim_lite_win(&1, &s.player2, &mock_proof(&s.env, &inputs)), Error::WrongPhase);
+
+    let inputs = lite_inputs(&s.env, &commitment(&s.env, 1), &s.player1, 1, &own, 2, 1);
+    let proof = mock_proof(&s.env, &inputs);
+    assert_error(&s.client.try_claim_lite_win(&1, &s.player1, &proof), Error::VkNotSet);
+    s.client.set_lite_vk(&soroban_sdk::Bytes::from_array(&s.env, &[7u8; 4]));
+
+    // Player1 claiming a different answer on tile 11 fails the proof
+    let lie = lite_inputs(&s.env, &commitment(&s.env, 1), &s.player1, 1, &[(10, 0), (11, 2)], 2, 1);
+    assert_error(&s.client.try_claim_lite_win(&1, &s.player1, &mock_proof(&s.env, &lie)), Error::InvalidProof);
+
+    s.client.claim_lite_win(&1, &s.player1, &proof);
+    let game = s.client.get_game(&1);
+    assert_eq!((game.phase, game.winner), (Phase::Finished, 1));
+    assert_eq!(s.client.get_result(&1).unwrap().outcome, Outcome::AllSpecialsFound);
+}
+
+#[test]
+fn test_games_by_player_pages_sessions_with_status() {
+    let s = setup();
+    s.start_and_commit(1);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+    s.client.start_game(&2, &s.player1, &s.player2, &100, &100);
+    s.client.cancel_game(&2, &s.player1);
+    s.start_and_commit(3);
+    let other = Address::generate(&s.env);
+    s.client.start_game(&4, &other, &s.player2, &100, &100);
+
+    assert_eq!(s.client.get_game_count(&s.player1), 3);
+    assert_eq!(s.client.get_game_count(&s.player2), 4);
+    let page = s.client.get_games_by_player(&s.player1, &0, &2);
+    assert_eq!(page, vec![
+        &s.env,
+        PlayerSession { session_id: 1, status: SessionStatus::Finished },
+        PlayerSession { session_id: 2, status: SessionStatus::Cancelled },
+    ]);
+    let page = s.client.get_games_by_player(&s.player1, &2, &10);
+    assert_eq!(page, vec![&s.env, PlayerSession { session_id: 3, status: SessionStatus::Active }]);
+    assert_eq!(s.client.get_games_by_player(&s.player2, &3, &10).get(0).unwrap().status, SessionStatus::Pending);
+    assert!(s.client.get_games_by_player(&s.player1, &3, &10).is_empty());
+}
+
+#[test]
+fn test_active_games_track_live_sessions() {
+    let s = setup();
+    s.start_and_commit(1);
+    s.client.start_game(&2, &s.player1, &s.player2, &100, &100);
+    s.client.start_game(&3, &s.player1, &s.player2, &100, &100);
+    assert_eq!(s.client.list_active_games(&0, &10), vec![&s.env, 1, 2, 3]);
+
+    s.client.cancel_game(&2, &s.player1);
+    assert_eq!(s.client.list_active_games(&0, &10), vec![&s.env, 1, 3]);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+    assert_eq!(s.client.list_active_games(&0, &10), vec![&s.env, 3]);
+    assert_eq!(s.client.get_active_game_count(), 1);
+    assert_error(&s.client.try_prune_active_game(&3), Error::WrongPhase);
+}
+
+#[test]
+fn test_moves_and_extend_game_ttl_keep_a_slow_game_alive() {
+    let s = setup();
+    s.env.ledger().with_mut(|l| l.min_temp_entry_ttl = 16);
+    let ttl = || s.env.as_contract(&s.client.address, || s.env.storage().temporary().get_ttl(&DataKey::Game(1)));
+
+    s.start_and_commit(1);
+    let full = ttl();
+    s.advance(400_000);
+    assert_eq!(ttl(), full - 400_000);
+    s.client.attack(&1, &s.player1, &5);
+    assert_eq!(ttl(), full);
+
+    s.advance(400_000);
+    assert_error(&s.client.try_extend_game_ttl(&1, &Address::generate(&s.env)), Error::NotPlayer);
+    s.client.extend_game_ttl(&1, &s.player2);
+    assert_eq!(ttl(), full);
+}
+
+#[test]
+fn test_high_stakes_game_is_kept_in_persistent_storage() {
+    let s = setup();
+    s.client.set_persistent_stake(&100);
+    assert_error(&s.client.try_set_persistent_stake(&-1), Error::NegativeStake);
+    let stored = |id: u32| s.env.as_contract(&s.client.address, || (
+        s.env.storage().temporary().has(&DataKey::Game(id)),
+        s.env.storage().persistent().has(&DataKey::Game(id)),
+    ));
+
+    s.client.start_game(&1, &s.player1, &s.player2, &50, &50);
+    assert_eq!(stored(1), (true, false));
+    assert!(!s.client.get_game(&1).persistent);
+
+    s.start_and_commit(2);
+    assert_eq!(stored(2), (false, true));
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(2, attacker, tile);
+    }
+    let game = s.client.get_game(&2);
+    assert!(game.persistent);
+    assert_eq!(game.winner, 1);
+    assert_error(&s.client.try_start_game(&2, &s.player1, &s.player2, &100, &100), Error::SessionExists);
+}
+
+#[test]
+fn test_poseidon_game_needs_its_own_key() {
+    let s = setup();
+    let mut config = timed_config(&s.env, 0);
+    config.commitment_scheme = CommitmentScheme::Poseidon;
+    assert_error(
+        &s.client.try_start_game_with_config(&1, &s.player1, &s.player2, &100, &100, &config),
+        Error::VkNotSet,
+    );
+
+    s.client.set_poseidon_vk(&soroban_sdk::Bytes::from_array(&s.env, &[9u8; 4]));
+    assert!(s.client.has_poseidon_vk());
+    let mut lite = config.clone();
+    lite.lite = true;
+    assert_error(
+        &s.client.try_start_game_with_config(&1, &s.player1, &s.player2, &100, &100, &lite),
+        Error::InvalidConfig,
+    );
+
+    s.start_with_config(1, &config);
+    s.client.set_scan_vk(&soroban_sdk::Bytes::from_array(&s.env, &[7u8; 4]));
+    assert_error(&s.client.try_scan(&1, &s.player1, &0), Error::WrongPhase);
+
+    s.play(1, 1, 0);
+    s.play(1, 2, 10);
+    s.play(1, 1, 1);
+    s.play(1, 2, 11);
+    s.play(1, 1, 2);
+    let game = s.client.get_game(&1);
+    assert_eq!((game.phase, game.winner), (Phase::Finished, 1));
+}
+
+#[test]
+fn test_commitments_are_bound_to_owner_and_session() {
+    let s = setup();
+    s.start_and_commit(1);
+    s.client.attack(&1, &s.player1, &0);
+
+    // Player2's board opened as if it were player1's, or from another session
+    let mirrored = reveal_inputs(&s.env, &commitment(&s.env, 2), &s.player1, 1, 0, 1, 2, 1, 0);
+    assert_error(&s.client.try_respond_to_attack(&1, &s.player2, &1, &mock_proof(&s.env, &mirrored)), Error::InvalidProof);
+    let replayed = reveal_inputs(&s.env, &commitment(&s.env, 2), &s.player2, 2, 0, 1, 2, 1, 0);
+    assert_error(&s.client.try_respond_to_attack(&1, &s.player2, &1, &mock_proof(&s.env, &replayed)), Error::InvalidProof);
+
+    // A rotated seat keeps the binding of the seat as opened
+    let moved = Address::generate(&s.env);
+    s.client.rotate_seat(&1, &s.player2, &moved);
+    let bound = reveal_inputs(&s.env, &commitment(&s.env, 2), &s.player2, 1, 0, 1, 2, 1, 0);
+    s.client.respond_to_attack(&1, &moved, &1, &mock_proof(&s.env, &bound));
+    assert_eq!(s.client.get_game(&1).board_owners, vec![&s.env, s.player1.clone(), s.player2.clone()]);
+}
+
+#[test]
+fn test_zero_and_duplicate_commitments_are_refused() {
+    let s = setup();
+    s.client.start_game(&1, &s.player1, &s.player2, &100, &100);
+    let zero = BytesN::from_array(&s.env, &[0u8; 32]);
+    assert_error(&s.client.try_commit_board(&1, &s.player1, &zero), Error::ZeroCommitment);
+
+    s.client.commit_board(&1, &s.player1, &commitment(&s.env, 1));
+    assert_error(&s.client.try_commit_board(&1, &s.player2, &commitment(&s.env, 1)), Error::DuplicateCommitment);
+    s.client.commit_board(&1, &s.player2, &commitment(&s.env, 2));
+    assert_eq!(s.client.get_game(&1).phase, Phase::Playing);
+    assert_eq!(Error::DuplicateCommitment.category(), ErrorCategory::Rules);
+}
@@ -0,0 +1,31 @@
+// The generic HubGame surface, driven only through `HubGameClient`.
+
+mod common;
+
+use common::setup;
+use poison_game::{HubGameClient, SessionStatus};
+
+#[test]
+fn test_hub_game_client_drives_a_session() {
+    let s = setup();
+    let game = HubGameClient::new(&s.env, &s.client.address);
+    assert_eq!(game.session_status(&1), SessionStatus::Unknown);
+
+    game.start_game(&1, &s.player1, &s.player2, &100, &100);
+    assert_eq!(game.session_status(&1), SessionStatus::Pending);
+
+    s.commit_both(1);
+    assert_eq!(game.session_status(&1), SessionStatus::Active);
+    assert!(game.try_cancel_game(&1, &s.player1).is_err());
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+    assert_eq!(game.session_status(&1), SessionStatus::Finished);
+    let result = game.session_result(&1).unwrap();
+    assert_eq!((result.winner, result.player1), (1, s.player1.clone()));
+
+    game.start_game(&2, &s.player1, &s.player2, &100, &100);
+    assert_eq!(game.session_result(&2), None);
+    game.cancel_game(&2, &s.player2);
+    assert_eq!(game.session_status(&2), SessionStatus::Cancelled);
+}
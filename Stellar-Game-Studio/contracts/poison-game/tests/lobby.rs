@@ -0,0 +1,67 @@
+// Open lobby: listing, joining, reserved session ids and expired
+// listings.
+
+mod common;
+
+use common::{assert_error, setup};
+use poison_game::{DataKey, Error, Phase, OPEN_GAME_LEDGERS};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::Address;
+
+#[test]
+fn test_listing_reserves_its_session_id() {
+    let s = setup();
+    s.client.create_open_game(&1, &s.player1, &100);
+
+    // Nobody else can start a game under the listed id
+    let other = Address::generate(&s.env);
+    assert_error(&s.client.try_start_game(&1, &other, &s.player2, &100, &100), Error::SessionExists);
+    assert_error(&s.client.try_create_open_game(&1, &other, &100), Error::SessionExists);
+
+    s.client.join_open_game(&1, &s.player2);
+    let game = s.client.get_game(&1);
+    assert_eq!((game.player1, game.player2, game.phase), (s.player1.clone(), s.player2.clone(), Phase::WaitingForCommits));
+    assert_error(&s.client.try_get_open_game(&1), Error::GameNotFound);
+    assert_error(&s.client.try_join_open_game(&1, &other), Error::GameNotFound);
+}
+
+#[test]
+fn test_expired_listing_refunds_its_creator() {
+    let s = setup();
+    s.client.create_open_game(&1, &s.player1, &100);
+    s.client.create_open_game(&2, &s.player1, &100);
+    assert_error(&s.client.try_expire_open_game(&1), Error::ClockNotExpired);
+
+    s.advance(OPEN_GAME_LEDGERS + 1);
+    assert!(s.client.list_open_games(&None, &0, &10).is_empty());
+    assert_error(&s.client.try_join_open_game(&1, &s.player2), Error::GameNotFound);
+
+    let released = s.hub.released(&s.player1);
+    s.client.expire_open_game(&1);
+    assert_eq!(s.hub.released(&s.player1), released + 100);
+    assert_error(&s.client.try_get_open_game(&1), Error::GameNotFound);
+    assert_error(&s.client.try_expire_open_game(&1), Error::GameNotFound);
+
+    // Cancelling drops the other listing from the lobby as well
+    s.client.cancel_open_game(&2);
+    s.env.as_contract(&s.client.address, || {
+        let lobby: soroban_sdk::Vec<u32> = s.env.storage().persistent().get(&DataKey::Lobby).unwrap();
+        assert!(lobby.is_empty());
+    });
+}
+
+#[test]
+fn test_listing_settles_on_the_hub_that_locked_its_stake() {
+    let s = setup();
+    s.client.create_open_game(&1, &s.player1, &100);
+    s.client.create_open_game(&2, &s.player1, &100);
+    let hub2_addr = s.env.register(common::MockGameHub, ());
+    let hub2      = common::MockGameHubClient::new(&s.env, &hub2_addr);
+    s.client.set_hub(&hub2_addr);
+
+    s.client.cancel_open_game(&1);
+    assert_eq!((s.hub.released(&s.player1), hub2.released(&s.player1)), (100, 0));
+    s.client.join_open_game(&2, &s.player2);
+    assert_eq!((s.hub.locked(&s.player2), hub2.locked(&s.player2)), (100, 0));
+    assert_eq!(s.client.get_game(&2).hub, s.hub.address);
+}
@@ -0,0 +1,65 @@
+// Practice games against the operator's house board: no wager, the house
+// answers with proofs and publishes the board once the round is retired.
+
+mod common;
+
+use common::{assert_error, commitment, setup, BOARD};
+use poison_game::testutils::{mock_proof, reveal_inputs};
+use poison_game::Error;
+use soroban_sdk::{Address, Bytes, BytesN, Vec};
+
+/// House answer for `tile_index` on the first house round's board.
+fn house_proof(s: &common::Setup, tile_index: u32) -> Bytes {
+    let inputs = reveal_inputs(
+        &s.env, &commitment(&s.env, 9), &s.client.address, 1, tile_index, BOARD[tile_index as usize], 2, 1, 0,
+    );
+    mock_proof(&s.env, &inputs)
+}
+
+fn attack(s: &common::Setup, player: &Address, tile_index: u32) {
+    s.client.practice_attack(player, &tile_index);
+    s.client.answer_practice(player, &BOARD[tile_index as usize], &house_proof(s, tile_index));
+}
+
+#[test]
+fn test_practice_ends_when_every_house_special_is_found() {
+    let s = setup();
+    assert_error(&s.client.try_start_practice(&s.player1), Error::GameNotFound);
+    assert_eq!(s.client.set_house_board(&commitment(&s.env, 9)), 1);
+    s.client.start_practice(&s.player1);
+
+    attack(&s, &s.player1, 5);
+    s.client.practice_attack(&s.player1, &0);
+    assert_error(&s.client.try_practice_attack(&s.player1, &1), Error::WrongPhase);
+    // The house cannot lie about a tile
+    assert_error(&s.client.try_answer_practice(&s.player1, &0, &house_proof(&s, 0)), Error::InvalidProof);
+    let short = Bytes::from_array(&s.env, &[0u8; 32]);
+    assert_error(&s.client.try_answer_practice(&s.player1, &BOARD[0], &short), Error::ProofWrongLength);
+    s.client.answer_practice(&s.player1, &BOARD[0], &house_proof(&s, 0));
+    assert_error(&s.client.try_practice_attack(&s.player1, &5), Error::TileAlreadyRevealed);
+    attack(&s, &s.player1, 1);
+    attack(&s, &s.player1, 2);
+
+    let game = s.client.get_practice(&s.player1).unwrap();
+    assert!(game.finished);
+    assert_eq!((game.moves, game.revealed.specials()), (4, (2, 1)));
+    assert_eq!(s.hub.locked(&s.player1), 0);
+    assert_error(&s.client.try_practice_attack(&s.player1, &3), Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_retired_house_board_is_revealed() {
+    let s = setup();
+    s.client.set_house_board(&commitment(&s.env, 9));
+    let tiles = Vec::from_array(&s.env, BOARD);
+    let salt = BytesN::from_array(&s.env, &[7u8; 32]);
+    assert_error(&s.client.try_reveal_house_board(&1, &tiles, &salt), Error::WrongPhase);
+
+    s.client.set_house_board(&commitment(&s.env, 10));
+    let mut bad = tiles.clone();
+    bad.set(3, 1);
+    assert_error(&s.client.try_reveal_house_board(&1, &bad, &salt), Error::BoardMismatch);
+    s.client.reveal_house_board(&1, &tiles, &salt);
+    assert_eq!(s.client.get_house_reveal(&1).unwrap().commitment, commitment(&s.env, 9));
+    assert_error(&s.client.try_reveal_house_board(&1, &tiles, &salt), Error::AlreadyCommitted);
+}
@@ -0,0 +1,127 @@
+// Randomized state-machine tests: seeded sequences of legal and illegal
+// moves are replayed against a reference model of the turn rules, and the
+// contract must agree with it after every step.
+
+mod common;
+
+use common::setup;
+use poison_game::Phase;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const CASES: u64 = 24;
+const STEPS: u32 = 48;
+
+/// Random board with 2 Poison and 1 Shield.
+fn random_board(rng: &mut StdRng) -> [u32; 15] {
+    let mut board = [0u32; 15];
+    for special in [1, 1, 2] {
+        loop {
+            let i = rng.gen_range(0..15);
+            if board[i] == 0 { board[i] = special; break; }
+        }
+    }
+    board
+}
+
+struct Model {
+    boards:   [[u32; 15]; 2],
+    revealed: [Vec<u32>; 2], // tile indices revealed on each player's board
+    turn:     u32,
+    pending:  Option<u32>,
+    winner:   u32,
+}
+
+impl Model {
+    fn found_all(&self, board_slot: u32) -> bool {
+        let board = &self.boards[board_slot as usize - 1];
+        let revealed = &self.revealed[board_slot as usize - 1];
+        let poison = revealed.iter().filter(|&&t| board[t as usize] == 1).count();
+        let shield = revealed.iter().filter(|&&t| board[t as usize] == 2).count();
+        poison >= 2 && shield >= 1
+    }
+}
+
+#[test]
+fn test_random_move_sequences_follow_turn_rules() {
+    for seed in 0..CASES {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let s = setup();
+        s.start_and_commit(1);
+
+        let mut m = Model {
+            boards:   [random_board(&mut rng), random_board(&mut rng)],
+            revealed: [Vec::new(), Vec::new()],
+            turn:     1,
+            pending:  None,
+            winner:   0,
+        };
+
+        for _ in 0..STEPS {
+            let actor = rng.gen_range(1..=2u32);
+            let other = 3 - actor;
+
+            if rng.gen_bool(0.5) {
+                let tile = rng.gen_range(0..16u32);
+                let legal = m.winner == 0
+                    && m.pending.is_none()
+                    && actor == m.turn
+                    && tile < 15
+                    && !m.revealed[other as usize - 1].contains(&tile);
+
+                let ok = s.client.try_attack(&1, s.player(actor), &tile).is_ok();
+                assert_eq!(ok, legal, "seed {seed}: attack by {actor} on {tile}");
+                if ok { m.pending = Some(tile); }
+            } else {
+                // Defender answers the pending tile; sometimes with a lie
+                let tile = m.pending.unwrap_or(0);
+                let truth = m.boards[actor as usize - 1][tile as usize];
+                let honest = rng.gen_bool(0.7);
+                let claimed = if honest { truth } else { (truth + rng.gen_range(1..3)) % 3 };
+                let legal = m.winner == 0 && m.pending.is_some() && actor != m.turn && honest;
+
+                let proof = s.proof(1, actor, tile, truth);
+                let ok = s.client.try_respond_to_attack(&1, s.player(actor), &claimed, &proof).is_ok();
+                assert_eq!(ok, legal, "seed {seed}: respond by {actor} claiming {claimed}");
+
+                if ok {
+                    m.revealed[actor as usize - 1].push(tile);
+                    m.pending = None;
+                    if m.found_all(actor) {
+                        m.winner = other;
+                    } else if truth != 2 {
+                        m.turn = actor;
+                    }
+                }
+            }
+
+            let game = s.client.get_game(&1);
+            assert_eq!(game.winner, m.winner, "seed {seed}");
+            assert_eq!(!game.pending_tiles.is_empty(), m.pending.is_some(), "seed {seed}");
+            if m.winner == 0 {
+                assert_eq!(game.current_turn, m.turn, "seed {seed}");
+            } else {
+                assert_eq!(game.phase, Phase::Finished, "seed {seed}");
+            }
+
+            // A tile is never revealed twice on the same board
+            for revealed in [&game.p1_revealed, &game.p2_revealed] {
+                for i in 0..revealed.len() {
+                    for j in (i + 1)..revealed.len() {
+                        assert_ne!(revealed.get(i).unwrap().tile_index,
+                                   revealed.get(j).unwrap().tile_index, "seed {seed}");
+                    }
+                }
+            }
+
+            // The packed masks track exactly the revealed lists
+            for (revealed, mask) in [(&game.p1_revealed, &game.p1_mask), (&game.p2_revealed, &game.p2_mask)] {
+                assert_eq!(mask.revealed.count_ones(), revealed.len(), "seed {seed}");
+                for r in revealed.iter() {
+                    assert!(mask.is_revealed(r.tile_index), "seed {seed}");
+                    assert_eq!(mask.poison & (1 << r.tile_index) != 0, r.tile_type == 1, "seed {seed}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// Relayed moves are authorized by the player's signature over the move,
+// pinned to the game's move number; session keys sign moves for a player.
+
+mod common;
+
+use common::{assert_error, setup, BOARD};
+use poison_game::{Error, MAX_SESSION_KEY_LEDGERS};
+use soroban_sdk::testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, MockAuth, MockAuthInvoke};
+use soroban_sdk::{vec, Address, IntoVal, Symbol};
+
+#[test]
+fn test_relayed_moves_sign_the_move_and_move_number() {
+    let s = setup();
+    s.start_and_commit(1);
+    let move_number = s.client.get_game(&1).move_count;
+
+    let tiles = vec![&s.env, 0u32];
+    s.client.relay_attack(&1, &s.player1, &move_number, &tiles);
+    let args = vec![&s.env, 1u32.into_val(&s.env), move_number.into_val(&s.env), tiles.into_val(&s.env)];
+    assert_eq!(s.env.auths(), std::vec![(s.player1.clone(), AuthorizedInvocation {
+        function:        AuthorizedFunction::Contract((s.client.address.clone(), Symbol::new(&s.env, "relay_attack"), args)),
+        sub_invocations: std::vec![],
+    })]);
+
+    // The same signed attack cannot be submitted twice
+    assert_error(&s.client.try_relay_attack(&1, &s.player1, &move_number, &tiles), Error::StaleMove);
+
+    let responses = vec![&s.env, (BOARD[0], s.proof(1, 2, 0, BOARD[0]))];
+    assert_error(&s.client.try_relay_respond(&1, &s.player2, &move_number, &responses), Error::StaleMove);
+    s.client.relay_respond(&1, &s.player2, &(move_number + 1), &responses);
+
+    let game = s.client.get_game(&1);
+    assert_eq!(game.p2_revealed.len(), 1);
+    assert_eq!(game.current_turn, 2);
+}
+
+#[test]
+fn test_session_key_signs_moves_until_it_expires() {
+    let s = setup();
+    s.start_and_commit(1);
+    let key = Address::generate(&s.env);
+    assert_error(&s.client.try_attack_with_session_key(&1, &s.player1, &0), Error::SessionKeyInvalid);
+
+    let now = s.env.ledger().sequence();
+    assert_error(&s.client.try_register_session_key(&s.player1, &key, &now), Error::InvalidConfig);
+    assert_error(
+        &s.client.try_register_session_key(&s.player1, &key, &(now + MAX_SESSION_KEY_LEDGERS + 1)),
+        Error::InvalidConfig,
+    );
+    s.client.register_session_key(&s.player1, &key, &(now + 100));
+
+    // The key, not the player, authorizes the attack
+    s.client.attack_with_session_key(&1, &s.player1, &0);
+    assert_eq!(s.env.auths().len(), 1);
+    assert_eq!(s.env.auths()[0].0, key);
+    s.client.respond_to_attack(&1, &s.player2, &BOARD[0], &s.proof(1, 2, 0, BOARD[0]));
+
+    s.client.attack(&1, &s.player2, &10);
+    let responses = vec![&s.env, (BOARD[10], s.proof(1, 1, 10, BOARD[10]))];
+    s.advance(101);
+    assert_error(&s.client.try_respond_with_session_key(&1, &s.player1, &responses), Error::SessionKeyInvalid);
+    s.client.register_session_key(&s.player1, &key, &(s.env.ledger().sequence() + 100));
+    s.client.respond_with_session_key(&1, &s.player1, &responses);
+    assert_eq!(s.client.get_game(&1).p1_revealed.len(), 1);
+
+    s.client.revoke_session_key(&s.player1);
+    assert_eq!(s.client.get_session_key(&s.player1), None);
+    assert_error(&s.client.try_attack_with_session_key(&1, &s.player1, &1), Error::SessionKeyInvalid);
+}
+
+#[test]
+fn test_session_key_cannot_cancel_or_register_for_the_player() {
+    let s = setup();
+    s.client.start_game(&1, &s.player1, &s.player2, &100, &100);
+    let key = Address::generate(&s.env);
+    let expires_at = s.env.ledger().sequence() + 100;
+
+    // Only the player signs a registration
+    s.client.register_session_key(&s.player1, &key, &expires_at);
+    let args = vec![&s.env, s.player1.into_val(&s.env), key.into_val(&s.env), expires_at.into_val(&s.env)];
+    assert_eq!(s.env.auths(), std::vec![(s.player1.clone(), AuthorizedInvocation {
+        function:        AuthorizedFunction::Contract((s.client.address.clone(), Symbol::new(&s.env, "register_session_key"), args)),
+        sub_invocations: std::vec![],
+    })]);
+
+    // The key's signature does not stand in for the player's on stakes
+    s.env.mock_auths(&[MockAuth {
+        address: &key,
+        invoke:  &MockAuthInvoke {
+            contract:     &s.client.address,
+            fn_name:      "cancel_game",
+            args:         (1u32, s.player1.clone()).into_val(&s.env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(s.client.try_cancel_game(&1, &s.player1).is_err());
+    assert!(!s.hub.refunded(&1));
+}
@@ -0,0 +1,97 @@
+// Best-of-N series: escrow, per-game payouts, duplicate ids and winding up
+// an idle series.
+
+mod common;
+
+use common::{assert_error, setup, Setup};
+use poison_game::{Error, SeriesPayout, SERIES_IDLE_LEDGERS};
+
+/// Player 1 uncovers both Poisons and the Shield on player 2's board.
+fn player1_wins(s: &Setup, session_id: u32) {
+    s.commit_both(session_id);
+    s.play(session_id, 1, 0);
+    s.play(session_id, 2, 10);
+    s.play(session_id, 1, 1);
+    s.play(session_id, 2, 11);
+    s.play(session_id, 1, 2);
+}
+
+#[test]
+fn test_series_releases_each_game_share_to_its_winner() {
+    let s = setup();
+    s.client.start_series(&1, &s.player1, &s.player2, &3, &90, &90, &SeriesPayout::PerGame);
+    assert_eq!(s.hub.locked(&s.player1), 90);
+    assert_eq!(s.hub.locked(&s.player2), 90);
+
+    s.client.start_series_game(&1, &10, &s.player1);
+    assert_error(&s.client.try_start_series_game(&1, &11, &s.player2), Error::SeriesGameActive);
+    player1_wins(&s, 10);
+    assert_eq!(s.hub.released(&s.player1), 60);
+
+    s.client.start_series_game(&1, &11, &s.player2);
+    player1_wins(&s, 11);
+    let series = s.client.get_series(&1);
+    assert!(series.finished);
+    assert_eq!(series.player1_wins, 2);
+    // Two games' shares, then the unplayed third share of each stake
+    assert_eq!(s.hub.released(&s.player1), 150);
+    assert_eq!(s.hub.released(&s.player2), 30);
+}
+
+#[test]
+fn test_series_id_cannot_be_reused() {
+    let s = setup();
+    s.client.start_series(&1, &s.player1, &s.player2, &3, &90, &90, &SeriesPayout::PerGame);
+    let result = s.client.try_start_series(&1, &s.player2, &s.player1, &1, &10, &10, &SeriesPayout::WinnerTakesAll);
+    assert_error(&result, Error::SeriesExists);
+    assert_eq!(s.hub.locked(&s.player1), 90);
+    assert_eq!(s.client.get_series(&1).best_of, 3);
+}
+
+#[test]
+fn test_idle_series_refunds_unreleased_stakes() {
+    let s = setup();
+    s.client.start_series(&1, &s.player1, &s.player2, &3, &90, &90, &SeriesPayout::PerGame);
+    s.client.start_series_game(&1, &10, &s.player1);
+    player1_wins(&s, 10);
+
+    s.advance(SERIES_IDLE_LEDGERS);
+    assert_error(&s.client.try_abandon_series(&1), Error::ClockNotExpired);
+    s.advance(1);
+    s.client.abandon_series(&1);
+
+    // Game one's pot went to player 1; the two unplayed shares come back
+    assert_eq!(s.hub.released(&s.player1), 60 + 60);
+    assert_eq!(s.hub.released(&s.player2), 60);
+    assert!(s.client.get_series(&1).finished);
+    assert_error(&s.client.try_abandon_series(&1), Error::SeriesFinished);
+}
+
+#[test]
+fn test_series_with_a_live_game_cannot_be_abandoned() {
+    let s = setup();
+    s.client.start_series(&1, &s.player1, &s.player2, &3, &90, &90, &SeriesPayout::WinnerTakesAll);
+    s.client.start_series_game(&1, &10, &s.player1);
+
+    s.advance(SERIES_IDLE_LEDGERS + 1);
+    assert_error(&s.client.try_abandon_series(&1), Error::SeriesGameActive);
+    assert_eq!(s.hub.released(&s.player1), 0);
+}
+
+#[test]
+fn test_series_settles_on_the_hub_that_locked_its_stakes() {
+    let s = setup();
+    s.client.start_series(&1, &s.player1, &s.player2, &3, &90, &90, &SeriesPayout::PerGame);
+    let hub2_addr = s.env.register(common::MockGameHub, ());
+    let hub2      = common::MockGameHubClient::new(&s.env, &hub2_addr);
+    s.client.set_hub(&hub2_addr);
+
+    s.client.start_series_game(&1, &10, &s.player1);
+    assert_eq!(s.client.get_game(&10).hub, s.hub.address);
+    player1_wins(&s, 10);
+    s.advance(SERIES_IDLE_LEDGERS + 1);
+    s.client.abandon_series(&1);
+    assert_eq!(s.hub.released(&s.player1), 60 + 60);
+    assert_eq!(s.hub.released(&s.player2), 60);
+    assert_eq!((hub2.released(&s.player1), hub2.released(&s.player2)), (0, 0));
+}
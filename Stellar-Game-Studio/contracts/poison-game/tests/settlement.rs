@@ -0,0 +1,408 @@
+// A session pays out or refunds exactly once.
+
+mod common;
+
+use common::{assert_error, setup};
+use poison_game::{
+    CommitmentScheme, DataKey, Discrepancy, Drift, Error, EscrowState, Evidence, GameConfig, Outcome, Phase, SettlementKind,
+    StakeLimits, Wager, WinCondition,
+};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{vec, Address};
+
+fn play_to_win(s: &common::Setup, session_id: u32) {
+    s.start_and_commit(session_id);
+    s.play(session_id, 1, 0);
+    s.play(session_id, 2, 10);
+    s.play(session_id, 1, 1);
+    s.play(session_id, 2, 11);
+    s.play(session_id, 1, 2);
+}
+
+#[test]
+fn test_finished_game_settles_once() {
+    let s = setup();
+    play_to_win(&s, 1);
+
+    let game = s.client.get_game(&1);
+    assert!(game.settled);
+    let receipt = s.client.get_settlement_receipt(&1).unwrap();
+    assert_eq!(receipt.kind, SettlementKind::Win);
+    assert_eq!(receipt.winner, 1);
+
+    // No later path can reach the hub again
+    assert_error(&s.client.try_claim_flag_fall(&1, &s.player2), Error::WrongPhase);
+    assert_error(&s.client.try_attack(&1, &s.player2, &5), Error::WrongPhase);
+    assert_error(&s.client.try_cancel_game(&1, &s.player1), Error::WrongPhase);
+    assert_eq!(s.hub.end_calls(&1), 1);
+}
+
+#[test]
+fn test_settled_session_id_cannot_be_reopened() {
+    let s = setup();
+    s.client.start_game(&1, &s.player1, &s.player2, &100, &100);
+    s.client.cancel_game(&1, &s.player1);
+    assert_eq!(s.client.get_settlement_receipt(&1).unwrap().kind, SettlementKind::Refund);
+
+    assert_error(&s.client.try_start_game(&1, &s.player1, &s.player2, &100, &100), Error::SessionExists);
+
+    play_to_win(&s, 2);
+    assert_error(&s.client.try_start_game(&2, &s.player1, &s.player2, &100, &100), Error::SessionExists);
+}
+
+#[test]
+fn test_expired_session_id_cannot_be_reused() {
+    let s = setup();
+    s.client.start_game(&1, &s.player1, &s.player2, &100, &100);
+    assert_error(&s.client.try_start_game(&1, &s.player1, &s.player2, &100, &100), Error::SessionExists);
+
+    // The live entry lapses without the session ever settling
+    s.env.as_contract(&s.client.address, || s.env.storage().temporary().remove(&DataKey::Game(1)));
+    let other = Address::generate(&s.env);
+    assert_error(&s.client.try_start_game(&1, &other, &s.player2, &100, &100), Error::SessionIdReused);
+    assert_error(&s.client.try_create_open_game(&1, &other, &100), Error::SessionIdReused);
+}
+
+#[test]
+fn test_reconcile_reports_missing_receipts() {
+    let s = setup();
+    play_to_win(&s, 1);
+    s.start_and_commit(2);
+    let ids = soroban_sdk::vec![&s.env, 1, 2, 3];
+    assert!(s.client.reconcile(&ids).is_empty());
+
+    // Simulate a lost receipt for the paid-out session
+    s.env.as_contract(&s.client.address, || {
+        s.env.storage().persistent().remove(&DataKey::Receipt(1));
+    });
+    let report = s.client.reconcile(&ids);
+    assert_eq!(report.len(), 1);
+    assert_eq!(report.get(0).unwrap(), Discrepancy {
+        session_id: 1,
+        expected:   EscrowState::Unknown,
+        drift:      Drift::MissingReceipt,
+    });
+}
+
+#[test]
+fn test_beating_a_champion_pays_the_bounty() {
+    let s = setup();
+    s.client.set_champion(&s.player2, &Some(60), &100);
+    s.client.fund_treasury(&s.admin, &100);
+
+    play_to_win(&s, 1);
+    assert_eq!(s.hub.released(&s.player1), 60);
+    assert_eq!(s.client.get_treasury_balance(), 40);
+    assert_eq!(s.client.get_champion(&s.player2).unwrap().defeats, 1);
+
+    // The treasury can no longer cover the bounty; the game still settles
+    play_to_win(&s, 2);
+    assert_eq!(s.hub.released(&s.player1), 60);
+    assert_eq!(s.hub.ended(&2), Some(true));
+}
+
+#[test]
+fn test_nonresponse_forfeits_and_slashes_bond() {
+    let s = setup();
+    let config = GameConfig {
+        poison_count:      2,
+        shield_count:      1,
+        player2_hub:       None,
+        reveal_delay:      0,
+        broadcast:         false,
+        rules_hash:        None,
+        time_bank:         0,
+        salvo_size:        0,
+        poison_penalty:    false,
+        max_bonus_attacks: 0,
+        decoy_count:       0,
+        lite:              false,
+        response_window:   10,
+        slash_bps:         2_500,
+        dispute_window:    0,
+        simultaneous:      false,
+        max_rounds:        0,
+        win_condition:     WinCondition::AllSpecials,
+        board_poison:      vec![&s.env],
+        commitment_scheme: CommitmentScheme::Pedersen,
+        commit_window:     0,
+        hub:               None,
+    };
+    s.start_with_config(1, &config);
+    assert_eq!(s.hub.locked(&s.player1), 25);
+    assert_eq!(s.hub.locked(&s.player2), 25);
+
+    assert_error(&s.client.try_claim_nonresponse(&1), Error::WrongPhase);
+    s.client.attack(&1, &s.player1, &0);
+    s.advance(10);
+    assert_error(&s.client.try_claim_nonresponse(&1), Error::ClockNotExpired);
+    s.advance(1);
+    s.client.claim_nonresponse(&1);
+
+    let game = s.client.get_game(&1);
+    assert_eq!((game.winner, game.forfeited), (1, 2));
+    assert_eq!(s.hub.ended(&1), Some(true));
+    assert_eq!(s.client.get_result(&1).unwrap().outcome, Outcome::NonResponse);
+    // Both bonds go to the attacker
+    assert_eq!(s.hub.released(&s.player1), 50);
+    assert_eq!(s.hub.released(&s.player2), 0);
+}
+
+fn held_config(s: &common::Setup) -> GameConfig {
+    let mut config = s.client.get_game(&1).config;
+    config.dispute_window = 20;
+    config
+}
+
+#[test]
+fn test_dispute_window_holds_payout_until_finalized() {
+    let s = setup();
+    s.client.set_champion(&s.player2, &Some(60), &100);
+    s.client.fund_treasury(&s.admin, &100);
+    s.start_and_commit(1);
+    let config = held_config(&s);
+    s.start_with_config(2, &config);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(2, attacker, tile);
+    }
+
+    let game = s.client.get_game(&2);
+    assert_eq!((game.phase, game.winner, game.settled), (Phase::Finished, 1, false));
+    assert_eq!(s.hub.ended(&2), None);
+    assert_eq!(s.hub.released(&s.player1), 0);
+    assert!(s.client.reconcile(&soroban_sdk::vec![&s.env, 2]).is_empty());
+
+    // Player1's answer at tile 10 was Normal; a matching proof is no contradiction
+    let normal = Evidence { tile_index: 10, tile_type: 0, proof: s.proof(2, 1, 10, 0) };
+    assert_error(&s.client.try_challenge(&2, &normal), Error::InvalidEvidence);
+    assert_error(&s.client.try_finalize(&2), Error::DisputeWindowOpen);
+
+    s.advance(21);
+    let late = Evidence { tile_index: 10, tile_type: 1, proof: s.proof(2, 1, 10, 1) };
+    assert_error(&s.client.try_challenge(&2, &late), Error::WrongPhase);
+    // A pause does not hold up the payout
+    s.client.pause();
+    s.client.finalize(&2);
+    assert_eq!(s.hub.ended(&2), Some(true));
+    // The champion's bounty waits for the result to be final
+    assert_eq!(s.hub.released(&s.player1), 60);
+    assert_error(&s.client.try_finalize(&2), Error::WrongPhase);
+}
+
+#[test]
+fn test_upheld_challenge_overturns_result() {
+    let s = setup();
+    s.start_and_commit(1);
+    let config = held_config(&s);
+    s.start_with_config(2, &config);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(2, attacker, tile);
+    }
+
+    // A second valid proof gives player1's tile 10 another type
+    let evidence = Evidence { tile_index: 10, tile_type: 1, proof: s.proof(2, 1, 10, 1) };
+    s.client.pause();
+    s.client.challenge(&2, &evidence);
+
+    let game = s.client.get_game(&2);
+    assert_eq!((game.winner, game.settled), (2, true));
+    assert_eq!(s.hub.ended(&2), Some(false));
+    let result = s.client.get_result(&2).unwrap();
+    assert_eq!((result.winner, result.outcome), (2, Outcome::Overturned));
+    assert!(s.client.reconcile(&soroban_sdk::vec![&s.env, 2]).is_empty());
+}
+
+#[test]
+fn test_fee_is_raked_from_the_winners_pot() {
+    let s = setup();
+    assert_error(&s.client.try_set_fee_bps(&10_001), Error::InvalidConfig);
+    assert_error(&s.client.try_set_fee_bps(&2_501), Error::InvalidConfig);
+    s.client.set_fee_bps(&500);
+    assert_eq!(s.client.get_fee_bps(), 500);
+
+    play_to_win(&s, 1);
+    assert!(s.client.get_game(&1).pooled);
+    assert_eq!(s.hub.locked(&s.player1), 100);
+    assert_eq!(s.hub.released(&s.player1), 190);
+    assert_eq!(s.client.get_fee_balance(), 10);
+
+    let to = Address::generate(&s.env);
+    assert_error(&s.client.try_withdraw_fees(&to, &11), Error::InsufficientBalance);
+    s.client.withdraw_fees(&to, &10);
+    assert_eq!((s.client.get_fee_balance(), s.hub.released(&to)), (0, 10));
+}
+
+#[test]
+fn test_zero_stake_game_never_reaches_the_hub() {
+    let s = setup();
+    s.client.set_tutorial_required(&true);
+    s.client.set_stake_limits(&StakeLimits { min_stake: 10, max_stake: 0, max_ratio: 0 });
+
+    s.client.start_game(&1, &s.player1, &s.player2, &0, &0);
+    s.commit_both(1);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+    assert_eq!(s.client.get_game(&1).winner, 1);
+    assert_eq!(s.hub.end_calls(&1), 0);
+    assert_eq!(s.client.get_settlement_receipt(&1).unwrap().kind, SettlementKind::Win);
+
+    s.client.start_game(&2, &s.player1, &s.player2, &0, &0);
+    s.client.cancel_game(&2, &s.player1);
+    assert!(!s.hub.refunded(&2));
+}
+
+#[test]
+fn test_token_game_escrows_and_pays_out_in_the_contract() {
+    let s = setup();
+    let sac   = s.env.register_stellar_asset_contract_v2(s.admin.clone());
+    let token = TokenClient::new(&s.env, &sac.address());
+    let mint  = StellarAssetClient::new(&s.env, &sac.address());
+    mint.mint(&s.player1, &1_000);
+    mint.mint(&s.player2, &1_000);
+
+    s.client.start_game_with_token(&1, &s.player1, &s.player2, &sac.address(), &250);
+    assert_eq!(token.balance(&s.client.address), 500);
+    s.commit_both(1);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+    assert_eq!((token.balance(&s.player1), token.balance(&s.player2)), (1_250, 750));
+    assert_eq!(token.balance(&s.client.address), 0);
+    assert_eq!((s.hub.end_calls(&1), s.hub.locked(&s.player1)), (0, 0));
+
+    s.client.start_game_with_token(&2, &s.player1, &s.player2, &sac.address(), &100);
+    s.client.cancel_game(&2, &s.player1);
+    assert_eq!((token.balance(&s.player1), token.balance(&s.player2)), (1_250, 750));
+    assert!(!s.hub.refunded(&2));
+}
+
+#[test]
+fn test_multi_asset_game_pays_both_legs_to_the_winner() {
+    let s = setup();
+    let xlm  = s.env.register_stellar_asset_contract_v2(s.admin.clone()).address();
+    let usdc = s.env.register_stellar_asset_contract_v2(s.admin.clone()).address();
+    StellarAssetClient::new(&s.env, &xlm).mint(&s.player1, &1_000);
+    StellarAssetClient::new(&s.env, &usdc).mint(&s.player2, &50);
+    let (xlm_c, usdc_c) = (TokenClient::new(&s.env, &xlm), TokenClient::new(&s.env, &usdc));
+
+    let wager1 = Wager { token: xlm.clone(), amount: 400 };
+    let wager2 = Wager { token: usdc.clone(), amount: 40 };
+    s.client.start_game_with_wagers(&1, &s.player1, &s.player2, &wager1, &wager2);
+    assert_eq!(s.client.get_game(&1).wagers.len(), 2);
+    assert_eq!((xlm_c.balance(&s.client.address), usdc_c.balance(&s.client.address)), (400, 40));
+
+    s.commit_both(1);
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+    assert_eq!((xlm_c.balance(&s.player1), usdc_c.balance(&s.player1)), (1_000, 40));
+    assert_eq!((xlm_c.balance(&s.player2), usdc_c.balance(&s.player2)), (0, 10));
+}
+
+#[test]
+fn test_perfect_game_wins_the_jackpot() {
+    let s = setup();
+    s.client.set_fee_bps(&2_000);
+    assert_error(&s.client.try_set_jackpot_bps(&1_000), Error::InvalidConfig);
+    s.client.set_fee_bps(&0);
+    s.client.set_jackpot_bps(&1_000);
+
+    // player2 finds one of player1's Poison tiles, so player1's win is not perfect
+    s.start_and_commit(1);
+    for (attacker, tile) in [(1, 0), (2, 0), (1, 1), (2, 10), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+    assert!(!s.client.get_game(&1).perfect);
+    assert_eq!(s.client.get_jackpot(), 20);
+    assert_eq!(s.hub.released(&s.player1), 180);
+
+    play_to_win(&s, 2);
+    assert!(s.client.get_game(&2).perfect);
+    assert_eq!(s.client.get_jackpot(), 0);
+    assert_eq!(s.hub.released(&s.player1), 180 + 180 + 40);
+}
+
+#[test]
+fn test_referrer_is_credited_a_share_of_the_fee() {
+    let s = setup();
+    let referrer = Address::generate(&s.env);
+    assert_error(&s.client.try_register_referral(&s.player1, &s.player1), Error::SelfPlay);
+    s.client.register_referral(&s.player1, &referrer);
+    assert_error(&s.client.try_register_referral(&s.player1, &s.player2), Error::AlreadyReferred);
+    assert_eq!(s.client.get_referrer(&s.player1), Some(referrer.clone()));
+
+    assert_error(&s.client.try_set_referral_bps(&5_001), Error::InvalidConfig);
+    s.client.set_referral_bps(&2_000);
+    s.client.set_fee_bps(&1_000);
+    play_to_win(&s, 1);
+    assert_eq!(s.client.get_referral_rewards(&referrer), 4);
+    assert_eq!(s.client.get_fee_balance(), 16);
+
+    assert_eq!(s.client.claim_referral_rewards(&referrer), 4);
+    assert_eq!(s.hub.released(&referrer), 4);
+    assert_eq!(s.client.claim_referral_rewards(&referrer), 0);
+}
+
+#[test]
+fn test_session_settles_on_its_allowlisted_hub() {
+    let s = setup();
+    play_to_win(&s, 1);
+    let hub2_addr = s.env.register(common::MockGameHub, ());
+    let hub2      = common::MockGameHubClient::new(&s.env, &hub2_addr);
+    let mut config = s.client.get_game(&1).config;
+    config.hub = Some(hub2_addr.clone());
+
+    let start = |session_id: u32| s.client.try_start_game_with_config(
+        &session_id, &s.player1, &s.player2, &100, &100, &config,
+    );
+    assert_error(&start(2), Error::InvalidConfig);
+    s.client.allow_hub(&hub2_addr);
+    assert_eq!(s.client.get_allowed_hubs(), vec![&s.env, hub2_addr.clone()]);
+
+    // Live sessions stay on their hub through a rake and a hub change
+    s.client.set_fee_bps(&1_000);
+    s.start_with_config(2, &config);
+    let game = s.client.get_game(&2);
+    assert_eq!(game.hub, hub2_addr);
+    assert_eq!(game.fee_bps, 0);
+    s.client.set_hub(&Address::generate(&s.env));
+    s.play(2, 1, 0);
+    s.play(2, 2, 10);
+    s.play(2, 1, 1);
+    s.play(2, 2, 11);
+    s.play(2, 1, 2);
+    assert_eq!(hub2.ended(&2), Some(true));
+    assert_eq!(s.hub.end_calls(&2), 0);
+    assert_eq!(s.client.get_fee_balance(), 0);
+
+    s.client.disallow_hub(&hub2_addr);
+    assert_error(&start(3), Error::InvalidConfig);
+}
+
+#[test]
+fn test_pools_stay_on_their_hub_after_set_hub() {
+    let s = setup();
+    s.client.set_fee_bps(&500);
+    play_to_win(&s, 1);
+    assert_eq!(s.client.get_fee_balance(), 10);
+
+    // The fee pool is still paid out on the hub that holds it
+    let hub2_addr = s.env.register(common::MockGameHub, ());
+    let hub2      = common::MockGameHubClient::new(&s.env, &hub2_addr);
+    s.client.set_hub(&hub2_addr);
+    let to = Address::generate(&s.env);
+    s.client.withdraw_fees(&to, &4);
+    assert_eq!((s.hub.released(&to), hub2.released(&to)), (4, 0));
+
+    // Sessions on the new hub are not raked into the old one's pool
+    s.start_and_commit(2);
+    assert!(!s.client.get_game(&2).pooled);
+
+    // Once drained, the pools follow the deployment's hub again
+    s.client.withdraw_fees(&to, &6);
+    s.client.set_hub(&hub2_addr);
+    s.client.fund_treasury(&s.player1, &50);
+    assert_eq!(hub2.locked(&s.player1), 50);
+}
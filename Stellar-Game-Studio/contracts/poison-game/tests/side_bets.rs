@@ -0,0 +1,80 @@
+// Spectators back a player before the cutoff and split the losing pool.
+
+mod common;
+
+use common::{assert_error, setup};
+use poison_game::{DataKey, Error, FEATURE_SIDE_BETS};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::Address;
+
+#[test]
+fn test_winning_backers_split_the_losing_pool() {
+    let s = setup();
+    let token = s.env.register_stellar_asset_contract_v2(s.admin.clone()).address();
+    let balance = TokenClient::new(&s.env, &token);
+    let (alice, bob, carol) = (Address::generate(&s.env), Address::generate(&s.env), Address::generate(&s.env));
+    for bettor in [&alice, &bob, &carol] {
+        StellarAssetClient::new(&s.env, &token).mint(bettor, &1_000);
+    }
+
+    s.start_and_commit(1);
+    assert_error(&s.client.try_place_bet(&1, &alice, &1, &100), Error::FeatureDisabled);
+    s.client.set_bet_market(&token, &2);
+    s.client.set_disabled_features(&FEATURE_SIDE_BETS);
+    assert_error(&s.client.try_place_bet(&1, &alice, &1, &100), Error::FeatureDisabled);
+    s.client.set_disabled_features(&0);
+
+    assert_error(&s.client.try_place_bet(&1, &s.player2, &1, &100), Error::SelfPlay);
+    s.client.place_bet(&1, &alice, &1, &100);
+    s.client.place_bet(&1, &bob, &1, &300);
+    s.client.place_bet(&1, &carol, &2, &200);
+    assert_error(&s.client.try_place_bet(&1, &carol, &1, &10), Error::InvalidConfig);
+
+    s.play(1, 1, 0);
+    s.play(1, 2, 10);
+    assert_error(&s.client.try_place_bet(&1, &carol, &2, &10), Error::BettingClosed);
+    assert_error(&s.client.try_claim_bet(&1, &alice), Error::WrongPhase);
+    for (attacker, tile) in [(1, 1), (2, 11), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+
+    assert_eq!(s.client.claim_bet(&1, &alice), 150);
+    assert_eq!(s.client.claim_bet(&1, &bob), 450);
+    assert_eq!(s.client.claim_bet(&1, &carol), 0);
+    assert_error(&s.client.try_claim_bet(&1, &alice), Error::BetNotFound);
+    assert_eq!((balance.balance(&alice), balance.balance(&bob), balance.balance(&carol)), (1_050, 1_150, 800));
+}
+
+#[test]
+fn test_bets_on_a_cancelled_session_are_returned() {
+    let s = setup();
+    let token = s.env.register_stellar_asset_contract_v2(s.admin.clone()).address();
+    let alice = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &token).mint(&alice, &1_000);
+    s.client.set_bet_market(&token, &5);
+
+    s.client.start_game(&1, &s.player1, &s.player2, &100, &100);
+    s.client.place_bet(&1, &alice, &2, &250);
+    s.client.cancel_game(&1, &s.player1);
+    assert_eq!(s.client.claim_bet(&1, &alice), 250);
+    assert_eq!(TokenClient::new(&s.env, &token).balance(&alice), 1_000);
+}
+
+#[test]
+fn test_bets_on_an_expired_session_are_returned() {
+    let s = setup();
+    let token = s.env.register_stellar_asset_contract_v2(s.admin.clone()).address();
+    let alice = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &token).mint(&alice, &1_000);
+    s.client.set_bet_market(&token, &5);
+
+    s.start_and_commit(1);
+    s.client.place_bet(&1, &alice, &1, &250);
+    assert_error(&s.client.try_claim_bet(&1, &alice), Error::WrongPhase);
+
+    // The live entry lapses without the session ever settling
+    s.env.as_contract(&s.client.address, || s.env.storage().temporary().remove(&DataKey::Game(1)));
+    assert_eq!(s.client.claim_bet(&1, &alice), 250);
+    assert_eq!(TokenClient::new(&s.env, &token).balance(&alice), 1_000);
+}
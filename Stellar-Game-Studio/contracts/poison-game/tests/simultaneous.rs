@@ -0,0 +1,122 @@
+// Simultaneous rounds: both players commit, reveal and answer an attack
+// each round; completing in the same round is a refunded draw, and a
+// stalled step can be claimed by forfeit.
+
+mod common;
+
+use common::{assert_error, setup, Setup, BOARD};
+use poison_game::testutils::attack_commitment;
+use poison_game::{CommitmentScheme, Error, GameConfig, Phase, RoundStep, WinCondition};
+use soroban_sdk::{vec, BytesN, Env};
+
+fn simultaneous_config(env: &Env) -> GameConfig {
+    GameConfig {
+        poison_count:      2,
+        shield_count:      1,
+        player2_hub:       None,
+        reveal_delay:      0,
+        broadcast:         false,
+        rules_hash:        None,
+        time_bank:         0,
+        salvo_size:        0,
+        poison_penalty:    false,
+        max_bonus_attacks: 0,
+        decoy_count:       0,
+        lite:              false,
+        response_window:   0,
+        slash_bps:         0,
+        dispute_window:    0,
+        simultaneous:      true,
+        max_rounds:        0,
+        win_condition:     WinCondition::AllSpecials,
+        board_poison:      vec![env],
+        commitment_scheme: CommitmentScheme::Pedersen,
+        commit_window:     0,
+        hub:               None,
+    }
+}
+
+/// Player1 attacks `tile1` on player2's board and player2 attacks `tile2` on player1's.
+fn round(s: &Setup, tile1: u32, tile2: u32) {
+    let salt = BytesN::from_array(&s.env, &[5u8; 32]);
+    s.client.commit_attack(&1, &s.player2, &attack_commitment(&s.env, tile2, &salt));
+    s.client.commit_attack(&1, &s.player1, &attack_commitment(&s.env, tile1, &salt));
+    s.client.reveal_attack(&1, &s.player1, &tile1, &salt);
+    s.client.reveal_attack(&1, &s.player2, &tile2, &salt);
+    s.client.respond_round(&1, &s.player2, &BOARD[tile1 as usize], &s.proof(1, 2, tile1, BOARD[tile1 as usize]));
+    s.client.respond_round(&1, &s.player1, &BOARD[tile2 as usize], &s.proof(1, 1, tile2, BOARD[tile2 as usize]));
+}
+
+#[test]
+fn test_first_to_find_every_special_wins() {
+    let s = setup();
+    s.start_with_config(1, &simultaneous_config(&s.env));
+    assert_error(&s.client.try_attack(&1, &s.player1, &0), Error::WrongPhase);
+
+    round(&s, 0, 5);
+    round(&s, 2, 6);
+    let game = s.client.get_game(&1);
+    assert_eq!((game.round.step, game.p1_revealed.len(), game.p2_revealed.len()), (RoundStep::CommitAttacks, 2, 2));
+
+    round(&s, 1, 7);
+    let game = s.client.get_game(&1);
+    assert_eq!((game.phase, game.winner), (Phase::Finished, 1));
+    assert_eq!(s.hub.ended(&1), Some(true));
+}
+
+#[test]
+fn test_completing_in_the_same_round_is_a_draw() {
+    let s = setup();
+    s.start_with_config(1, &simultaneous_config(&s.env));
+    round(&s, 0, 0);
+    round(&s, 1, 1);
+    round(&s, 2, 2);
+
+    let game = s.client.get_game(&1);
+    assert_eq!((game.phase, game.winner), (Phase::Finished, 0));
+    assert!(s.hub.refunded(&1));
+    assert_eq!(s.hub.ended(&1), None);
+}
+
+#[test]
+fn test_round_steps_are_enforced() {
+    let s = setup();
+    let mut config = simultaneous_config(&s.env);
+    config.salvo_size = 2;
+    assert_error(&s.client.try_start_game_with_config(&1, &s.player1, &s.player2, &100, &100, &config), Error::InvalidConfig);
+
+    s.start_with_config(1, &simultaneous_config(&s.env));
+    let salt = BytesN::from_array(&s.env, &[5u8; 32]);
+    assert_error(&s.client.try_reveal_attack(&1, &s.player1, &0, &salt), Error::NotYourTurn);
+    s.client.commit_attack(&1, &s.player1, &attack_commitment(&s.env, 0, &salt));
+    assert_error(&s.client.try_commit_attack(&1, &s.player1, &attack_commitment(&s.env, 1, &salt)), Error::NotYourTurn);
+    s.client.commit_attack(&1, &s.player2, &attack_commitment(&s.env, 3, &salt));
+
+    // The revealed tile must be the committed one
+    assert_error(&s.client.try_reveal_attack(&1, &s.player1, &1, &salt), Error::AttackMismatch);
+    s.client.reveal_attack(&1, &s.player1, &0, &salt);
+    assert_eq!(s.client.get_game(&1).round.step, RoundStep::RevealAttacks);
+}
+
+#[test]
+fn test_stalled_round_step_is_forfeited() {
+    let s = setup();
+    let mut config = simultaneous_config(&s.env);
+    config.response_window = 10;
+    s.start_with_config(1, &config);
+    assert_error(&s.client.try_claim_nonresponse(&1), Error::WrongPhase);
+
+    let salt = BytesN::from_array(&s.env, &[5u8; 32]);
+    s.client.commit_attack(&1, &s.player1, &attack_commitment(&s.env, 0, &salt));
+    s.client.commit_attack(&1, &s.player2, &attack_commitment(&s.env, 5, &salt));
+    s.client.reveal_attack(&1, &s.player2, &5, &salt);
+
+    // Player1 never reveals; player2 takes the game once the window lapses
+    s.advance(10);
+    assert_error(&s.client.try_claim_nonresponse(&1), Error::ClockNotExpired);
+    s.advance(1);
+    s.client.claim_nonresponse(&1);
+    let game = s.client.get_game(&1);
+    assert_eq!((game.phase, game.winner, game.forfeited), (Phase::Finished, 2, 1));
+    assert_eq!(s.hub.ended(&1), Some(false));
+}
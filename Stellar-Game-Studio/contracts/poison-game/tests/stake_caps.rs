@@ -0,0 +1,56 @@
+// Player-side stake caps: lowering one applies at once, raising or
+// clearing it waits out the delay.
+
+mod common;
+
+use common::{assert_error, setup};
+use poison_game::{Error, PendingStakeCap, SeriesPayout, STAKE_CAP_DELAY_LEDGERS};
+
+#[test]
+fn test_stake_cap_binds_globally_and_per_opponent() {
+    let s = setup();
+    assert_error(&s.client.try_set_stake_cap(&s.player1, &None, &Some(-1)), Error::InvalidConfig);
+    s.client.set_stake_cap(&s.player1, &None, &Some(200));
+    s.client.set_stake_cap(&s.player1, &Some(s.player2.clone()), &Some(100));
+    assert_eq!(s.client.get_stake_cap(&s.player1, &None), Some(200));
+
+    assert_error(&s.client.try_start_game(&1, &s.player1, &s.player2, &101, &100), Error::StakeCapExceeded);
+    let result = s.client.try_start_series(&1, &s.player1, &s.player2, &3, &150, &150, &SeriesPayout::PerGame);
+    assert_error(&result, Error::StakeCapExceeded);
+    s.client.start_game(&1, &s.player1, &s.player2, &100, &500);
+}
+
+#[test]
+fn test_raising_a_stake_cap_waits_out_the_delay() {
+    let s = setup();
+    s.client.set_stake_cap(&s.player1, &None, &Some(100));
+    s.client.set_stake_cap(&s.player1, &None, &Some(500));
+
+    let effective_at = s.env.ledger().sequence() + STAKE_CAP_DELAY_LEDGERS;
+    assert_eq!(
+        s.client.get_pending_stake_cap(&s.player1, &None),
+        Some(PendingStakeCap { max_stake: Some(500), effective_at }),
+    );
+    assert_eq!(s.client.get_stake_cap(&s.player1, &None), Some(100));
+    assert_error(&s.client.try_start_game(&1, &s.player1, &s.player2, &300, &100), Error::StakeCapExceeded);
+
+    s.advance(STAKE_CAP_DELAY_LEDGERS);
+    assert_eq!(s.client.get_stake_cap(&s.player1, &None), Some(500));
+    s.client.start_game(&1, &s.player1, &s.player2, &300, &100);
+}
+
+#[test]
+fn test_lowering_a_stake_cap_applies_at_once_and_drops_a_queued_raise() {
+    let s = setup();
+    s.client.set_stake_cap(&s.player1, &None, &Some(100));
+    // Clearing is a raise too
+    s.client.set_stake_cap(&s.player1, &None, &None);
+    assert_eq!(s.client.get_stake_cap(&s.player1, &None), Some(100));
+
+    s.client.set_stake_cap(&s.player1, &None, &Some(50));
+    assert_eq!(s.client.get_stake_cap(&s.player1, &None), Some(50));
+    assert_eq!(s.client.get_pending_stake_cap(&s.player1, &None), None);
+    s.advance(STAKE_CAP_DELAY_LEDGERS);
+    assert_eq!(s.client.get_stake_cap(&s.player1, &None), Some(50));
+    assert_error(&s.client.try_start_game(&1, &s.player1, &s.player2, &51, &50), Error::StakeCapExceeded);
+}
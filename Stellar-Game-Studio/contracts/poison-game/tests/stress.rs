@@ -0,0 +1,27 @@
+// Burn-in stress mode: synthetic sessions resolve without proofs or a hub.
+
+#![cfg(feature = "stress")]
+
+mod common;
+
+use common::{assert_error, setup};
+use poison_game::{Error, Phase, SettlementKind, MAX_STRESS_SESSIONS};
+
+#[test]
+fn test_stress_run_resolves_synthetic_sessions() {
+    let s = setup();
+    s.client.stress_run(&100, &2);
+    s.client.stress_run(&102, &1);
+
+    for session_id in 100..103 {
+        let game = s.client.get_game(&session_id);
+        assert_eq!((game.phase, game.winner, game.move_count), (Phase::Finished, 1, 12));
+        assert_eq!(s.client.get_move_log(&session_id).len(), 5);
+        assert_eq!(s.client.get_settlement_receipt(&session_id).unwrap().kind, SettlementKind::Win);
+        assert_eq!(s.hub.end_calls(&session_id), 0);
+    }
+    assert!(s.client.reconcile(&soroban_sdk::vec![&s.env, 100, 101, 102]).is_empty());
+
+    assert_error(&s.client.try_stress_run(&102, &1), Error::SessionExists);
+    assert_error(&s.client.try_stress_run(&200, &(MAX_STRESS_SESSIONS + 1)), Error::InsufficientResources);
+}
@@ -0,0 +1,48 @@
+// The scripted tutorial and the ranked-entry gate it unlocks.
+
+mod common;
+
+use common::{assert_error, setup, Setup};
+use poison_game::{Error, SeriesPayout};
+use soroban_sdk::Address;
+
+/// Poison at 2 and 13, Shield at 6 on the scripted board.
+fn complete_tutorial(s: &Setup, player: &Address) {
+    s.client.start_tutorial(player);
+    for tile in [2, 6, 13] { s.client.tutorial_attack(player, &tile); }
+}
+
+#[test]
+fn test_tutorial_answers_from_the_script_and_completes() {
+    let s = setup();
+    assert_error(&s.client.try_tutorial_attack(&s.player1, &0), Error::TutorialNotStarted);
+
+    s.client.start_tutorial(&s.player1);
+    assert_eq!(s.client.tutorial_attack(&s.player1, &0), 0);
+    assert_eq!(s.client.tutorial_attack(&s.player1, &2), 1);
+    assert_eq!(s.client.tutorial_attack(&s.player1, &6), 2);
+    assert_error(&s.client.try_tutorial_attack(&s.player1, &2), Error::TileAlreadyRevealed);
+    assert_error(&s.client.try_tutorial_attack(&s.player1, &15), Error::InvalidTileIndex);
+    assert_eq!(s.client.get_tutorial(&s.player1).unwrap().moves, 3);
+    assert!(!s.client.has_completed_tutorial(&s.player1));
+
+    assert_eq!(s.client.tutorial_attack(&s.player1, &13), 1);
+    assert!(s.client.has_completed_tutorial(&s.player1));
+    assert_eq!(s.client.get_tutorial(&s.player1), None);
+}
+
+#[test]
+fn test_required_tutorial_gates_staked_play() {
+    let s = setup();
+    s.client.set_tutorial_required(&true);
+    complete_tutorial(&s, &s.player1);
+
+    assert_error(&s.client.try_start_game(&1, &s.player1, &s.player2, &100, &100), Error::TutorialRequired);
+    let result = s.client.try_start_series(&1, &s.player1, &s.player2, &3, &90, &90, &SeriesPayout::PerGame);
+    assert_error(&result, Error::TutorialRequired);
+    // Friendly games are the onboarding path
+    s.client.start_game(&2, &s.player1, &s.player2, &0, &0);
+
+    complete_tutorial(&s, &s.player2);
+    s.client.start_game(&1, &s.player1, &s.player2, &100, &100);
+}
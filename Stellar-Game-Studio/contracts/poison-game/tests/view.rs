@@ -0,0 +1,31 @@
+// The read-only PoisonGameView surface, driven only through `PoisonGameViewClient`.
+
+mod common;
+
+use common::setup;
+use poison_game::{Phase, PoisonGameViewClient};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::Address;
+
+#[test]
+fn test_view_client_follows_a_session() {
+    let s = setup();
+    let view = PoisonGameViewClient::new(&s.env, &s.client.address);
+    assert_eq!((view.get_phase(&1), view.get_winner(&1)), (None, 0));
+
+    s.client.start_game(&1, &s.player1, &s.player2, &100, &100);
+    assert_eq!(view.get_phase(&1), Some(Phase::WaitingForCommits));
+    assert!(view.is_player(&1, &s.player2));
+    assert!(!view.is_player(&1, &Address::generate(&s.env)));
+
+    s.commit_both(1);
+    assert_eq!(view.get_phase(&1), Some(Phase::Playing));
+    for (attacker, tile) in [(1, 0), (2, 10), (1, 1), (2, 11), (1, 2)] {
+        s.play(1, attacker, tile);
+    }
+    assert_eq!((view.get_phase(&1), view.get_winner(&1)), (Some(Phase::Finished), 1));
+
+    s.client.start_game(&2, &s.player1, &s.player2, &100, &100);
+    s.client.cancel_game(&2, &s.player1);
+    assert_eq!((view.get_phase(&2), view.get_winner(&2)), (Some(Phase::Finished), 0));
+}
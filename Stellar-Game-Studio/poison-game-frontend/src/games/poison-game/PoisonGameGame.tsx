@@ -285,7 +285,7 @@ export function PoisonGameGame({
     setError(null);
     try {
       const salt       = generateSalt();
-      const commitment = await computeBoardHash(myBoard, salt);
+      const commitment = await computeBoardHash(myBoard, salt, myAddress, sessionId);
       const signer     = getContractSigner();
 
       saveBoard(sessionId, myAddress, myBoard, salt, commitment);
@@ -346,7 +346,7 @@ export function PoisonGameGame({
 
         setZkProgress('Generating ZK proof... (~5–20 seconds)');
         const proof = await generateTileProof(
-          boardData.tiles, boardData.salt, boardData.commitment, pendingTile, tileType
+          boardData.tiles, boardData.salt, boardData.commitment, myAddress, sessionId, pendingTile, tileType
         );
 
         setZkProgress('Submitting proof on-chain...');
@@ -143,7 +143,12 @@ export interface Client {
 
   /**
    * Construct and simulate a commit_board transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
-   * board_hash = pedersen_hash([tile0..tile14, salt]) computed in the browser.
+   * board_hash = pedersen_hash([tile0..tile14, salt, owner, session_id])
+   * computed in the browser (poseidon_hash for Poseidon-committed games),
+   * where `owner` is the seat's address as the game was opened, encoded
+   * like the proofs' public inputs (see circuits/poison_lib/src/commitment.nr).
+   * Binding both stops a player mirroring the opponent's commitment or
+   * reusing a board across sessions.
    * Once both players commit, phase moves to Playing.
    */
   commit_board: ({session_id, player, board_hash}: {session_id: u32, player: string, board_hash: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
@@ -1,16 +1,33 @@
 /**
  * zkPoisonEngine.ts — ZK engine for Poison Game
- * Circuit: poison_game (15 tiles, 2 poison + 1 shield)
+ * Circuit: poison_game (15 tiles, 2 poison + 1 shield by default)
  * Backend: UltraHonk, keccak oracle, bb v0.87.0
  */
 
 import { Noir } from '@noir-lang/noir_js';
 import { UltraHonkBackend, Barretenberg, Fr } from '@aztec/bb.js';
 import type { CompiledCircuit } from '@noir-lang/noir_js';
+import { Address, hash } from '@stellar/stellar-sdk';
 import { Buffer } from 'buffer';
 
 export type TileType = 0 | 1 | 2;
 
+// Default rules; games opened with a custom config pass their own counts
+export const DEFAULT_POISON = 2;
+export const DEFAULT_SHIELD = 1;
+
+/**
+ * Encode an address as a field element the way the contract does:
+ * sha256 of its ScVal XDR with the top byte cleared.
+ */
+export const addressField = (address: string): bigint => {
+  const digest = Buffer.from(hash(Address.fromString(address).toScVal().toXDR()));
+  digest[0] = 0;
+  return BigInt('0x' + digest.toString('hex'));
+};
+
+const field = (value: bigint | number): string => `0x${BigInt(value).toString(16).padStart(64, '0')}`;
+
 export interface ZkProofEngineStatus {
   initialized: boolean;
   circuitLoaded: boolean;
@@ -72,17 +89,21 @@ class ZkProofEngine {
   }
 
   /**
-   * Compute pedersen_hash([tile0..tile14, salt]).
+   * Compute pedersen_hash([tile0..tile14, salt, owner, session_id]).
    * Must match exactly what Noir's std::hash::pedersen_hash produces.
+   * `owner` is the committing player's address; binding it and the session
+   * stops the opponent mirroring the commitment or a board being replayed.
    * Returns 32-byte Buffer — pass directly to commit_board().
    */
-  async computeBoardHash(tiles: TileType[], salt: bigint): Promise<Buffer> {
+  async computeBoardHash(tiles: TileType[], salt: bigint, owner: string, sessionId: number): Promise<Buffer> {
     await this.init();
     if (!this.bb) throw new Error('Barretenberg not initialised');
     if (tiles.length !== 15) throw new Error(`Need 15 tiles, got ${tiles.length}`);
 
     const inputs: Fr[] = tiles.map(t => new Fr(BigInt(t)));
     inputs.push(new Fr(salt));
+    inputs.push(new Fr(addressField(owner)));
+    inputs.push(new Fr(BigInt(sessionId)));
 
     const hashFr = await this.bb.pedersenHash(inputs, 0);
     return Buffer.from(hashFr.toBuffer());
@@ -96,10 +117,14 @@ class ZkProofEngine {
    *   board_layout — full 15-tile layout
    *   salt         — random value used in commitment
    *
-   * Public inputs (verified on-chain):
+   * Public inputs (verified on-chain, in this order):
    *   commitment       — pedersen hash stored at commit_board time
+   *   owner            — the defender's address field
+   *   session_id       — the session the board was committed in
    *   tile_index       — which tile was attacked
    *   tile_type_result — what type that tile is (0=Normal, 1=Poison, 2=Shield)
+   *   poison_count     — Poison the board must hide (this board's, under a handicap)
+   *   shield_count     — Shield the board must hide
    *
    * Returns 14592-byte Uint8Array — pass directly to respond_to_attack().
    */
@@ -107,8 +132,12 @@ class ZkProofEngine {
     tiles: TileType[],
     salt: bigint,
     commitment: Buffer,
+    owner: string,
+    sessionId: number,
     tileIndex: number,
     tileType: TileType,
+    poisonCount: number = DEFAULT_POISON,
+    shieldCount: number = DEFAULT_SHIELD,
   ): Promise<Uint8Array> {
     await this.init();
     if (!this.noir || !this.backend) throw new Error('Engine not initialised');
@@ -116,13 +145,16 @@ class ZkProofEngine {
     if (tileIndex < 0 || tileIndex > 14) throw new Error('Invalid tile index');
     if (tiles[tileIndex] !== tileType) throw new Error('Tile type mismatch — check board data');
 
-   
     const inputs = {
-      board_layout: tiles.map(t => `0x${BigInt(t).toString(16).padStart(64, '0')}`),
-      salt: `0x${salt.toString(16).padStart(64, '0')}`,
+      board_layout: tiles.map(t => field(t)),
+      salt: field(salt),
       commitment: '0x' + commitment.toString('hex'),
+      owner: field(addressField(owner)),
+      session_id: sessionId,
       tile_index: tileIndex,
       tile_type_result: tileType,
+      poison_count: poisonCount,
+      shield_count: shieldCount,
     };
 
     const t0 = performance.now();
@@ -163,16 +195,18 @@ class ZkProofEngine {
 export const zkProofEngine = new ZkProofEngine();
 
 // ── Exported helpers ─────────────────────────
-export const computeBoardHash = (tiles: TileType[], salt: bigint) =>
-  zkProofEngine.computeBoardHash(tiles, salt);
+export const computeBoardHash = (tiles: TileType[], salt: bigint, owner: string, sessionId: number) =>
+  zkProofEngine.computeBoardHash(tiles, salt, owner, sessionId);
 
 export const generateTileProof = (
   tiles: TileType[],
   salt: bigint,
   commitment: Buffer,
+  owner: string,
+  sessionId: number,
   tileIndex: number,
   tileType: TileType,
-) => zkProofEngine.generateTileProof(tiles, salt, commitment, tileIndex, tileType);
+) => zkProofEngine.generateTileProof(tiles, salt, commitment, owner, sessionId, tileIndex, tileType);
 
 export const generateSalt = (): bigint => {
   const bytes = crypto.getRandomValues(new Uint8Array(31));
@@ -184,7 +218,7 @@ export const validateBoard = (tiles: TileType[]): { valid: boolean; error?: stri
   if (tiles.some(t => t !== 0 && t !== 1 && t !== 2)) return { valid: false, error: 'Invalid tile values' };
   const poisons = tiles.filter(t => t === 1).length;
   const shields = tiles.filter(t => t === 2).length;
-  if (poisons !== 2) return { valid: false, error: `Need exactly 2 poison tiles, got ${poisons}` };
-  if (shields !== 1) return { valid: false, error: `Need exactly 1 shield tile, got ${shields}` };
+  if (poisons !== DEFAULT_POISON) return { valid: false, error: `Need exactly ${DEFAULT_POISON} poison tiles, got ${poisons}` };
+  if (shields !== DEFAULT_SHIELD) return { valid: false, error: `Need exactly ${DEFAULT_SHIELD} shield tile, got ${shields}` };
   return { valid: true };
 };
\ No newline at end of file